@@ -0,0 +1,110 @@
+use anyhow::Result;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::resume_state::retry_backoff_secs;
+
+/// One dispatchable unit of work, re-enqueued by id up to `max_tries` times
+/// on failure.
+struct QueuedItem<T> {
+    chunk_id: usize,
+    payload: T,
+    attempt: u32,
+}
+
+/// Modeled on Av1an's `Broker`/`determine_workers`: dispatches arbitrary
+/// per-chunk work (Phase 1 splitting, Phase 2 QR extraction) across a fixed
+/// pool of worker threads via a bounded channel, retrying a failed chunk up
+/// to `max_tries` times - with the same exponential backoff `ResumeState`
+/// already uses - before giving up on it, so one bad chunk never stalls or
+/// aborts the rest of the job.
+pub struct ChunkBroker {
+    worker_count: usize,
+    max_tries: u32,
+}
+
+impl ChunkBroker {
+    pub fn new(worker_count: usize, max_tries: u32) -> Self {
+        Self {
+            worker_count: worker_count.max(1),
+            max_tries: max_tries.max(1),
+        }
+    }
+
+    /// Run `work` once per `(chunk_id, payload)` in `items`, dispatched
+    /// across this broker's worker pool. Each worker is tagged with its own
+    /// `consumer_idx` (0..worker_count), passed through to `work` so callers
+    /// can report which worker slot is handling a chunk - e.g. for the TUI's
+    /// per-worker lane view. `on_success`/`on_failure` fire from whichever
+    /// worker thread actually finished that chunk - `on_success` once `work`
+    /// returns `Ok`, `on_failure` only once a chunk has exhausted
+    /// `max_tries` attempts. `on_retry` fires once per failed-but-not-yet-
+    /// abandoned attempt, before the backoff delay, so a caller can surface
+    /// it (e.g. `ProcessingEvent::ChunkRetry`) or log it without having to
+    /// infer a retry from the gap between two `on_failure`/`on_success` calls.
+    pub fn run<T, R, F, S, E, RT>(&self, items: Vec<(usize, T)>, work: F, on_success: S, on_failure: E, on_retry: RT)
+    where
+        T: Send,
+        R: Send,
+        F: Fn(usize, &T, usize) -> Result<R> + Sync,
+        S: Fn(usize, &T, R) + Sync,
+        E: Fn(usize, &T, &str, u32) + Sync,
+        RT: Fn(usize, &T, u32, u32, &str) + Sync,
+    {
+        let total = items.len();
+        if total == 0 {
+            return;
+        }
+
+        let (tx, rx) = crossbeam_channel::bounded::<QueuedItem<T>>(total);
+        let pending = Mutex::new(total);
+
+        for (chunk_id, payload) in items {
+            tx.send(QueuedItem { chunk_id, payload, attempt: 0 }).ok();
+        }
+
+        std::thread::scope(|scope| {
+            for consumer_idx in 0..self.worker_count {
+                let rx = rx.clone();
+                let tx = tx.clone();
+                let pending = &pending;
+                let work = &work;
+                let on_success = &on_success;
+                let on_failure = &on_failure;
+                let max_tries = self.max_tries;
+
+                scope.spawn(move || loop {
+                    if *pending.lock().unwrap() == 0 {
+                        break;
+                    }
+
+                    let received = rx.recv_timeout(Duration::from_millis(50));
+                    let mut item = match received {
+                        Ok(item) => item,
+                        Err(crossbeam_channel::RecvTimeoutError::Timeout) => continue,
+                        Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break,
+                    };
+
+                    match work(item.chunk_id, &item.payload, consumer_idx) {
+                        Ok(result) => {
+                            on_success(item.chunk_id, &item.payload, result);
+                            *pending.lock().unwrap() -= 1;
+                        }
+                        Err(e) => {
+                            item.attempt += 1;
+                            if item.attempt >= max_tries {
+                                on_failure(item.chunk_id, &item.payload, &e.to_string(), item.attempt);
+                                *pending.lock().unwrap() -= 1;
+                            } else {
+                                on_retry(item.chunk_id, &item.payload, item.attempt, max_tries, &e.to_string());
+                                let delay = retry_backoff_secs(item.attempt - 1, 1, 30);
+                                std::thread::sleep(Duration::from_secs(delay));
+                                tx.send(item).ok();
+                            }
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::video::VideoChunk;
+
+/// One planned unit of work, persisted to `chunk_queue.json` right after
+/// Phase 1 splitting so Phase 2/resume logic never has to re-derive it (or
+/// guess at how many chunks exist) from a hardcoded id range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkQueueEntry {
+    pub id: usize,
+    pub chunk_path: String,
+    pub jsonl_path: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// The full plan for a run, modeled on Av1an's `save_chunk_queue`/
+/// `read_chunk_queue`: written once after splitting, read back on resume
+/// instead of globbing `chunk_NNN.*` over a fixed range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkQueue {
+    pub chunks: Vec<ChunkQueueEntry>,
+}
+
+impl ChunkQueue {
+    pub fn from_video_chunks(chunks: &[VideoChunk], output_dir: &PathBuf) -> Self {
+        let entries = chunks
+            .iter()
+            .map(|chunk| ChunkQueueEntry {
+                id: chunk.id,
+                chunk_path: chunk.path.to_string_lossy().to_string(),
+                jsonl_path: output_dir
+                    .join(format!("chunk_{:03}.jsonl", chunk.id + 1))
+                    .to_string_lossy()
+                    .to_string(),
+                start_time: chunk.start_time,
+                end_time: chunk.end_time,
+            })
+            .collect();
+
+        Self { chunks: entries }
+    }
+
+    fn queue_path(output_dir: &PathBuf) -> PathBuf {
+        output_dir.join("chunk_queue.json")
+    }
+
+    pub fn save(&self, output_dir: &PathBuf) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&Self::queue_path(output_dir), &content)
+    }
+
+    pub fn load(output_dir: &PathBuf) -> Result<Option<Self>> {
+        let path = Self::queue_path(output_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+}
+
+/// Recorded once a chunk's JSONL has been fully flushed: the completion
+/// flag itself is just membership in `DoneManifest::done`, this carries the
+/// QR count so status reporting doesn't need to re-read every JSONL file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkCompletion {
+    pub qr_codes_found: usize,
+}
+
+/// The set of chunk ids whose JSONL has been fully flushed, persisted to
+/// `done.json` and updated via `mark_done` as each chunk finishes. Mirrors
+/// Av1an's `get_done`/`init_done`: workers record completion here rather
+/// than Phase 3 having to infer it from file existence alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DoneManifest {
+    done: HashMap<usize, ChunkCompletion>,
+}
+
+impl DoneManifest {
+    fn manifest_path(output_dir: &PathBuf) -> PathBuf {
+        output_dir.join("done.json")
+    }
+
+    /// Load `done.json` if present (resuming a previous run), otherwise
+    /// start with an empty set.
+    pub fn load_or_create(output_dir: &PathBuf) -> Result<Self> {
+        let path = Self::manifest_path(output_dir);
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn is_done(&self, chunk_id: usize) -> bool {
+        self.done.contains_key(&chunk_id)
+    }
+
+    pub fn qr_codes_found(&self, chunk_id: usize) -> Option<usize> {
+        self.done.get(&chunk_id).map(|c| c.qr_codes_found)
+    }
+
+    pub fn pending<'a>(&self, queue: &'a ChunkQueue) -> Vec<&'a ChunkQueueEntry> {
+        queue
+            .chunks
+            .iter()
+            .filter(|entry| !self.is_done(entry.id))
+            .collect()
+    }
+
+    /// Record `chunk_id` as done (with the QR count found in it) and persist
+    /// atomically via a write-to-temp-then-rename so a crash mid-write never
+    /// leaves a truncated `done.json` behind. Callers sharing a manifest
+    /// across threads should hold it behind a `Mutex` so insert-then-save
+    /// stays a single atomic step from every worker's point of view.
+    pub fn mark_done(&mut self, output_dir: &PathBuf, chunk_id: usize, qr_codes_found: usize) -> Result<()> {
+        self.done.insert(chunk_id, ChunkCompletion { qr_codes_found });
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&Self::manifest_path(output_dir), &content)
+    }
+}
+
+fn write_atomic(path: &PathBuf, content: &str) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// List `chunk_*.jsonl` and `chunk_*.mp4` files left in `output_dir` from a
+/// previous run, without assuming any bound on how many chunks there were.
+pub fn scan_existing_chunk_files(output_dir: &PathBuf) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut jsonl_files = Vec::new();
+    let mut mp4_files = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(output_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let is_chunk_file = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.starts_with("chunk_"))
+                .unwrap_or(false);
+
+            if !is_chunk_file {
+                continue;
+            }
+
+            match path.extension().and_then(|s| s.to_str()) {
+                Some("jsonl") => jsonl_files.push(path),
+                Some("mp4") => mp4_files.push(path),
+                _ => {}
+            }
+        }
+    }
+
+    (jsonl_files, mp4_files)
+}
@@ -1,9 +1,13 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::io::BufRead;
 use std::path::PathBuf;
 use std::fs;
+use std::time::{Duration, Instant};
+use crossbeam_queue::ArrayQueue;
 use crate::qr_extraction::QrCodeData;
 use crate::error_logger::ErrorLogger;
+use crate::events::{EventCallback, ProcessingEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkCompletionInfo {
@@ -16,6 +20,40 @@ pub struct ChunkCompletionInfo {
     pub completion_percentage: f64,
     pub is_complete: bool,
     pub completion_reason: String,
+    /// Present when at least one `M:` packet was seen in the chunk, letting
+    /// completion be judged by which payload indices the QR stream actually
+    /// carried rather than by frame-count heuristics.
+    pub content_completion: Option<CompletionInfo>,
+}
+
+/// What the decoded QR payloads themselves say about completeness: how many
+/// of the `0..payload_total` chunk indices advertised by the `M:` packet's
+/// `chunks_count` have actually shown up in `D:`/`R:` packets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionInfo {
+    pub payload_indices_seen: Vec<usize>,
+    pub payload_total: usize,
+    pub missing_indices: Vec<usize>,
+}
+
+/// A JSONL line that failed `serde_json::from_str::<QrCodeData>`, recorded
+/// rather than silently dropped so a half-corrupt chunk doesn't look
+/// deceptively complete by line count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MalformedLine {
+    pub line_number: usize,
+    pub raw_text: String,
+    pub parse_error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkIntegrityReport {
+    pub chunk_id: usize,
+    pub total_lines: usize,
+    pub valid_lines: usize,
+    pub malformed_lines: Vec<MalformedLine>,
+    /// Number of distinct `frame_number` values that appeared more than once.
+    pub duplicate_frame_count: usize,
 }
 
 pub struct CompletionDetector {
@@ -24,6 +62,7 @@ pub struct CompletionDetector {
     frame_rate: f64,
     chunk_count: usize,
     skip_frames: usize,
+    num_threads: usize,
     logger: ErrorLogger,
 }
 
@@ -42,36 +81,49 @@ impl CompletionDetector {
             frame_rate,
             chunk_count,
             skip_frames,
+            num_threads: 1,
             logger,
         })
     }
 
+    /// Use `num_threads` workers for `analyze_all_chunks_parallel`/
+    /// `get_all_resume_points_parallel` instead of the serial default.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads.max(1);
+        self
+    }
+
     pub fn analyze_chunk_completion(&self, chunk_id: usize, output_dir: &PathBuf) -> Result<ChunkCompletionInfo> {
         let expected_frames = self.calculate_expected_frames(chunk_id);
         let expected_duration = self.calculate_expected_duration(chunk_id);
 
         let jsonl_file = output_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
+        let resolved_path = match self.resolve_jsonl_path(&jsonl_file) {
+            Some(path) => path,
+            None => {
+                return Ok(ChunkCompletionInfo {
+                    chunk_id,
+                    expected_frames,
+                    actual_frames_processed: 0,
+                    expected_duration_secs: expected_duration,
+                    qr_codes_found: 0,
+                    jsonl_size_bytes: 0,
+                    completion_percentage: 0.0,
+                    is_complete: false,
+                    completion_reason: "JSONL file does not exist".to_string(),
+                    content_completion: None,
+                });
+            }
+        };
 
-        if !jsonl_file.exists() {
-            return Ok(ChunkCompletionInfo {
-                chunk_id,
-                expected_frames,
-                actual_frames_processed: 0,
-                expected_duration_secs: expected_duration,
-                qr_codes_found: 0,
-                jsonl_size_bytes: 0,
-                completion_percentage: 0.0,
-                is_complete: false,
-                completion_reason: "JSONL file does not exist".to_string(),
-            });
-        }
-
-        let (actual_frames, qr_codes, max_frame, min_frame) = self.analyze_jsonl_content(&jsonl_file)?;
-        let file_size = fs::metadata(&jsonl_file)?.len();
+        let (actual_frames, qr_codes, max_frame, min_frame, _frames_seen) = self.analyze_jsonl_content(&jsonl_file)?;
+        let file_size = fs::metadata(&resolved_path)?.len();
+        let content_completion = self.analyze_payload_sequence(&jsonl_file)?;
 
         // Multiple completion criteria
         let (is_complete, reason) = self.determine_completion(
-            chunk_id, expected_frames, actual_frames, max_frame, min_frame, qr_codes, expected_duration
+            chunk_id, expected_frames, actual_frames, max_frame, min_frame, qr_codes, expected_duration,
+            content_completion.as_ref(),
         );
 
         let completion_percentage = if expected_frames > 0 {
@@ -93,6 +145,7 @@ impl CompletionDetector {
             completion_percentage,
             is_complete,
             completion_reason: reason,
+            content_completion,
         })
     }
 
@@ -118,16 +171,51 @@ impl CompletionDetector {
         }
     }
 
-    fn analyze_jsonl_content(&self, jsonl_file: &PathBuf) -> Result<(u64, usize, u64, u64)> {
-        let content = fs::read_to_string(jsonl_file)?;
-        let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    /// Resolve `jsonl_file` to whichever of the plain file, a `.zst`
+    /// sibling, or a `.gz` sibling actually exists on disk, so callers can
+    /// transparently analyze compressed chunk output without a separate
+    /// decompress step.
+    fn resolve_jsonl_path(&self, jsonl_file: &PathBuf) -> Option<PathBuf> {
+        if jsonl_file.exists() {
+            return Some(jsonl_file.clone());
+        }
+        for ext in ["zst", "gz"] {
+            let candidate = PathBuf::from(format!("{}.{}", jsonl_file.display(), ext));
+            if candidate.exists() {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+
+    fn open_jsonl_reader(&self, jsonl_file: &PathBuf) -> Result<Box<dyn BufRead>> {
+        let path = self.resolve_jsonl_path(jsonl_file)
+            .ok_or_else(|| anyhow!("{} does not exist (checked .zst/.gz siblings)", jsonl_file.display()))?;
+        let file = fs::File::open(&path)?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("zst") => Ok(Box::new(std::io::BufReader::new(zstd::stream::read::Decoder::new(file)?))),
+            Some("gz") => Ok(Box::new(std::io::BufReader::new(flate2::read::GzDecoder::new(file)))),
+            _ => Ok(Box::new(std::io::BufReader::new(file))),
+        }
+    }
+
+    fn analyze_jsonl_content(&self, jsonl_file: &PathBuf) -> Result<(u64, usize, u64, u64, std::collections::HashSet<u64>)> {
+        let reader = self.open_jsonl_reader(jsonl_file)?;
 
         let mut max_frame = 0u64;
         let mut min_frame = u64::MAX;
         let mut frames_seen = std::collections::HashSet::new();
+        let mut line_count = 0usize;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            line_count += 1;
 
-        for line in &lines {
-            if let Ok(qr_data) = serde_json::from_str::<QrCodeData>(line) {
+            if let Ok(qr_data) = serde_json::from_str::<QrCodeData>(&line) {
                 frames_seen.insert(qr_data.frame_number);
                 if qr_data.frame_number > max_frame {
                     max_frame = qr_data.frame_number;
@@ -142,11 +230,226 @@ impl CompletionDetector {
             min_frame = 0;
         }
 
-        Ok((frames_seen.len() as u64, lines.len(), max_frame, min_frame))
+        Ok((frames_seen.len() as u64, line_count, max_frame, min_frame, frames_seen))
+    }
+
+    /// Walk the expected skip-aligned frame grid for `chunk_id`
+    /// (`min_frame, min_frame+s, min_frame+2s, ...`, where `s = skip_frames + 1`)
+    /// up to whichever is smaller of the chunk's expected max frame or the
+    /// highest frame actually seen, and collect every grid point absent from
+    /// `frames_seen`, coalescing consecutive absences into `(start, end)`
+    /// ranges. Frames beyond `max_frame` are the tail, not a gap - those are
+    /// already covered by `resume_from_frame` in `get_chunk_resume_point`.
+    pub fn compute_missing_intervals(&self, chunk_id: usize, frames_seen: &std::collections::HashSet<u64>, min_frame: u64, max_frame: u64) -> Vec<(u64, u64)> {
+        let expected_frames = self.calculate_expected_frames(chunk_id);
+        let skip_interval = self.skip_frames as u64 + 1;
+        let expected_max_frame = if self.skip_frames > 0 {
+            expected_frames / skip_interval
+        } else {
+            expected_frames
+        };
+        let grid_end = expected_max_frame.min(max_frame);
+
+        let mut intervals = Vec::new();
+        let mut gap_start: Option<u64> = None;
+        let mut frame = min_frame;
+        while frame <= grid_end {
+            if frames_seen.contains(&frame) {
+                if let Some(start) = gap_start.take() {
+                    intervals.push((start, frame - skip_interval));
+                }
+            } else if gap_start.is_none() {
+                gap_start = Some(frame);
+            }
+            frame += skip_interval;
+        }
+        if let Some(start) = gap_start {
+            intervals.push((start, grid_end));
+        }
+        intervals
+    }
+
+    /// Read the `M:`/`D:`/`R:` payload fields this wire format carries -
+    /// `chunks_count` from the metadata packet as the advertised total, and
+    /// the source chunk indices referenced by `D:`/`R:` packets as the
+    /// indices actually seen - so completion can be judged by what was sent
+    /// rather than by how many frames decoded. Returns `None` when the
+    /// chunk's JSONL has no metadata packet to read a total from.
+    fn analyze_payload_sequence(&self, jsonl_file: &PathBuf) -> Result<Option<CompletionInfo>> {
+        let reader = self.open_jsonl_reader(jsonl_file)?;
+        let mut payload_total: Option<usize> = None;
+        let mut indices_seen: std::collections::BTreeSet<usize> = std::collections::BTreeSet::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let qr_data = match serde_json::from_str::<QrCodeData>(&line) {
+                Ok(qr_data) => qr_data,
+                Err(_) => continue,
+            };
+            let payload = qr_data.data.as_str();
+
+            if let Some(rest) = payload.strip_prefix("M:") {
+                let parts: Vec<&str> = rest.split(':').collect();
+                if let Some(count) = parts.get(4).and_then(|s| s.parse::<usize>().ok()) {
+                    payload_total = Some(count);
+                }
+            } else if let Some(rest) = payload.strip_prefix("D:") {
+                let parts: Vec<&str> = rest.split(':').collect();
+                if parts.len() >= 6 {
+                    let data_part = parts[5..].join(":");
+                    if data_part.contains('|') {
+                        // Systematic packet: `chunkIndex:base64Data[:crc32Hex]` records.
+                        for record in data_part.split('|') {
+                            if let Some(idx) = record.splitn(3, ':').next().and_then(|s| s.parse::<usize>().ok()) {
+                                indices_seen.insert(idx);
+                            }
+                        }
+                    } else if data_part.contains(',') {
+                        // Fountain packet: comma-separated source chunk indices.
+                        for idx_str in data_part.split(',') {
+                            if let Ok(idx) = idx_str.parse::<usize>() {
+                                indices_seen.insert(idx);
+                            }
+                        }
+                    }
+                }
+            } else if let Some(rest) = payload.strip_prefix("R:") {
+                // `R:packet_id:block_id:share_index:n:k:base64Data` - a share
+                // covers source chunks `block_id*k .. block_id*k+k`.
+                let parts: Vec<&str> = rest.split(':').collect();
+                if parts.len() >= 5 {
+                    if let (Ok(block_id), Ok(k)) = (parts[1].parse::<usize>(), parts[4].parse::<usize>()) {
+                        for i in 0..k {
+                            indices_seen.insert(block_id * k + i);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(payload_total.map(|total| {
+            let missing_indices: Vec<usize> = (0..total).filter(|i| !indices_seen.contains(i)).collect();
+            CompletionInfo {
+                payload_indices_seen: indices_seen.into_iter().collect(),
+                payload_total: total,
+                missing_indices,
+            }
+        }))
+    }
+
+    /// Walk a chunk's JSONL recording every line that fails to parse as
+    /// `QrCodeData` - line number, raw text, and the parse error - instead
+    /// of silently discarding it the way `analyze_jsonl_content` does, plus
+    /// how many distinct frames showed up more than once.
+    pub fn scan_chunk_integrity(&self, chunk_id: usize, output_dir: &PathBuf) -> Result<ChunkIntegrityReport> {
+        let jsonl_file = output_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
+        let reader = self.open_jsonl_reader(&jsonl_file)?;
+
+        let mut total_lines = 0usize;
+        let mut valid_lines = 0usize;
+        let mut malformed_lines = Vec::new();
+        let mut frame_counts: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
+
+        for (line_num, line_result) in reader.lines().enumerate() {
+            let line = line_result?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            total_lines += 1;
+
+            match serde_json::from_str::<QrCodeData>(&line) {
+                Ok(qr_data) => {
+                    valid_lines += 1;
+                    *frame_counts.entry(qr_data.frame_number).or_insert(0) += 1;
+                }
+                Err(e) => malformed_lines.push(MalformedLine {
+                    line_number: line_num + 1,
+                    raw_text: line,
+                    parse_error: e.to_string(),
+                }),
+            }
+        }
+
+        let duplicate_frame_count = frame_counts.values().filter(|&&count| count > 1).count();
+
+        self.logger.log_info(&format!("Chunk {}: integrity scan - {}/{} valid lines, {} malformed, {} duplicate frame(s)",
+                                    chunk_id + 1, valid_lines, total_lines, malformed_lines.len(), duplicate_frame_count));
+
+        Ok(ChunkIntegrityReport {
+            chunk_id,
+            total_lines,
+            valid_lines,
+            malformed_lines,
+            duplicate_frame_count,
+        })
+    }
+
+    /// Rewrite a chunk's JSONL keeping only the last valid record per frame
+    /// (deduplicating `duplicate_frame_count`) and moving every malformed
+    /// line into a `chunk_NNN.corrupt.jsonl` quarantine file alongside it,
+    /// so a re-run of analysis or decoding sees a clean chunk instead of
+    /// re-discovering the same corruption. Returns the pre-repair report.
+    pub fn repair_chunk_integrity(&self, chunk_id: usize, output_dir: &PathBuf) -> Result<ChunkIntegrityReport> {
+        let jsonl_file = output_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
+        let report = self.scan_chunk_integrity(chunk_id, output_dir)?;
+
+        if report.malformed_lines.is_empty() && report.duplicate_frame_count == 0 {
+            return Ok(report);
+        }
+
+        let reader = self.open_jsonl_reader(&jsonl_file)?;
+        let mut last_valid_by_frame: std::collections::BTreeMap<u64, String> = std::collections::BTreeMap::new();
+        let mut corrupt_lines: Vec<String> = Vec::new();
+
+        for line_result in reader.lines() {
+            let line = line_result?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<QrCodeData>(&line) {
+                Ok(qr_data) => {
+                    last_valid_by_frame.insert(qr_data.frame_number, line);
+                }
+                Err(_) => corrupt_lines.push(line),
+            }
+        }
+
+        let clean_contents = last_valid_by_frame.values()
+            .map(|line| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&jsonl_file, clean_contents + "\n")?;
+
+        if !corrupt_lines.is_empty() {
+            let quarantine_path = output_dir.join(format!("chunk_{:03}.corrupt.jsonl", chunk_id + 1));
+            fs::write(&quarantine_path, corrupt_lines.join("\n") + "\n")?;
+            self.logger.log_warning("REPAIR", &format!("Chunk {}: quarantined {} malformed line(s) to {}",
+                                    chunk_id + 1, corrupt_lines.len(), quarantine_path.display()));
+        }
+
+        self.logger.log_info(&format!("Chunk {}: repaired - {} unique frame(s) kept, {} malformed line(s) quarantined",
+                                    chunk_id + 1, last_valid_by_frame.len(), corrupt_lines.len()));
+
+        Ok(report)
     }
 
     fn determine_completion(&self, chunk_id: usize, expected_frames: u64, actual_frames: u64,
-                           max_frame: u64, min_frame: u64, qr_codes: usize, expected_duration: f64) -> (bool, String) {
+                           max_frame: u64, min_frame: u64, qr_codes: usize, expected_duration: f64,
+                           content_completion: Option<&CompletionInfo>) -> (bool, String) {
+        // Content-driven completion: if the QR stream carried a metadata
+        // packet advertising a payload total, trust what was actually
+        // observed over frame-count guesswork.
+        if let Some(info) = content_completion {
+            return if info.missing_indices.is_empty() {
+                (true, format!("COMPLETE: all {} payload indices observed", info.payload_total))
+            } else {
+                (false, format!("INCOMPLETE: {}/{} payload indices observed, {} missing",
+                               info.payload_indices_seen.len(), info.payload_total, info.missing_indices.len()))
+            };
+        }
 
         // Criterion 1: Frame count completeness
         let frame_completeness = actual_frames as f64 / expected_frames as f64;
@@ -215,6 +518,43 @@ impl CompletionDetector {
         Ok(results)
     }
 
+    /// Same result as `analyze_all_chunks`, but chunk ids are pushed into a
+    /// bounded `ArrayQueue` and drained by `num_threads` workers, each
+    /// calling `analyze_chunk_completion` concurrently - worthwhile once a
+    /// run has enough chunks that the per-chunk JSONL read in
+    /// `analyze_jsonl_content` dominates wall time.
+    pub fn analyze_all_chunks_parallel(&self, output_dir: &PathBuf) -> Result<Vec<ChunkCompletionInfo>> {
+        let queue = ArrayQueue::new(self.chunk_count.max(1));
+        for i in 0..self.chunk_count {
+            let _ = queue.push(i);
+        }
+
+        let results = std::sync::Mutex::new(Vec::with_capacity(self.chunk_count));
+        let worker_count = self.num_threads.min(self.chunk_count.max(1));
+
+        std::thread::scope(|scope| {
+            for _ in 0..worker_count {
+                scope.spawn(|| {
+                    while let Some(chunk_id) = queue.pop() {
+                        match self.analyze_chunk_completion(chunk_id, output_dir) {
+                            Ok(info) => results.lock().unwrap().push(info),
+                            Err(e) => self.logger.log_error("PARALLEL_ANALYSIS",
+                                &format!("chunk {} failed: {}", chunk_id + 1, e)),
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|c| c.chunk_id);
+
+        self.logger.log_info(&format!("Completion Analysis Summary ({} threads): {}/{} chunks complete",
+                                    worker_count, results.iter().filter(|c| c.is_complete).count(), self.chunk_count));
+
+        Ok(results)
+    }
+
     pub fn get_incomplete_chunks(&self, output_dir: &PathBuf) -> Result<Vec<usize>> {
         let analysis = self.analyze_all_chunks(output_dir)?;
         Ok(analysis.iter()
@@ -273,7 +613,7 @@ impl CompletionDetector {
         let jsonl_file = output_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
         let expected_frames = self.calculate_expected_frames(chunk_id);
 
-        if !jsonl_file.exists() {
+        if self.resolve_jsonl_path(&jsonl_file).is_none() {
             return Ok(ChunkResumePoint {
                 chunk_id,
                 should_resume: true,
@@ -281,12 +621,15 @@ impl CompletionDetector {
                 frames_already_processed: 0,
                 qr_codes_already_found: 0,
                 completion_status: "No JSONL file - start from beginning".to_string(),
+                gap_intervals: Vec::new(),
             });
         }
 
-        let (actual_frames, qr_codes, max_frame, min_frame) = self.analyze_jsonl_content(&jsonl_file)?;
+        let (actual_frames, qr_codes, max_frame, min_frame, frames_seen) = self.analyze_jsonl_content(&jsonl_file)?;
+        let content_completion = self.analyze_payload_sequence(&jsonl_file)?;
         let (is_complete, reason) = self.determine_completion(
-            chunk_id, expected_frames, actual_frames, max_frame, min_frame, qr_codes, 0.0
+            chunk_id, expected_frames, actual_frames, max_frame, min_frame, qr_codes, 0.0,
+            content_completion.as_ref(),
         );
 
         if is_complete {
@@ -297,6 +640,7 @@ impl CompletionDetector {
                 frames_already_processed: actual_frames,
                 qr_codes_already_found: qr_codes,
                 completion_status: format!("COMPLETE: {}", reason),
+                gap_intervals: Vec::new(),
             });
         }
 
@@ -311,8 +655,13 @@ impl CompletionDetector {
             resume_frame
         };
 
-        self.logger.log_info(&format!("Chunk {}: Resume from frame {} (was at frame {}, {} QR codes)",
-                                    chunk_id + 1, aligned_resume_frame, max_frame, qr_codes));
+        // Frames dropped in the middle of the chunk (decode failures, skipped
+        // seeks) that `resume_from_frame` alone would never revisit, since it
+        // only tails from `max_frame`.
+        let gap_intervals = self.compute_missing_intervals(chunk_id, &frames_seen, min_frame, max_frame);
+
+        self.logger.log_info(&format!("Chunk {}: Resume from frame {} (was at frame {}, {} QR codes, {} gap(s))",
+                                    chunk_id + 1, aligned_resume_frame, max_frame, qr_codes, gap_intervals.len()));
 
         Ok(ChunkResumePoint {
             chunk_id,
@@ -323,6 +672,7 @@ impl CompletionDetector {
             completion_status: format!("RESUME: from frame {} ({:.1}% complete)",
                                      aligned_resume_frame,
                                      actual_frames as f64 / expected_frames as f64 * 100.0),
+            gap_intervals,
         })
     }
 
@@ -341,6 +691,41 @@ impl CompletionDetector {
 
         Ok(resume_points)
     }
+
+    /// `get_all_resume_points` split across `num_threads` workers via the
+    /// same bounded-queue pattern as `analyze_all_chunks_parallel`.
+    pub fn get_all_resume_points_parallel(&self, output_dir: &PathBuf) -> Result<Vec<ChunkResumePoint>> {
+        let queue = ArrayQueue::new(self.chunk_count.max(1));
+        for i in 0..self.chunk_count {
+            let _ = queue.push(i);
+        }
+
+        let results = std::sync::Mutex::new(Vec::with_capacity(self.chunk_count));
+        let worker_count = self.num_threads.min(self.chunk_count.max(1));
+
+        std::thread::scope(|scope| {
+            for _ in 0..worker_count {
+                scope.spawn(|| {
+                    while let Some(chunk_id) = queue.pop() {
+                        match self.get_chunk_resume_point(chunk_id, output_dir) {
+                            Ok(point) => results.lock().unwrap().push(point),
+                            Err(e) => self.logger.log_error("PARALLEL_RESUME",
+                                &format!("chunk {} failed: {}", chunk_id + 1, e)),
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut resume_points = results.into_inner().unwrap();
+        resume_points.sort_by_key(|p| p.chunk_id);
+
+        let incomplete_count = resume_points.iter().filter(|p| p.should_resume).count();
+        self.logger.log_info(&format!("Resume Analysis ({} threads): {} chunks need processing, {} already complete",
+                                    worker_count, incomplete_count, self.chunk_count - incomplete_count));
+
+        Ok(resume_points)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -351,4 +736,159 @@ pub struct ChunkResumePoint {
     pub frames_already_processed: u64,
     pub qr_codes_already_found: usize,
     pub completion_status: String,
+    /// Expected-but-missing frame ranges strictly before `resume_from_frame`
+    /// - holes left by decode failures or skipped seeks in the middle of an
+    /// otherwise-processed span, as opposed to the unprocessed tail.
+    pub gap_intervals: Vec<(u64, u64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StallSeverity {
+    Warn,
+    Critical,
+}
+
+/// One line of `--monitor`'s machine-readable output: serialized via
+/// `serde_json::to_string` so an operator can pipe it into `jq` or a log
+/// aggregator instead of scraping the human-readable alert text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StallAlert {
+    /// `None` means the alert is about the whole run, not one chunk.
+    pub chunk_id: Option<usize>,
+    pub severity: StallSeverity,
+    pub message: String,
+}
+
+/// Watches a batch run over time, modeled on a classic setup-ceremony
+/// monitor loop: poll every chunk's JSONL growth at `polling_interval`,
+/// remember each chunk's last-changed timestamp and last frame count, and
+/// alert when a chunk goes `chunk_timeout` with no new frames or the whole
+/// run exceeds `overall_timeout` - so a long unattended run can be watched
+/// for hangs instead of only audited after the fact.
+pub struct ProgressMonitor {
+    chunk_count: usize,
+    polling_interval: Duration,
+    chunk_timeout: Duration,
+    overall_timeout: Duration,
+    logger: ErrorLogger,
+    callback: Option<EventCallback>,
+    run_started_at: Instant,
+    last_frame_count: std::collections::HashMap<usize, u64>,
+    last_changed_at: std::collections::HashMap<usize, Instant>,
+    job_complete_fired: bool,
+}
+
+impl ProgressMonitor {
+    pub fn new(chunk_count: usize, polling_interval: Duration, chunk_timeout: Duration,
+               overall_timeout: Duration, output_dir: &PathBuf) -> Result<Self> {
+        let log_path = output_dir.join("processing.log");
+        let logger = ErrorLogger::new(&log_path.to_string_lossy())
+            .unwrap_or_else(|_| ErrorLogger::new("/tmp/processing.log").unwrap());
+
+        Ok(Self {
+            chunk_count,
+            polling_interval,
+            chunk_timeout,
+            overall_timeout,
+            logger,
+            callback: None,
+            run_started_at: Instant::now(),
+            last_frame_count: std::collections::HashMap::new(),
+            last_changed_at: std::collections::HashMap::new(),
+            job_complete_fired: false,
+        })
+    }
+
+    /// Fire an `EventCallback` (e.g. wired up to a webhook poster) alongside
+    /// the `ErrorLogger` record for every alert raised by `poll_once`.
+    pub fn with_callback(mut self, callback: EventCallback) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    pub fn polling_interval(&self) -> Duration {
+        self.polling_interval
+    }
+
+    /// Poll every chunk's current frame count via `detector` once, updating
+    /// last-changed bookkeeping and returning any alerts raised this tick.
+    /// Callers loop this at `polling_interval` for the lifetime of a run.
+    pub fn poll_once(&mut self, detector: &CompletionDetector, output_dir: &PathBuf) -> Result<Vec<StallAlert>> {
+        let mut alerts = Vec::new();
+        let now = Instant::now();
+        let mut all_complete = true;
+        let mut total_qr_codes = 0usize;
+
+        for chunk_id in 0..self.chunk_count {
+            let info = detector.analyze_chunk_completion(chunk_id, output_dir)?;
+            let frames = info.actual_frames_processed;
+            total_qr_codes += info.qr_codes_found;
+
+            let changed = self.last_frame_count.get(&chunk_id).map(|&last| last != frames).unwrap_or(true);
+            if changed {
+                self.last_changed_at.insert(chunk_id, now);
+            }
+            self.last_frame_count.insert(chunk_id, frames);
+
+            if info.is_complete {
+                continue;
+            }
+
+            all_complete = false;
+
+            let stalled_for = now.duration_since(*self.last_changed_at.get(&chunk_id).unwrap_or(&now));
+            if stalled_for >= self.chunk_timeout {
+                let alert = StallAlert {
+                    chunk_id: Some(chunk_id),
+                    severity: StallSeverity::Warn,
+                    message: format!("Chunk {} stalled at {} frames for {:.0}s with no new progress",
+                                    chunk_id + 1, frames, stalled_for.as_secs_f64()),
+                };
+                self.logger.log_warning("STALL", &alert.message);
+                self.fire(&alert);
+                alerts.push(alert);
+            }
+        }
+
+        let overall_elapsed = now.duration_since(self.run_started_at);
+        if overall_elapsed >= self.overall_timeout {
+            let alert = StallAlert {
+                chunk_id: None,
+                severity: StallSeverity::Critical,
+                message: format!("Run exceeded overall timeout of {:.0}s ({:.0}s elapsed)",
+                                self.overall_timeout.as_secs_f64(), overall_elapsed.as_secs_f64()),
+            };
+            self.logger.log_error("STALL", &alert.message);
+            self.fire(&alert);
+            alerts.push(alert);
+        }
+
+        if all_complete && !self.job_complete_fired {
+            self.job_complete_fired = true;
+            self.logger.log_info(&format!("All {} chunks complete, {} QR codes total", self.chunk_count, total_qr_codes));
+            if let Some(callback) = &self.callback {
+                callback(ProcessingEvent::MonitorJobComplete {
+                    chunk_count: self.chunk_count,
+                    total_qr_codes,
+                    elapsed_secs: overall_elapsed.as_secs_f64(),
+                });
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// Whether `poll_once` has already fired the one-time job-complete rollup.
+    pub fn is_job_complete(&self) -> bool {
+        self.job_complete_fired
+    }
+
+    fn fire(&self, alert: &StallAlert) {
+        if let Some(callback) = &self.callback {
+            callback(ProcessingEvent::SystemError {
+                context: alert.chunk_id.map(|id| format!("chunk_{}", id + 1)).unwrap_or_else(|| "overall".to_string()),
+                error: alert.message.clone(),
+            });
+        }
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Result};
 use base64::{Engine as _, engine::general_purpose};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -13,6 +14,12 @@ struct FileMetadata {
     file_size: usize,
     chunks_count: usize,
     file_checksum: Option<String>,
+    /// Compression applied before chunking (currently only `"zstd"`), sniffed
+    /// from the metadata packet or the zstd magic at the head of `file_data`.
+    compression: Option<String>,
+    /// Original (uncompressed) size, when the sender recorded it, to
+    /// sanity-check the decompressed output.
+    original_size: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +36,19 @@ struct DataPacket {
     xor_data: Option<Vec<u8>>,
 }
 
+/// One Reed-Solomon share of a `k`-of-`n` erasure-coded block. `block_id`
+/// anchors the block to source chunk indices `block_id*k .. block_id*k+k`;
+/// `share_index` is the Vandermonde evaluation point (`x = share_index+1`,
+/// never 0 so it's always invertible over GF(256)).
+#[derive(Debug, Clone)]
+struct RsShare {
+    block_id: usize,
+    share_index: usize,
+    n: usize,
+    k: usize,
+    data: Vec<u8>,
+}
+
 struct FountainDecoder {
     initialized: bool,
     meta_data: Option<FileMetadata>,
@@ -36,10 +56,26 @@ struct FountainDecoder {
     source_chunks: HashMap<usize, Vec<u8>>,
     recovered_chunk_count: usize,
     coded_packets: Vec<DataPacket>,
+    rs_shares: HashMap<usize, Vec<RsShare>>,
+    /// Set when the output file for this decoder is already present on disk
+    /// from a previous run (see `QRFileDecoder::process_metadata_packet`).
+    /// Lets a resumed run that replays its whole input again treat every
+    /// packet for this file as a no-op instead of redoing (or re-writing)
+    /// work that already completed.
+    already_finalized: bool,
+    /// Last time any packet (systematic, fountain, or RS) arrived for this
+    /// file. A gap here past `--chunk-timeout` means the feed itself has
+    /// gone quiet for this file, not that the decode is stuck.
+    last_packet_at: std::time::Instant,
+    /// Last time `recovered_chunk_count` actually increased. A gap here
+    /// while packets keep arriving means the decode is stalled - buffered
+    /// fountain packets don't have enough rank to peel another chunk.
+    last_progress_at: std::time::Instant,
 }
 
 impl FountainDecoder {
     fn new() -> Self {
+        let now = std::time::Instant::now();
         Self {
             initialized: false,
             meta_data: None,
@@ -47,6 +83,10 @@ impl FountainDecoder {
             source_chunks: HashMap::new(),
             recovered_chunk_count: 0,
             coded_packets: Vec::new(),
+            rs_shares: HashMap::new(),
+            already_finalized: false,
+            last_packet_at: now,
+            last_progress_at: now,
         }
     }
 
@@ -56,20 +96,127 @@ impl FountainDecoder {
         self.source_chunks.clear();
         self.recovered_chunk_count = 0;
         self.coded_packets.clear();
+        self.rs_shares.clear();
+        self.already_finalized = false;
         self.initialized = true;
+        self.last_packet_at = std::time::Instant::now();
+        self.last_progress_at = self.last_packet_at;
 
         println!("📄 Initialized decoder for {} ({} chunks, {} bytes)",
                 metadata.file_name, metadata.chunks_count, metadata.file_size);
         self.print_progress();
     }
 
+    /// Mark this file as already reconstructed on disk by a previous run,
+    /// so subsequent packets for it are cheap no-ops and `finalize` won't
+    /// overwrite the existing output.
+    fn mark_already_finalized(&mut self) {
+        self.already_finalized = true;
+    }
+
+    /// Record one RS share, then attempt to solve its block once `k`
+    /// distinct shares have arrived.
+    fn add_rs_share(&mut self, share: RsShare) -> bool {
+        if !self.initialized {
+            return false;
+        }
+        if self.already_finalized {
+            return true;
+        }
+        self.last_packet_at = std::time::Instant::now();
+        let recovered_before = self.recovered_chunk_count;
+
+        let block_id = share.block_id;
+        let (n, k) = (share.n, share.k);
+        let shares = self.rs_shares.entry(block_id).or_insert_with(Vec::new);
+        if !shares.iter().any(|s| s.share_index == share.share_index) {
+            shares.push(share);
+        }
+        println!("🧩 RS block {}: {}/{} shares (need {} of {})", block_id, shares.len(), n, k, n);
+
+        self.try_solve_rs_block(block_id);
+        if self.recovered_chunk_count > recovered_before {
+            self.last_progress_at = std::time::Instant::now();
+        }
+        true
+    }
+
+    /// Once `k` of a block's `n` shares are present, select any `k`, invert
+    /// the corresponding `k x k` Vandermonde submatrix over GF(256), and
+    /// multiply it by the share values byte-column by byte-column to
+    /// recover the block's source chunks.
+    fn try_solve_rs_block(&mut self, block_id: usize) {
+        let Some(shares) = self.rs_shares.get(&block_id) else { return };
+        let Some(first) = shares.first() else { return };
+        let k = first.k;
+        if shares.len() < k || k == 0 {
+            return;
+        }
+
+        let mut chosen: Vec<RsShare> = shares.clone();
+        chosen.sort_by_key(|s| s.share_index);
+        chosen.truncate(k);
+
+        let tables = gf256_tables();
+
+        let matrix: Vec<Vec<u8>> = chosen.iter().map(|s| {
+            let x = (s.share_index as u8).wrapping_add(1);
+            let mut row = vec![1u8; k];
+            for j in 1..k {
+                row[j] = gf_mul(&tables, row[j - 1], x);
+            }
+            row
+        }).collect();
+
+        let Some(inverse) = gf_invert_matrix(&tables, &matrix) else {
+            // Not full rank with this choice of shares (shouldn't happen
+            // with distinct evaluation points) - wait for another share.
+            return;
+        };
+
+        let payload_len = chosen.iter().map(|s| s.data.len()).max().unwrap_or(0);
+        if payload_len == 0 {
+            return;
+        }
+
+        let mut recovered: Vec<Vec<u8>> = vec![vec![0u8; payload_len]; k];
+        for byte_pos in 0..payload_len {
+            for (j, row) in recovered.iter_mut().enumerate() {
+                let mut acc = 0u8;
+                for (i, share) in chosen.iter().enumerate() {
+                    let y = *share.data.get(byte_pos).unwrap_or(&0);
+                    acc ^= gf_mul(&tables, inverse[j][i], y);
+                }
+                row[byte_pos] = acc;
+            }
+        }
+
+        for (local_idx, bytes) in recovered.into_iter().enumerate() {
+            let chunk_idx = block_id * k + local_idx;
+            if !self.source_chunks.contains_key(&chunk_idx) {
+                self.source_chunks.insert(chunk_idx, bytes);
+                self.recovered_chunk_count += 1;
+                println!("🧩 RS recovered chunk {} (block {})", chunk_idx, block_id);
+            }
+        }
+
+        self.rs_shares.remove(&block_id);
+        self.print_progress();
+    }
+
     fn add_packet(&mut self, packet: DataPacket) -> bool {
         if !self.initialized {
             return false;
         }
+        if self.already_finalized {
+            return true;
+        }
+        self.last_packet_at = std::time::Instant::now();
+        let recovered_before = self.recovered_chunk_count;
 
         if !packet.systematic_data_chunks.is_empty() {
-            // Process systematic chunks directly
+            // Process systematic chunks directly (already idempotent: a
+            // chunk index already in source_chunks is left alone).
             for chunk in &packet.systematic_data_chunks {
                 if !self.source_chunks.contains_key(&chunk.chunk_index) {
                     self.source_chunks.insert(chunk.chunk_index, chunk.chunk_data.clone());
@@ -78,11 +225,22 @@ impl FountainDecoder {
                 }
             }
         } else if packet.xor_data.is_some() {
-            // Store fountain packet for later processing
-            self.coded_packets.push(packet);
-            self.process_coded();
+            // A resumed run that replays its whole input will see every
+            // fountain packet again, including ones already folded into the
+            // restored state - skip exact (seed, index-set) repeats so they
+            // don't pile up in `coded_packets` forever.
+            let is_duplicate = self.coded_packets.iter().any(|existing| {
+                existing.packet_id == packet.packet_id && existing.source_chunks == packet.source_chunks
+            });
+            if !is_duplicate {
+                self.coded_packets.push(packet);
+                self.process_coded();
+            }
         }
 
+        if self.recovered_chunk_count > recovered_before {
+            self.last_progress_at = std::time::Instant::now();
+        }
         true
     }
 
@@ -128,16 +286,164 @@ impl FountainDecoder {
                 }
             }
         }
+
+        // Peeling stalled with degree-1 packets exhausted. A solvable
+        // system can still exist among the remaining degree->=2 packets
+        // (e.g. packets covering {a, b}, {b, c} and {a, b, c}: XORing the
+        // last two pins down a, then b and c) - fall back to Gaussian elimination
+        // over GF(2) before giving up.
+        if !self.coded_packets.is_empty() && self.solve_by_elimination() {
+            self.process_coded();
+        }
+    }
+
+    /// Solve the remaining coded packets as a linear system over GF(2):
+    /// each packet is an equation "XOR of its still-missing source chunks
+    /// == rhs", where `rhs` is `xor_data` XORed with every chunk the
+    /// packet covers that's already known. Rows are reduced to echelon
+    /// form by pivoting on the first set coefficient bit, XORing both the
+    /// coefficient bitset and the RHS byte-vector together; any row that
+    /// ends with exactly one set coefficient directly yields that chunk's
+    /// bytes. Rank-deficient rows are left alone for a later round.
+    /// Returns true if at least one chunk was recovered.
+    fn solve_by_elimination(&mut self) -> bool {
+        let mut unknowns: Vec<usize> = self.coded_packets.iter()
+            .flat_map(|p| p.source_chunks.iter()
+                .filter(|&&idx| !self.source_chunks.contains_key(&idx))
+                .cloned())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        unknowns.sort_unstable();
+        if unknowns.is_empty() {
+            return false;
+        }
+        let col_of: HashMap<usize, usize> = unknowns.iter().enumerate()
+            .map(|(col, &idx)| (idx, col))
+            .collect();
+
+        // Pad every payload (known chunks and xor_data) to a common length
+        // so XORing rows together is well-defined.
+        let chunk_size = self.coded_packets.iter()
+            .filter_map(|p| p.xor_data.as_ref().map(|d| d.len()))
+            .chain(self.source_chunks.values().map(|c| c.len()))
+            .max()
+            .unwrap_or(0);
+        if chunk_size == 0 {
+            return false;
+        }
+
+        let mut rows: Vec<(Vec<bool>, Vec<u8>)> = Vec::new();
+        for packet in &self.coded_packets {
+            let missing: Vec<usize> = packet.source_chunks.iter()
+                .filter(|&&idx| !self.source_chunks.contains_key(&idx))
+                .cloned()
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+
+            let mut rhs = packet.xor_data.clone().unwrap_or_default();
+            rhs.resize(chunk_size, 0);
+            for &idx in &packet.source_chunks {
+                if !missing.contains(&idx) {
+                    if let Some(known) = self.source_chunks.get(&idx) {
+                        for j in 0..chunk_size.min(known.len()) {
+                            rhs[j] ^= known[j];
+                        }
+                    }
+                }
+            }
+
+            let mut coeffs = vec![false; unknowns.len()];
+            for idx in &missing {
+                coeffs[col_of[idx]] = true;
+            }
+            rows.push((coeffs, rhs));
+        }
+
+        // Gauss-Jordan elimination over GF(2): pivot column by column,
+        // zeroing that column out of every other row (above and below) so
+        // a full-rank row ends up with a single set coefficient.
+        let mut next_row = 0;
+        for col in 0..unknowns.len() {
+            if next_row >= rows.len() {
+                break;
+            }
+            let Some(pivot) = (next_row..rows.len()).find(|&r| rows[r].0[col]) else { continue };
+            rows.swap(next_row, pivot);
+
+            let (pivot_coeffs, pivot_rhs) = rows[next_row].clone();
+            for r in 0..rows.len() {
+                if r != next_row && rows[r].0[col] {
+                    let (coeffs, rhs) = &mut rows[r];
+                    for c in 0..coeffs.len() {
+                        coeffs[c] ^= pivot_coeffs[c];
+                    }
+                    for b in 0..rhs.len() {
+                        rhs[b] ^= pivot_rhs[b];
+                    }
+                }
+            }
+
+            next_row += 1;
+        }
+
+        let mut recovered_any = false;
+        for (coeffs, rhs) in &rows {
+            let set_cols: Vec<usize> = coeffs.iter().enumerate()
+                .filter(|(_, &set)| set)
+                .map(|(col, _)| col)
+                .collect();
+            if set_cols.len() == 1 {
+                let chunk_idx = unknowns[set_cols[0]];
+                if !self.source_chunks.contains_key(&chunk_idx) {
+                    self.source_chunks.insert(chunk_idx, rhs.clone());
+                    self.recovered_chunk_count += 1;
+                    println!("🧮 Elimination recovered chunk {}", chunk_idx);
+                    recovered_any = true;
+                }
+            }
+        }
+
+        if recovered_any {
+            self.print_progress();
+            self.coded_packets.retain(|p| p.source_chunks.iter()
+                .any(|idx| !self.source_chunks.contains_key(idx)));
+        }
+
+        recovered_any
     }
 
     fn is_complete(&self) -> bool {
-        self.recovered_chunk_count >= self.total_chunks
+        self.already_finalized || self.recovered_chunk_count >= self.total_chunks
     }
 
     fn is_nearly_complete(&self, threshold: f64) -> bool {
         (self.recovered_chunk_count as f64 / self.total_chunks as f64) >= threshold
     }
 
+    /// Parse and apply a raw `D:`/`R:` QR line directly, bypassing
+    /// `QRFileDecoder`'s routing. Used by the parallel per-file dispatch in
+    /// `main()`, where a sequential classify pass has already bucketed each
+    /// file's lines by the decoder they belong to, so no `M:`-driven
+    /// `current_active_decoder` lookup is needed here.
+    fn add_line(&mut self, line: &str) -> bool {
+        if line.starts_with("D:") {
+            match parse_data_packet_str(line) {
+                Ok(packet) => self.add_packet(packet),
+                Err(_) => false,
+            }
+        } else if line.starts_with("R:") {
+            match parse_rs_packet_str(line) {
+                Ok(share) => self.add_rs_share(share),
+                Err(_) => false,
+            }
+        } else {
+            false
+        }
+    }
+
     fn print_progress(&self) {
         let percentage = ((self.recovered_chunk_count as f64 / self.total_chunks as f64) * 100.0).round() as usize;
         let progress_bars = percentage / 2;
@@ -150,6 +456,12 @@ impl FountainDecoder {
     }
 
     fn finalize(&mut self, output_dir: &str) -> Result<Option<Vec<u8>>> {
+        if self.already_finalized {
+            println!("✅ {} already reconstructed on disk - skipping re-finalize",
+                     self.meta_data.as_ref().map(|m| m.file_name.as_str()).unwrap_or("<unknown>"));
+            return Ok(None);
+        }
+
         if !self.is_complete() {
             println!("\n❌ File incomplete: {}/{} chunks", self.recovered_chunk_count, self.total_chunks);
 
@@ -196,7 +508,47 @@ impl FountainDecoder {
         // Truncate to exact file size
         file_data.truncate(metadata.file_size);
 
-        // Verify checksum if available
+        // Transparently restore zstd-compressed payloads: senders may
+        // compress before chunking to shrink the number of QR frames
+        // needed, sniffed either from the metadata packet or the zstd
+        // magic at the head of the reassembled bytes. This must happen
+        // before checksum verification below - `file_checksum` is the
+        // hash of the original (uncompressed) bytes, not the wire bytes.
+        const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+        let is_zstd = metadata.compression.as_deref() == Some("zstd")
+            || file_data.get(..4) == Some(&ZSTD_MAGIC[..]);
+
+        std::fs::create_dir_all(output_dir)?;
+        let file_data = if is_zstd {
+            match decompress_zstd(&file_data) {
+                Ok(decompressed) => {
+                    if let Some(expected) = metadata.original_size {
+                        if decompressed.len() != expected {
+                            println!("⚠️ Decompressed size {} does not match recorded original size {}",
+                                     decompressed.len(), expected);
+                        }
+                    }
+                    println!("📦 Decompressed zstd payload: {} -> {} bytes", file_data.len(), decompressed.len());
+                    decompressed
+                }
+                Err(e) => {
+                    // A truncated zstd frame most often means some chunk
+                    // got corrupted in transit despite checksums upstream.
+                    // Leave the compressed bytes as `.partial` rather than
+                    // writing a broken final file, so a resumed run (which
+                    // keeps chunks in memory and retries finalize on every
+                    // new packet) can still recover once better data shows up.
+                    let partial_path = PathBuf::from(output_dir).join(format!("{}.partial", metadata.file_name));
+                    let _ = std::fs::write(&partial_path, &file_data);
+                    println!("❌ zstd decompression failed ({}) - left compressed bytes at {}", e, partial_path.display());
+                    return Ok(None);
+                }
+            }
+        } else {
+            file_data
+        };
+
+        // Verify checksum against the (now decompressed) original bytes.
         if let Some(ref expected_checksum) = metadata.file_checksum {
             let calculated = self.calculate_checksum(&file_data);
             if calculated == *expected_checksum {
@@ -208,7 +560,6 @@ impl FountainDecoder {
         }
 
         // Write file to output directory
-        std::fs::create_dir_all(output_dir)?;
         let output_path = PathBuf::from(output_dir).join(&metadata.file_name);
         std::fs::write(&output_path, &file_data)?;
 
@@ -216,20 +567,204 @@ impl FountainDecoder {
         Ok(Some(file_data))
     }
 
+    /// A real CRC32 (not the previous truncated FNV-1a) so a single-bit
+    /// corruption is actually caught rather than possibly hashing away.
     fn calculate_checksum(&self, data: &[u8]) -> String {
-        let mut hash: u32 = 2166136261; // FNV-1a offset basis
-        for &byte in data {
-            hash ^= byte as u32;
-            hash = hash.wrapping_mul(16777619); // FNV-1a prime
+        format!("{:08x}", crc32fast::hash(data))
+    }
+}
+
+/// Parse a `D:packet_id:...:chunkCount:chunkIndex:base64Data[:crc32Hex]|...`
+/// (systematic) or `D:packet_id:...:chunkCount:idx,idx,...:base64XorData`
+/// (fountain) line into a `DataPacket`, independent of any `QRFileDecoder`
+/// state, so it can run off the main thread. The many systematic records in
+/// a single packet are base64-decoded (and CRC32-checked) concurrently,
+/// since that's the hot loop on packets carrying dozens of chunks.
+fn parse_data_packet_str(data_string: &str) -> Result<DataPacket> {
+    let parts: Vec<&str> = data_string.split(':').collect();
+    if parts.len() < 6 {
+        return Err(anyhow!("Invalid data packet format"));
+    }
+
+    let mut packet = DataPacket {
+        packet_id: parts[1].parse()?,
+        source_chunks: Vec::new(),
+        systematic_data_chunks: Vec::new(),
+        xor_data: None,
+    };
+
+    if parts.len() >= 7 {
+        let data_field_offset = 6;
+        // Reconstruct data part by joining from dataFieldOffset onwards (critical fix!)
+        let all_data_part = parts[data_field_offset..].join(":");
+
+        if all_data_part.contains('|') {
+            let records: Vec<&str> = all_data_part.split('|').collect();
+
+            let decoded: Vec<(usize, Vec<u8>)> = records.par_iter()
+                .filter_map(|record| {
+                    // `chunkIndex:base64Data` or, when the sender attached a
+                    // per-chunk CRC32, `chunkIndex:base64Data:crc32Hex`.
+                    let chunk_parts: Vec<&str> = record.splitn(3, ':').collect();
+                    if chunk_parts.len() < 2 {
+                        return None;
+                    }
+                    let chunk_index: usize = chunk_parts[0].parse().ok()?;
+                    let chunk_data_b64 = chunk_parts[1];
+                    if chunk_data_b64.is_empty() {
+                        return None;
+                    }
+
+                    let chunk_data = match general_purpose::STANDARD.decode(chunk_data_b64) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            println!("❌ Failed to decode chunk {}: {}", chunk_index, e);
+                            return None;
+                        }
+                    };
+
+                    if let Some(expected_hex) = chunk_parts.get(2) {
+                        if let Ok(expected) = u32::from_str_radix(expected_hex, 16) {
+                            let actual = crc32fast::hash(&chunk_data);
+                            if actual != expected {
+                                println!("❌ CRC32 mismatch for chunk {}: expected {:08x}, got {:08x} - dropping (a later copy may still arrive)",
+                                        chunk_index, expected, actual);
+                                return None;
+                            }
+                        }
+                    }
+
+                    Some((chunk_index, chunk_data))
+                })
+                .collect();
+
+            for (chunk_index, chunk_data) in decoded {
+                packet.source_chunks.push(chunk_index);
+                packet.systematic_data_chunks.push(SystematicChunk { chunk_index, chunk_data });
+            }
+        } else if all_data_part.contains(',') {
+            // Fountain packet: comma-separated indices
+            packet.source_chunks = all_data_part.split(',')
+                .map(|s| s.parse())
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // XOR data would be in next field for fountain packets
+            if parts.len() >= 8 {
+                match general_purpose::STANDARD.decode(parts[7]) {
+                    Ok(xor_data) => packet.xor_data = Some(xor_data),
+                    Err(e) => println!("Failed to decode fountain XOR data: {}", e),
+                }
+            }
         }
-        format!("{:x}", hash)[..8.min(format!("{:x}", hash).len())].to_string()
     }
+
+    Ok(packet)
+}
+
+/// Parse `R:packet_id:block_id:share_index:n:k:base64Data` into an
+/// `RsShare`, independent of any `QRFileDecoder` state.
+fn parse_rs_packet_str(data_string: &str) -> Result<RsShare> {
+    let parts: Vec<&str> = data_string.split(':').collect();
+    if parts.len() < 7 {
+        return Err(anyhow!("Invalid RS packet format"));
+    }
+
+    Ok(RsShare {
+        block_id: parts[2].parse()?,
+        share_index: parts[3].parse()?,
+        n: parts[4].parse()?,
+        k: parts[5].parse()?,
+        data: general_purpose::STANDARD.decode(parts[6..].join(":"))?,
+    })
+}
+
+/// Decompress a zstd-framed buffer with a pure-Rust, dependency-light
+/// streaming decoder rather than shelling out or linking libzstd.
+fn decompress_zstd(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = ruzstd::StreamingDecoder::new(data)
+        .map_err(|e| anyhow!("zstd stream init failed: {}", e))?;
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// GF(256) exp/log tables generated from primitive element 0x02 and the
+/// QR-code/Reed-Solomon reduction polynomial 0x11D. `exp` is doubled to length
+/// 512 so `exp[log(a) + log(b)]` never needs a modulo.
+fn gf256_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+
+    let mut x: u16 = 1;
+    for i in 0..255usize {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11D;
+        }
+    }
+    for i in 255..512 {
+        exp[i] = exp[i - 255];
+    }
+
+    (exp, log)
+}
+
+fn gf_mul(tables: &([u8; 512], [u8; 256]), a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = tables;
+    exp[log[a as usize] as usize + log[b as usize] as usize]
+}
+
+fn gf_inv(tables: &([u8; 512], [u8; 256]), a: u8) -> u8 {
+    let (exp, log) = tables;
+    exp[(255 - log[a as usize] as usize) % 255]
+}
+
+/// Invert a `k x k` matrix over GF(256) via Gauss-Jordan elimination on the
+/// matrix augmented with the identity.
+fn gf_invert_matrix(tables: &([u8; 512], [u8; 256]), matrix: &[Vec<u8>]) -> Option<Vec<Vec<u8>>> {
+    let k = matrix.len();
+    let mut aug: Vec<Vec<u8>> = matrix.iter().enumerate().map(|(i, row)| {
+        let mut r = row.clone();
+        let mut identity = vec![0u8; k];
+        identity[i] = 1;
+        r.extend(identity);
+        r
+    }).collect();
+
+    for col in 0..k {
+        let pivot = (col..k).find(|&r| aug[r][col] != 0)?;
+        aug.swap(col, pivot);
+
+        let inv = gf_inv(tables, aug[col][col]);
+        for c in 0..aug[col].len() {
+            aug[col][c] = gf_mul(tables, aug[col][c], inv);
+        }
+
+        for r in 0..k {
+            if r != col && aug[r][col] != 0 {
+                let factor = aug[r][col];
+                for c in 0..aug[r].len() {
+                    aug[r][c] ^= gf_mul(tables, factor, aug[col][c]);
+                }
+            }
+        }
+    }
+
+    Some(aug.iter().map(|row| row[k..].to_vec()).collect())
 }
 
 struct QRFileDecoder {
     file_decoders: HashMap<String, FountainDecoder>,
     current_active_decoder: Option<String>,
     output_dir: String,
+    /// Last time any QR code was successfully processed, across every file -
+    /// drives the `--ceremony-timeout` global stall check.
+    last_success_at: std::time::Instant,
 }
 
 impl QRFileDecoder {
@@ -238,18 +773,23 @@ impl QRFileDecoder {
             file_decoders: HashMap::new(),
             current_active_decoder: None,
             output_dir: "./decoded_files".to_string(),
+            last_success_at: std::time::Instant::now(),
         }
     }
 
     fn process_qr_code(&mut self, qr_data: &str, frame_index: usize) -> ProcessResult {
-        match self.try_process_qr_code(qr_data, frame_index) {
+        let result = match self.try_process_qr_code(qr_data, frame_index) {
             Ok(result) => result,
             Err(error) => ProcessResult {
                 is_valid: false,
                 qr_type: "error".to_string(),
                 reason: Some(error.to_string()),
             }
+        };
+        if result.is_valid {
+            self.last_success_at = std::time::Instant::now();
         }
+        result
     }
 
     fn try_process_qr_code(&mut self, qr_data: &str, frame_index: usize) -> Result<ProcessResult> {
@@ -257,6 +797,8 @@ impl QRFileDecoder {
             self.process_metadata_packet(qr_data, frame_index)
         } else if qr_data.starts_with("D:") {
             self.process_data_packet(qr_data, frame_index)
+        } else if qr_data.starts_with("R:") {
+            self.process_rs_packet(qr_data, frame_index)
         } else {
             Ok(ProcessResult {
                 is_valid: false,
@@ -279,12 +821,25 @@ impl QRFileDecoder {
             file_size: parts[4].parse()?,
             chunks_count: parts[5].parse()?,
             file_checksum: parts.get(13).filter(|s| !s.is_empty()).map(|s| s.to_string()),
+            compression: parts.get(14).filter(|s| !s.is_empty()).map(|s| s.to_string()),
+            original_size: parts.get(15).and_then(|s| s.parse().ok()),
         };
 
         // Initialize new file decoder if not exists
         if !self.file_decoders.contains_key(&metadata.file_name) {
             let mut decoder = FountainDecoder::new();
             decoder.initialize(metadata.clone());
+
+            // A resumed run may replay its whole input (e.g. a second pass
+            // over the same capture) after a file already finished and its
+            // `.streaming.json` was deleted - don't let that re-trigger a
+            // full decode and overwrite output already on disk.
+            let output_path = PathBuf::from(&self.output_dir).join(&metadata.file_name);
+            if output_path.metadata().map(|m| m.len() as usize == metadata.file_size).unwrap_or(false) {
+                println!("✅ {} already reconstructed on disk - skipping", metadata.file_name);
+                decoder.mark_already_finalized();
+            }
+
             self.file_decoders.insert(metadata.file_name.clone(), decoder);
         }
 
@@ -299,90 +854,39 @@ impl QRFileDecoder {
         })
     }
 
-    fn process_data_packet(&mut self, data_string: &str, _frame_index: usize) -> Result<ProcessResult> {
-        let parts: Vec<&str> = data_string.split(':').collect();
-        if parts.len() < 6 {
-            return Err(anyhow!("Invalid data packet format"));
-        }
-
-        let mut packet = DataPacket {
-            packet_id: parts[1].parse()?,
-            source_chunks: Vec::new(),
-            systematic_data_chunks: Vec::new(),
-            xor_data: None,
-        };
-
-        // Parse enhanced format - CORRECTED to match HTML script exactly
-        if parts.len() >= 7 {
-            let chunk_count = parts[5].parse::<usize>()?;
-            let data_field_offset = 6;
-
-            // Reconstruct data part by joining from dataFieldOffset onwards (critical fix!)
-            let all_data_part = parts[data_field_offset..].join(":");
-
-            if all_data_part.contains('|') {
-                // Systematic packet format: chunkIndex:base64Data|chunkIndex:base64Data
-                let records: Vec<&str> = all_data_part.split('|').collect();
-
-                // Debug: log packet structure for first few packets
-                if packet.packet_id <= 5 {
-                    println!("\n🔍 DEBUG Packet {}: chunkCount={}, records={}",
-                            packet.packet_id, chunk_count, records.len());
-                    println!("  AllDataPart length: {}", all_data_part.len());
-                    for (idx, record) in records.iter().enumerate() {
-                        if let Some(colon_index) = record.find(':') {
-                            println!("  Record {}: chunk {}, data length {}",
-                                    idx, &record[..colon_index], record.len() - colon_index - 1);
-                        } else {
-                            println!("  Record {}: no colon, length {}", idx, record.len());
-                        }
-                    }
-                }
-
-                for record in records {
-                    let chunk_parts: Vec<&str> = record.splitn(2, ':').collect();
-
-                    if chunk_parts.len() == 2 {
-                        let chunk_index: usize = chunk_parts[0].parse()?;
-                        let chunk_data_b64 = chunk_parts[1];
-
-                        if !chunk_data_b64.is_empty() {
-                            match general_purpose::STANDARD.decode(chunk_data_b64) {
-                                Ok(chunk_data) => {
-                                    packet.source_chunks.push(chunk_index);
-                                    packet.systematic_data_chunks.push(SystematicChunk {
-                                        chunk_index,
-                                        chunk_data,
-                                    });
-
-                                    if packet.packet_id <= 5 {
-                                        println!("    ✅ Decoded chunk {}: {} bytes",
-                                                chunk_index, packet.systematic_data_chunks.last().unwrap().chunk_data.len());
-                                    }
-                                },
-                                Err(e) => {
-                                    println!("❌ Failed to decode chunk {}: {}", chunk_index, e);
-                                }
-                            }
-                        }
-                    }
-                }
-            } else if all_data_part.contains(',') {
-                // Fountain packet: comma-separated indices
-                packet.source_chunks = all_data_part.split(',')
-                    .map(|s| s.parse())
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                // XOR data would be in next field for fountain packets
-                if parts.len() >= 8 {
-                    match general_purpose::STANDARD.decode(parts[7]) {
-                        Ok(xor_data) => packet.xor_data = Some(xor_data),
-                        Err(e) => println!("Failed to decode fountain XOR data: {}", e),
-                    }
+    /// Walk `qr_codes` sequentially, following `M:` packets to track
+    /// `current_active_decoder` exactly as `process_qr_code` would, but
+    /// instead of decoding `D:`/`R:` lines inline, bucket them by the file
+    /// they belong to. The buckets can then be decoded for each file
+    /// concurrently (line parsing and chunk recovery don't depend on
+    /// ordering once a file's lines are collected), since only this
+    /// temporal routing step is inherently sequential.
+    fn classify(&mut self, qr_codes: &[String]) -> HashMap<String, Vec<String>> {
+        let mut buckets: HashMap<String, Vec<String>> = HashMap::new();
+
+        for qr_code in qr_codes {
+            if qr_code.starts_with("M:") {
+                let _ = self.process_metadata_packet(qr_code, 0);
+            } else if qr_code.starts_with("D:") || qr_code.starts_with("R:") {
+                if let Some(name) = &self.current_active_decoder {
+                    buckets.entry(name.clone()).or_default().push(qr_code.clone());
                 }
             }
         }
 
+        buckets
+    }
+
+    fn process_data_packet(&mut self, data_string: &str, _frame_index: usize) -> Result<ProcessResult> {
+        let packet = parse_data_packet_str(data_string)?;
+        self.route_data_packet(packet)
+    }
+
+    /// Route an already-parsed data packet to its temporally-active
+    /// decoder. Split out from `process_data_packet` so the worker threads
+    /// in `process_streaming_jsonl` can parse packets off the main thread
+    /// and take the shared decoder lock only to route and apply them.
+    fn route_data_packet(&mut self, packet: DataPacket) -> Result<ProcessResult> {
         // Route to current active decoder (temporal routing - CRITICAL FIX!)
         let current_decoder_name = match &self.current_active_decoder {
             Some(name) => name.clone(),
@@ -421,6 +925,54 @@ impl QRFileDecoder {
             reason: None,
         })
     }
+
+    /// Parse `R:packet_id:block_id:share_index:n:k:base64Data` - a Reed-
+    /// Solomon share, as an alternative to XOR fountain packets that gives
+    /// bounded, deterministic recovery (any `k` of `n` shares) instead of
+    /// probabilistic peeling.
+    fn process_rs_packet(&mut self, data_string: &str, _frame_index: usize) -> Result<ProcessResult> {
+        let share = parse_rs_packet_str(data_string)?;
+        self.route_rs_share(share)
+    }
+
+    /// Route an already-parsed RS share; see `route_data_packet` for why
+    /// parsing and routing are split.
+    fn route_rs_share(&mut self, share: RsShare) -> Result<ProcessResult> {
+        let current_decoder_name = match &self.current_active_decoder {
+            Some(name) => name.clone(),
+            None => {
+                println!("⚠️ No active decoder for RS packet");
+                return Ok(ProcessResult {
+                    is_valid: false,
+                    qr_type: "rs".to_string(),
+                    reason: Some("No active decoder".to_string()),
+                });
+            }
+        };
+
+        let success = if let Some(decoder) = self.file_decoders.get_mut(&current_decoder_name) {
+            decoder.add_rs_share(share)
+        } else {
+            false
+        };
+
+        let is_complete = self.file_decoders.get(&current_decoder_name)
+            .map(|d| d.is_complete())
+            .unwrap_or(false);
+
+        if is_complete {
+            println!("\n🎉 File complete! Finalizing...");
+            if let Some(decoder) = self.file_decoders.get_mut(&current_decoder_name) {
+                let _ = decoder.finalize(&self.output_dir);
+            }
+        }
+
+        Ok(ProcessResult {
+            is_valid: success,
+            qr_type: "rs".to_string(),
+            reason: None,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -488,18 +1040,49 @@ fn main() -> Result<()> {
 
     let args: Vec<String> = std::env::args().collect();
 
+    if args.get(1).map(|s| s.as_str()) == Some("serve") {
+        let bind_addr = args.get(2).map(|s| s.as_str()).unwrap_or("0.0.0.0:8080");
+        return run_server(bind_addr);
+    }
+
     // Auto-detect stdin mode when input is piped
     let stdin_mode = args.iter().any(|arg| arg == "--stdin") || !std::io::stdin().is_terminal();
 
+    let resume = args.iter().any(|arg| arg == "--resume");
+
+    let chunk_timeout = std::time::Duration::from_secs(
+        args.iter().position(|a| a == "--chunk-timeout")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(60),
+    );
+    let ceremony_timeout = std::time::Duration::from_secs(
+        args.iter().position(|a| a == "--ceremony-timeout")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(300),
+    );
+
+    if let Some(pos) = args.iter().position(|arg| arg == "--camera") {
+        let device_path = args.get(pos + 1).map(|s| s.as_str()).unwrap_or("/dev/video0");
+        return process_streaming_camera(device_path, resume);
+    }
+
     if stdin_mode {
         println!("🌊 Processing streaming JSONL from stdin...");
-        return process_streaming_stdin();
+        return process_streaming_stdin(resume, chunk_timeout, ceremony_timeout);
     }
 
     if args.len() < 2 {
-        println!("Usage: {} <qr_codes.json> [--stream]", args[0]);
+        println!("Usage: {} <qr_codes.json> [--stream] [--resume] [--jobs N]", args[0]);
         println!("       echo jsonl | {}  (auto-detects piped input)", args[0]);
+        println!("       {} --camera [/dev/videoN] [--resume]  (live webcam capture)", args[0]);
+        println!("       {} serve [bind_addr]  (HTTP upload/decode server, default 0.0.0.0:8080)", args[0]);
         println!("  --stream: Process JSONL format with continuous progress saving");
+        println!("  --resume: Rehydrate recovered chunks from a previous run's *.partial.json/*.streaming.json");
+        println!("  --jobs N: Decode N files in parallel (default: available CPU cores)");
+        println!("  --chunk-timeout SECS: Warn when a file gets no new packets / no decode progress for this long (default 60)");
+        println!("  --ceremony-timeout SECS: Exit non-zero if nothing decodes at all for this long (default 300)");
         std::process::exit(1);
     }
 
@@ -509,7 +1092,7 @@ fn main() -> Result<()> {
     println!("📖 Loading QR codes from: {}", input_file);
 
     if stream_mode {
-        return process_streaming_jsonl(input_file);
+        return process_streaming_jsonl(input_file, resume, chunk_timeout, ceremony_timeout);
     }
 
     // Create output directory
@@ -550,27 +1133,43 @@ fn main() -> Result<()> {
 
     // Initialize decoder
     let mut decoder = QRFileDecoder::new();
-
-    // Process QR codes
-    let mut processed = 0;
-    let mut successful = 0;
-
-    for (i, qr_code) in qr_codes.iter().enumerate() {
-        if i % 100 == 0 {
-            println!("\nProcessing QR code {} / {}...", i + 1, qr_codes.len());
-        }
-
-        let result = decoder.process_qr_code(qr_code, i);
-        if result.is_valid {
-            successful += 1;
-        } else if let Some(reason) = result.reason {
-            if i < 10 { // Only show first few errors to avoid spam
-                println!("Warning: Failed to process QR {}: {}", i + 1, reason);
-            }
-        }
-        processed += 1;
+    if resume {
+        rehydrate_from_partials(&mut decoder, &decoder.output_dir.clone())?;
     }
 
+    // Phase 1 (sequential): follow M: packets to bucket D:/R: lines by the
+    // file they belong to. This temporal routing is the only part that
+    // can't be parallelized - everything downstream, per file, can be.
+    let buckets = decoder.classify(&qr_codes);
+    let processed = qr_codes.len();
+
+    let jobs = args.iter().position(|a| a == "--jobs")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+    println!("⚙️  Decoding {} files across up to {} worker threads...", buckets.len(), jobs);
+
+    // Phase 2 (parallel): decode each file's bucketed lines concurrently -
+    // recovering one file's chunks doesn't depend on any other file's.
+    let mut file_decoders: Vec<(String, FountainDecoder)> = decoder.file_decoders.drain().collect();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs.max(1)).build()?;
+    let successful: usize = pool.install(|| {
+        file_decoders.par_iter_mut()
+            .map(|(file_name, fountain_decoder)| {
+                let mut ok = 0usize;
+                if let Some(lines) = buckets.get(file_name) {
+                    for line in lines {
+                        if fountain_decoder.add_line(line) {
+                            ok += 1;
+                        }
+                    }
+                }
+                ok
+            })
+            .sum()
+    });
+    decoder.file_decoders = file_decoders.into_iter().collect();
+
     // Finalize any remaining files and save partial progress
     let mut completed_files = 0;
     let mut partial_files = 0;
@@ -621,7 +1220,9 @@ fn main() -> Result<()> {
                     "totalChunks": fountain_decoder.total_chunks,
                     "percentage": percentage,
                     "missingChunks": missing,
-                    "availableFountainPackets": fountain_decoder.coded_packets.len()
+                    "availableFountainPackets": fountain_decoder.coded_packets.len(),
+                    "sourceChunksB64": encode_source_chunks(&fountain_decoder.source_chunks),
+                    "codedPackets": encode_coded_packets(&fountain_decoder.coded_packets)
                 });
 
                 let partial_path = format!("./decoded_files/{}.partial.json", file_name);
@@ -690,9 +1291,22 @@ fn parse_jsonl_format(data_str: &str) -> Result<Vec<String>> {
     Ok(qr_codes)
 }
 
-fn process_streaming_jsonl(input_file: &str) -> Result<()> {
+/// Line-reading/JSON-parsing stays single-threaded here - only `M:` packets
+/// need to be, since `current_active_decoder` is temporal routing state that
+/// must be updated in file order. `D:`/`R:` lines are handed off to a small
+/// pool of worker threads (sized by `available_parallelism`), each given a
+/// deterministic slice of files by hashing the file name, so every packet
+/// for a given file always lands on the same worker and that file's chunk
+/// maps never see concurrent writers. Packet parsing (base64 decode, field
+/// validation) happens on the worker before it takes the shared decoder
+/// lock, so only the actual chunk-recovery step is serialized.
+fn process_streaming_jsonl(input_file: &str, resume: bool, chunk_timeout: std::time::Duration, ceremony_timeout: std::time::Duration) -> Result<()> {
     use std::io::{BufRead, BufReader};
     use std::fs::File;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
 
     println!("🌊 Processing streaming JSONL format with continuous progress saving");
 
@@ -701,13 +1315,66 @@ fn process_streaming_jsonl(input_file: &str) -> Result<()> {
 
     // Initialize decoder
     let mut decoder = QRFileDecoder::new();
+    if resume {
+        rehydrate_from_partials(&mut decoder, &decoder.output_dir.clone())?;
+    }
+    let decoder = Arc::new(Mutex::new(decoder));
+
+    let num_workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let processed = Arc::new(AtomicUsize::new(0));
+    let successful = Arc::new(AtomicUsize::new(0));
+
+    let mut senders = Vec::with_capacity(num_workers);
+    let mut handles = Vec::with_capacity(num_workers);
+    for _ in 0..num_workers {
+        let (tx, rx) = mpsc::sync_channel::<(String, String)>(256);
+        let decoder = decoder.clone();
+        let processed = processed.clone();
+        let successful = successful.clone();
+        let handle = std::thread::spawn(move || {
+            for (file_name, line) in rx {
+                let parsed_result = if line.starts_with("D:") {
+                    parse_data_packet_str(&line).map(PacketKind::Data)
+                } else {
+                    parse_rs_packet_str(&line).map(PacketKind::Rs)
+                };
+
+                let is_valid = match parsed_result {
+                    Ok(packet) => {
+                        let mut decoder = decoder.lock().unwrap();
+                        // Apply the packet to the file it was dispatched for,
+                        // not whichever file the reader has switched to since.
+                        let active = decoder.current_active_decoder.replace(file_name);
+                        let result = match packet {
+                            PacketKind::Data(packet) => decoder.route_data_packet(packet),
+                            PacketKind::Rs(share) => decoder.route_rs_share(share),
+                        };
+                        decoder.current_active_decoder = active;
+                        result.map(|r| r.is_valid).unwrap_or(false)
+                    }
+                    Err(_) => false,
+                };
+
+                processed.fetch_add(1, Ordering::Relaxed);
+                if is_valid {
+                    successful.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+        senders.push(tx);
+        handles.push(handle);
+    }
+
+    let worker_for = |file_name: &str| -> usize {
+        let mut hasher = DefaultHasher::new();
+        file_name.hash(&mut hasher);
+        (hasher.finish() as usize) % num_workers
+    };
 
     // Open file for line-by-line reading
     let file = File::open(input_file)?;
     let reader = BufReader::new(file);
 
-    let mut processed = 0;
-    let mut successful = 0;
     let mut qr_count = 0;
 
     for (line_num, line_result) in reader.lines().enumerate() {
@@ -726,28 +1393,45 @@ fn process_streaming_jsonl(input_file: &str) -> Result<()> {
                         video_info.width,
                         video_info.height);
             },
-            Ok(JsonlEntry::QrCode { frame_number, timestamp_ms: _, data }) => {
+            Ok(JsonlEntry::QrCode { frame_number: _, timestamp_ms: _, data }) => {
                 qr_count += 1;
 
-                // Process QR code immediately
-                let result = decoder.process_qr_code(&data, frame_number as usize);
-                if result.is_valid {
-                    successful += 1;
-                } else if let Some(reason) = result.reason {
-                    if qr_count <= 10 { // Only show first few errors
-                        println!("Warning: Failed to process QR {}: {}", qr_count, reason);
+                if data.starts_with("M:") {
+                    let mut guard = decoder.lock().unwrap();
+                    let result = guard.process_metadata_packet(&data, 0);
+                    drop(guard);
+                    processed.fetch_add(1, Ordering::Relaxed);
+                    match result {
+                        Ok(_) => { successful.fetch_add(1, Ordering::Relaxed); }
+                        Err(e) if qr_count <= 10 => println!("Warning: Failed to process QR {}: {}", qr_count, e),
+                        Err(_) => {}
+                    }
+                } else if data.starts_with("D:") || data.starts_with("R:") {
+                    let current_file = decoder.lock().unwrap().current_active_decoder.clone();
+                    match current_file {
+                        Some(name) => {
+                            let worker = worker_for(&name);
+                            if senders[worker].send((name, data)).is_err() {
+                                processed.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        None => {
+                            println!("⚠️ No active decoder for data packet");
+                            processed.fetch_add(1, Ordering::Relaxed);
+                        }
                     }
+                } else {
+                    processed.fetch_add(1, Ordering::Relaxed);
                 }
-                processed += 1;
 
                 // Save progress every 100 QR codes
                 if qr_count % 100 == 0 {
-                    println!("🔄 Processed {} QR codes (line {}), {} successful", qr_count, line_num + 1, successful);
-                    save_current_progress(&mut decoder, qr_count)?;
+                    println!("🔄 Dispatched {} QR codes (line {}), {} successful so far", qr_count, line_num + 1, successful.load(Ordering::Relaxed));
+                    let mut guard = decoder.lock().unwrap();
+                    save_current_progress(&mut guard, qr_count)?;
+                    run_stall_watchdog(&guard, chunk_timeout, ceremony_timeout);
+                    check_and_finalize_completed_files(&mut guard)?;
                 }
-
-                // Check for completed files and finalize them immediately
-                check_and_finalize_completed_files(&mut decoder)?;
             },
             Ok(JsonlEntry::Footer { summary }) => {
                 println!("📊 Processing summary: {} frames processed, {} QR codes found, {:.2}s processing time",
@@ -762,18 +1446,214 @@ fn process_streaming_jsonl(input_file: &str) -> Result<()> {
         }
     }
 
+    // Dropping the senders closes every worker's channel once its queue
+    // drains, so joining here waits for all in-flight packets to finish
+    // before the final progress save and finalization pass below.
+    drop(senders);
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    let mut decoder = Arc::try_unwrap(decoder).map_err(|_| anyhow!("decoder still has outstanding references"))?.into_inner().unwrap();
+
     // Final progress save
     save_current_progress(&mut decoder, qr_count)?;
 
     // Finalize any remaining files
     finalize_all_files(&mut decoder)?;
 
+    let processed = processed.load(Ordering::Relaxed);
+    let successful = successful.load(Ordering::Relaxed);
     println!("\n✅ Streaming processing complete: {}/{} QR codes successfully processed", successful, processed);
     println!("📁 Check './decoded_files' directory for extracted files");
 
     Ok(())
 }
 
+/// A parsed `D:`/`R:` line, tagged so a worker thread can route it through
+/// the right `QRFileDecoder` method once it has the shared lock.
+enum PacketKind {
+    Data(DataPacket),
+    Rs(RsShare),
+}
+
+/// Base64-encode recovered source chunks, keyed by (string) chunk index, so
+/// they can round-trip through JSON and be rehydrated into a new
+/// `FountainDecoder` by a later run instead of starting from zero.
+fn encode_source_chunks(chunks: &HashMap<usize, Vec<u8>>) -> HashMap<String, String> {
+    chunks.iter()
+        .map(|(idx, data)| (idx.to_string(), general_purpose::STANDARD.encode(data)))
+        .collect()
+}
+
+/// Encode the surviving fountain `coded_packets` (their `source_chunks` and
+/// base64 `xor_data`) so a resumed run can keep peeling with them instead of
+/// waiting for fresh fountain packets to reappear.
+fn encode_coded_packets(packets: &[DataPacket]) -> Vec<serde_json::Value> {
+    packets.iter().map(|p| serde_json::json!({
+        "packetId": p.packet_id,
+        "sourceChunks": p.source_chunks,
+        "xorDataB64": p.xor_data.as_ref().map(|d| general_purpose::STANDARD.encode(d)),
+    })).collect()
+}
+
+/// Scan `output_dir` for `*.partial.json`/`*.streaming.json` files left by a
+/// previous run and rehydrate a `FountainDecoder` per file from them -
+/// recovered chunk bytes and surviving coded packets included - so a second
+/// capture of the same stream can pick up where the first left off instead
+/// of re-decoding everything.
+fn rehydrate_from_partials(decoder: &mut QRFileDecoder, output_dir: &str) -> Result<()> {
+    let dir = std::path::Path::new(output_dir);
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !name.ends_with(".partial.json") && !name.ends_with(".streaming.json") {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let value: serde_json::Value = match serde_json::from_str(&content) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        let Some(file_name) = value.get("fileName").and_then(|v| v.as_str()) else { continue };
+        if decoder.file_decoders.contains_key(file_name) {
+            continue; // the first partial found for this file already won
+        }
+
+        let metadata_value = value.get("metadata");
+        let metadata = FileMetadata {
+            version: "1".to_string(),
+            file_name: file_name.to_string(),
+            file_type: String::new(),
+            file_size: metadata_value.and_then(|m| m.get("file_size")).and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+            chunks_count: metadata_value.and_then(|m| m.get("chunks_count")).and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+            file_checksum: None,
+            compression: None,
+            original_size: None,
+        };
+
+        let mut fountain = FountainDecoder::new();
+        fountain.initialize(metadata);
+
+        if let Some(chunks) = value.get("sourceChunksB64").and_then(|v| v.as_object()) {
+            for (idx_str, b64) in chunks {
+                let (Ok(idx), Some(b64)) = (idx_str.parse::<usize>(), b64.as_str()) else { continue };
+                if let Ok(bytes) = general_purpose::STANDARD.decode(b64) {
+                    if fountain.source_chunks.insert(idx, bytes).is_none() {
+                        fountain.recovered_chunk_count += 1;
+                    }
+                }
+            }
+        }
+
+        if let Some(packets) = value.get("codedPackets").and_then(|v| v.as_array()) {
+            for packet in packets {
+                let Some(packet_id) = packet.get("packetId").and_then(|v| v.as_u64()) else { continue };
+                let source_chunks: Vec<usize> = packet.get("sourceChunks")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| arr.iter().filter_map(|v| v.as_u64().map(|n| n as usize)).collect())
+                    .unwrap_or_default();
+                let xor_data = packet.get("xorDataB64")
+                    .and_then(|v| v.as_str())
+                    .and_then(|b64| general_purpose::STANDARD.decode(b64).ok());
+
+                fountain.coded_packets.push(DataPacket {
+                    packet_id: packet_id as usize,
+                    source_chunks,
+                    systematic_data_chunks: Vec::new(),
+                    xor_data,
+                });
+            }
+        }
+
+        println!("♻️  Resumed {} from {}: {}/{} chunks, {} coded packets carried over",
+                 file_name, name, fountain.recovered_chunk_count, fountain.total_chunks, fountain.coded_packets.len());
+
+        decoder.file_decoders.insert(file_name.to_string(), fountain);
+    }
+
+    Ok(())
+}
+
+/// Why a file looks stuck: has the feed gone quiet for it, or is it
+/// receiving packets that just aren't enough rank to peel another chunk?
+#[derive(Debug)]
+enum StallKind {
+    Feed,
+    Decode,
+}
+
+#[derive(Debug)]
+struct StalledFile {
+    file_name: String,
+    kind: StallKind,
+    stalled_secs: u64,
+    missing_chunks: usize,
+}
+
+/// Classify every incomplete, non-finalized file against `chunk_timeout`:
+/// no packets at all in that window is a feed stall (the capture/source
+/// dried up for this file); packets still arriving but `recovered_chunk_count`
+/// frozen is a decode stall (buffered fountain packets are rank-deficient).
+fn check_stalls(decoder: &QRFileDecoder, chunk_timeout: std::time::Duration) -> Vec<StalledFile> {
+    let mut stalled = Vec::new();
+    for (file_name, fd) in &decoder.file_decoders {
+        if fd.is_complete() {
+            continue;
+        }
+
+        let missing_chunks = fd.total_chunks.saturating_sub(fd.recovered_chunk_count);
+
+        if fd.last_packet_at.elapsed() >= chunk_timeout {
+            stalled.push(StalledFile {
+                file_name: file_name.clone(),
+                kind: StallKind::Feed,
+                stalled_secs: fd.last_packet_at.elapsed().as_secs(),
+                missing_chunks,
+            });
+        } else if fd.last_progress_at.elapsed() >= chunk_timeout && !fd.coded_packets.is_empty() {
+            stalled.push(StalledFile {
+                file_name: file_name.clone(),
+                kind: StallKind::Decode,
+                stalled_secs: fd.last_progress_at.elapsed().as_secs(),
+                missing_chunks,
+            });
+        }
+    }
+    stalled
+}
+
+/// Print a warning for every file `check_stalls` flags, then check the
+/// whole-stream `--ceremony-timeout`: if nothing has decoded successfully
+/// at all in that window, print a stuck-file summary and exit non-zero so
+/// automated pipelines can alert instead of hanging forever.
+fn run_stall_watchdog(decoder: &QRFileDecoder, chunk_timeout: std::time::Duration, ceremony_timeout: std::time::Duration) {
+    for stall in check_stalls(decoder, chunk_timeout) {
+        match stall.kind {
+            StallKind::Feed => println!("⏱️  Feed stall: no new QR codes for '{}' in {}s ({} chunks missing)",
+                                        stall.file_name, stall.stalled_secs, stall.missing_chunks),
+            StallKind::Decode => println!("⏱️  Decode stall: '{}' has buffered fountain packets but no progress in {}s ({} chunks missing)",
+                                          stall.file_name, stall.stalled_secs, stall.missing_chunks),
+        }
+    }
+
+    if decoder.last_success_at.elapsed() >= ceremony_timeout {
+        println!("\n❌ No QR code successfully processed in {}s - giving up. Stuck files:",
+                 decoder.last_success_at.elapsed().as_secs());
+        for stall in check_stalls(decoder, chunk_timeout) {
+            println!("   {} - {} chunks missing", stall.file_name, stall.missing_chunks);
+        }
+        std::process::exit(1);
+    }
+}
+
 fn save_current_progress(decoder: &mut QRFileDecoder, qr_count: usize) -> Result<()> {
     for (file_name, fountain_decoder) in &decoder.file_decoders {
         if !fountain_decoder.is_complete() {
@@ -800,7 +1680,9 @@ fn save_current_progress(decoder: &mut QRFileDecoder, qr_count: usize) -> Result
                     "missingChunks": missing,
                     "availableFountainPackets": fountain_decoder.coded_packets.len(),
                     "qrCodesProcessed": qr_count,
-                    "lastUpdated": chrono::Utc::now().to_rfc3339()
+                    "lastUpdated": chrono::Utc::now().to_rfc3339(),
+                    "sourceChunksB64": encode_source_chunks(&fountain_decoder.source_chunks),
+                    "codedPackets": encode_coded_packets(&fountain_decoder.coded_packets)
                 });
 
                 let partial_path = format!("./decoded_files/{}.streaming.json", file_name);
@@ -866,7 +1748,7 @@ fn finalize_all_files(decoder: &mut QRFileDecoder) -> Result<()> {
     Ok(())
 }
 
-fn process_streaming_stdin() -> Result<()> {
+fn process_streaming_stdin(resume: bool, chunk_timeout: std::time::Duration, ceremony_timeout: std::time::Duration) -> Result<()> {
     use std::io::{BufRead, BufReader};
 
     println!("🌊 Processing streaming JSONL from stdin with real-time file generation");
@@ -876,6 +1758,9 @@ fn process_streaming_stdin() -> Result<()> {
 
     // Initialize decoder
     let mut decoder = QRFileDecoder::new();
+    if resume {
+        rehydrate_from_partials(&mut decoder, &decoder.output_dir.clone())?;
+    }
 
     // Open stdin for line-by-line reading
     let stdin = std::io::stdin();
@@ -920,6 +1805,7 @@ fn process_streaming_stdin() -> Result<()> {
                     print!("\r🔄 Processed {} QR codes, {} successful", qr_count, successful);
                     std::io::stdout().flush().unwrap();
                     save_current_progress(&mut decoder, qr_count)?;
+                    run_stall_watchdog(&decoder, chunk_timeout, ceremony_timeout);
                 }
 
                 // Check for completed files and finalize them immediately
@@ -952,4 +1838,294 @@ fn process_streaming_stdin() -> Result<()> {
     println!("📁 Check './decoded_files' directory for extracted files");
 
     Ok(())
+}
+
+/// Capture QR codes live from a V4L2 device (e.g. `/dev/video0`) and feed
+/// them straight into the streaming decoder, so a file can be reconstructed
+/// by just pointing a webcam at a playing QR video instead of pre-extracting
+/// frames to JSONL first. Uses the same `save_current_progress`/
+/// `check_and_finalize_completed_files` calls as the JSONL/stdin paths,
+/// saving progress every 20 QR codes and checking for finished files
+/// after each one.
+fn process_streaming_camera(device_path: &str, resume: bool) -> Result<()> {
+    use v4l::buffer::Type;
+    use v4l::io::traits::CaptureStream;
+    use v4l::prelude::*;
+    use v4l::video::Capture;
+    use v4l::FourCC;
+
+    println!("📷 Capturing QR codes live from {}", device_path);
+
+    std::fs::create_dir_all("./decoded_files")?;
+
+    let mut decoder = QRFileDecoder::new();
+    if resume {
+        rehydrate_from_partials(&mut decoder, &decoder.output_dir.clone())?;
+    }
+
+    let mut dev = Device::with_path(device_path)?;
+
+    // Prefer MJPEG (one JPEG frame per buffer - `image` decodes it directly);
+    // fall back to YUYV (packed 4:2:2, where we only need the luma bytes).
+    let mut requested = dev.format()?;
+    requested.fourcc = FourCC::new(b"MJPG");
+    let format = match dev.set_format(&requested) {
+        Ok(f) if f.fourcc == FourCC::new(b"MJPG") => f,
+        _ => {
+            let mut requested = dev.format()?;
+            requested.fourcc = FourCC::new(b"YUYV");
+            dev.set_format(&requested)?
+        }
+    };
+
+    let is_mjpeg = format.fourcc == FourCC::new(b"MJPG");
+    if !is_mjpeg && format.fourcc != FourCC::new(b"YUYV") {
+        return Err(anyhow!(
+            "Camera returned unexpected pixel format: expected MJPG or YUYV, got {}",
+            format.fourcc
+        ));
+    }
+    println!("📷 Negotiated format: {} {}x{}", format.fourcc, format.width, format.height);
+
+    let mut stream = MmapStream::with_buffers(&mut dev, Type::VideoCapture, 4)?;
+
+    let mut processed = 0usize;
+    let mut successful = 0usize;
+    let mut frame_number = 0usize;
+    let mut last_payloads: HashSet<String> = HashSet::new();
+
+    loop {
+        let (buf, _meta) = stream.next()?;
+        frame_number += 1;
+
+        let luma = if is_mjpeg {
+            match image::load_from_memory(buf) {
+                Ok(img) => img.to_luma8(),
+                Err(e) => {
+                    println!("⚠️ Failed to decode MJPEG frame {}: {}", frame_number, e);
+                    continue;
+                }
+            }
+        } else {
+            yuyv_to_luma(buf, format.width, format.height)
+        };
+
+        let payloads = scan_camera_frame(&luma);
+        if payloads.is_empty() {
+            continue;
+        }
+
+        // A held-still frame re-decodes the same payload(s) every capture -
+        // skip it so `processed` isn't inflated by a camera pointed at a
+        // paused screen.
+        let this_frame: HashSet<String> = payloads.iter().cloned().collect();
+        if this_frame == last_payloads {
+            continue;
+        }
+        last_payloads = this_frame;
+
+        for data in &payloads {
+            let result = decoder.process_qr_code(data, frame_number);
+            if result.is_valid {
+                successful += 1;
+            } else if let Some(reason) = &result.reason {
+                println!("Warning: Failed to process QR at frame {}: {}", frame_number, reason);
+            }
+            processed += 1;
+
+            if processed % 20 == 0 {
+                print!("\r🔄 Processed {} QR codes, {} successful", processed, successful);
+                std::io::stdout().flush().unwrap();
+                save_current_progress(&mut decoder, processed)?;
+            }
+
+            check_and_finalize_completed_files(&mut decoder)?;
+        }
+
+        if !decoder.file_decoders.is_empty() && decoder.file_decoders.values().all(|fd| fd.is_complete()) {
+            break;
+        }
+    }
+
+    save_current_progress(&mut decoder, processed)?;
+    finalize_all_files(&mut decoder)?;
+
+    println!("\n✅ Camera capture complete: {}/{} QR codes successfully processed", successful, processed);
+    println!("📁 Check './decoded_files' directory for extracted files");
+
+    Ok(())
+}
+
+/// Extract the luma plane from a packed YUYV (4:2:2) buffer - every pixel
+/// has its own Y byte, interleaved with chroma shared between pixel pairs.
+fn yuyv_to_luma(data: &[u8], width: u32, height: u32) -> image::GrayImage {
+    let expected = (width * height) as usize;
+    let mut luma = Vec::with_capacity(expected);
+    for pixel in data.chunks_exact(2) {
+        luma.push(pixel[0]);
+        if luma.len() == expected {
+            break;
+        }
+    }
+    luma.resize(expected, 0);
+    image::GrayImage::from_raw(width, height, luma)
+        .unwrap_or_else(|| image::GrayImage::new(width, height))
+}
+
+/// Scan a grayscale camera frame for QR payloads, trying `rqrr` (fast, pure
+/// Rust) first and falling back to `quircs` - the same two-scanner fallback
+/// `qr_extraction.rs` uses for decoded video frames.
+fn scan_camera_frame(luma: &image::GrayImage) -> Vec<String> {
+    let mut codes = Vec::new();
+
+    let mut scanner = rqrr::PreparedImage::prepare(luma.clone());
+    for grid in scanner.detect_grids() {
+        if let Ok((_, content)) = grid.decode() {
+            codes.push(content);
+        }
+    }
+
+    if codes.is_empty() {
+        let mut decoder = quircs::Quirc::new();
+        for code in decoder.identify(luma.width() as usize, luma.height() as usize, luma) {
+            if let Ok(valid_code) = code {
+                if let Ok(decoded) = valid_code.decode() {
+                    if let Ok(content) = String::from_utf8(decoded.payload) {
+                        codes.push(content);
+                    }
+                }
+            }
+        }
+    }
+
+    codes
+}
+
+/// One upload's worth of decode state, kept alive for the lifetime of the
+/// server so `GET /jobs/{id}/progress` and `/files/{name}` can be polled
+/// after the upload finishes. Each job gets its own decoder and output
+/// directory (`./decoded_files/<job_id>/`) so concurrent uploads don't
+/// collide the way the CLI's hardcoded `./decoded_files` would.
+struct ServerJob {
+    decoder: std::sync::Mutex<QRFileDecoder>,
+    output_dir: PathBuf,
+}
+
+struct ServerState {
+    jobs: std::sync::Mutex<HashMap<String, std::sync::Arc<ServerJob>>>,
+    next_job_id: std::sync::atomic::AtomicU64,
+}
+
+fn internal_error<E: std::fmt::Display>(e: E) -> (axum::http::StatusCode, String) {
+    (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+/// Run the `qrf serve` HTTP server: upload a JSONL capture, poll its
+/// progress, download reconstructed files once complete. Decodes each
+/// uploaded line in turn the way `process_streaming_stdin` does, just fed
+/// from an HTTP request body instead of stdin.
+fn run_server(bind_addr: &str) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(serve(bind_addr))
+}
+
+async fn serve(bind_addr: &str) -> Result<()> {
+    use axum::routing::{get, post};
+    use axum::Router;
+    use axum::extract::DefaultBodyLimit;
+
+    let state = std::sync::Arc::new(ServerState {
+        jobs: std::sync::Mutex::new(HashMap::new()),
+        next_job_id: std::sync::atomic::AtomicU64::new(1),
+    });
+
+    let app = Router::new()
+        .route("/jobs", post(create_job))
+        .route("/jobs/:id/progress", get(job_progress))
+        .route("/jobs/:id/files/:name", get(download_file))
+        // Captures can be gigabytes of JSONL - the default 2MB body limit
+        // would reject almost every real upload.
+        .layer(DefaultBodyLimit::max(4 * 1024 * 1024 * 1024))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(bind_addr).await?;
+    println!("🌐 qrf serve listening on {}", bind_addr);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn create_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<ServerState>>,
+    mut multipart: axum::extract::Multipart,
+) -> Result<axum::Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+    let job_id = state.next_job_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst).to_string();
+    let output_dir = PathBuf::from("./decoded_files").join(&job_id);
+    std::fs::create_dir_all(&output_dir).map_err(internal_error)?;
+
+    let mut decoder = QRFileDecoder::new();
+    decoder.output_dir = output_dir.to_string_lossy().to_string();
+
+    while let Some(field) = multipart.next_field().await.map_err(internal_error)? {
+        let body = field.bytes().await.map_err(internal_error)?;
+        for line in body.split(|&b| b == b'\n') {
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Ok(JsonlEntry::QrCode { frame_number, data, .. }) = serde_json::from_str::<JsonlEntry>(line) {
+                decoder.process_qr_code(&data, frame_number as usize);
+                let _ = check_and_finalize_completed_files(&mut decoder);
+            }
+        }
+    }
+
+    let _ = save_current_progress(&mut decoder, 0);
+
+    state.jobs.lock().unwrap().insert(job_id.clone(), std::sync::Arc::new(ServerJob {
+        decoder: std::sync::Mutex::new(decoder),
+        output_dir,
+    }));
+
+    Ok(axum::Json(serde_json::json!({ "jobId": job_id })))
+}
+
+async fn job_progress(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<ServerState>>,
+    axum::extract::Path(job_id): axum::extract::Path<String>,
+) -> Result<axum::Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&job_id)
+        .ok_or((axum::http::StatusCode::NOT_FOUND, format!("unknown job: {}", job_id)))?;
+    let decoder = job.decoder.lock().unwrap();
+
+    // Mirrors the structure save_current_progress writes to *.streaming.json.
+    let files: Vec<serde_json::Value> = decoder.file_decoders.iter().map(|(file_name, fd)| {
+        let percentage = ((fd.recovered_chunk_count as f64 / fd.total_chunks.max(1) as f64) * 100.0).round() as usize;
+        let missing: Vec<usize> = (0..fd.total_chunks).filter(|i| !fd.source_chunks.contains_key(i)).collect();
+        serde_json::json!({
+            "fileName": file_name,
+            "recoveredChunks": fd.recovered_chunk_count,
+            "totalChunks": fd.total_chunks,
+            "percentage": percentage,
+            "missingChunks": missing,
+            "availableFountainPackets": fd.coded_packets.len(),
+            "complete": fd.is_complete(),
+        })
+    }).collect();
+
+    Ok(axum::Json(serde_json::json!({ "jobId": job_id, "files": files })))
+}
+
+async fn download_file(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<ServerState>>,
+    axum::extract::Path((job_id, file_name)): axum::extract::Path<(String, String)>,
+) -> Result<Vec<u8>, (axum::http::StatusCode, String)> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&job_id)
+        .ok_or((axum::http::StatusCode::NOT_FOUND, format!("unknown job: {}", job_id)))?;
+    let path = job.output_dir.join(&file_name);
+    std::fs::read(&path)
+        .map_err(|_| (axum::http::StatusCode::NOT_FOUND, format!("{} not finalized yet", file_name)))
 }
\ No newline at end of file
@@ -161,27 +161,29 @@ pub fn setup_signal_handlers(error_handler: Arc<ErrorHandler>) -> Result<()> {
     Ok(())
 }
 
-// Memory monitoring
+/// Current process resident memory, in bytes and as a percentage of total
+/// system RAM. Backed by `sysinfo`, which reads `/proc/self/statm` on Linux,
+/// `GetProcessMemoryInfo` on Windows, and `task_info` on macOS - the same
+/// cross-platform dependency `resume_state::available_memory_bytes` already
+/// uses, rather than three hand-rolled platform backends here too.
 pub fn check_memory_usage() -> Result<(u64, f64)> {
-    // Get current memory usage in bytes and percentage
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-
-        let output = Command::new("ps")
-            .args(&["-o", "rss=", "-p", &std::process::id().to_string()])
-            .output()?;
-
-        if let Ok(rss_str) = String::from_utf8(output.stdout) {
-            if let Ok(rss_kb) = rss_str.trim().parse::<u64>() {
-                let bytes = rss_kb * 1024;
-                let percentage = (bytes as f64 / (8.0 * 1024.0 * 1024.0 * 1024.0)) * 100.0; // Assume 8GB total
-                return Ok((bytes, percentage));
-            }
-        }
-    }
+    let pid = sysinfo::get_current_pid()
+        .map_err(|e| anyhow!("Failed to determine current process id: {}", e))?;
+
+    let mut system = sysinfo::System::new();
+    system.refresh_process(pid);
+    system.refresh_memory();
+
+    let resident_bytes = system.process(pid).map(|p| p.memory()).unwrap_or(0);
+    let total_bytes = system.total_memory();
+
+    let percentage = if total_bytes > 0 {
+        (resident_bytes as f64 / total_bytes as f64) * 100.0
+    } else {
+        0.0
+    };
 
-    Ok((0, 0.0))
+    Ok((resident_bytes, percentage))
 }
 
 // Disk space monitoring
@@ -1,10 +1,47 @@
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use chrono::Utc;
 
+/// Minimum severity a record must have to be written. Ordered so
+/// `level >= min_level` is a plain comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    QrData,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::QrData => "QR_DATA",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+struct LogState {
+    writer: BufWriter<File>,
+    bytes_written: u64,
+    last_flush: Instant,
+}
+
 pub struct ErrorLogger {
-    log_file: Mutex<std::fs::File>,
+    log_path: PathBuf,
+    log_file: Mutex<LogState>,
+    json_sink: Mutex<Option<BufWriter<File>>>,
+    min_level: LogLevel,
+    max_bytes: u64,
+    max_backups: u32,
+    flush_interval: Duration,
 }
 
 impl ErrorLogger {
@@ -13,9 +50,20 @@ impl ErrorLogger {
             .create(true)
             .append(true)
             .open(log_path)?;
+        let bytes_written = file.metadata().map(|m| m.len()).unwrap_or(0);
 
         let logger = Self {
-            log_file: Mutex::new(file),
+            log_path: PathBuf::from(log_path),
+            log_file: Mutex::new(LogState {
+                writer: BufWriter::new(file),
+                bytes_written,
+                last_flush: Instant::now(),
+            }),
+            json_sink: Mutex::new(None),
+            min_level: LogLevel::Debug,
+            max_bytes: 0, // 0 = rotation disabled
+            max_backups: 5,
+            flush_interval: Duration::from_secs(1),
         };
 
         // Write session header
@@ -24,28 +72,109 @@ impl ErrorLogger {
         Ok(logger)
     }
 
+    /// Suppress records below `level` (e.g. `LogLevel::Info` to drop
+    /// `DEBUG`/`QR_DATA` noise in production).
+    pub fn with_min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Rotate `<log_path>` to `<log_path>.1..max_backups` once it exceeds
+    /// `max_bytes`, keeping a bounded ring of old files.
+    pub fn with_rotation(mut self, max_bytes: u64, max_backups: u32) -> Self {
+        self.max_bytes = max_bytes;
+        self.max_backups = max_backups.max(1);
+        self
+    }
+
+    /// Additionally emit each record as a JSON-lines object
+    /// (`{"ts","level","context","message","chunk_id"}`) to `json_path`.
+    pub fn with_json_sink(self, json_path: &str) -> Result<Self, std::io::Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(json_path)?;
+        *self.json_sink.lock().unwrap() = Some(BufWriter::new(file));
+        Ok(self)
+    }
+
     pub fn log_error(&self, context: &str, error: &str) {
-        self.write_log("ERROR", context, error);
+        self.write_log(LogLevel::Error, context, error, None);
     }
 
     pub fn log_warning(&self, context: &str, message: &str) {
-        self.write_log("WARN", context, message);
+        self.write_log(LogLevel::Warn, context, message, None);
     }
 
     pub fn log_info(&self, message: &str) {
-        self.write_log("INFO", "SYSTEM", message);
+        self.write_log(LogLevel::Info, "SYSTEM", message, None);
     }
 
     pub fn log_debug(&self, context: &str, details: &str) {
-        self.write_log("DEBUG", context, details);
+        self.write_log(LogLevel::Debug, context, details, None);
     }
 
-    fn write_log(&self, level: &str, context: &str, message: &str) {
-        if let Ok(mut file) = self.log_file.lock() {
-            let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
-            let log_line = format!("[{}] {} [{}]: {}\n", timestamp, level, context, message);
-            let _ = file.write_all(log_line.as_bytes());
-            let _ = file.flush();
+    fn write_log(&self, level: LogLevel, context: &str, message: &str, chunk_id: Option<usize>) {
+        if level < self.min_level {
+            return;
+        }
+
+        let now = Utc::now();
+        let timestamp = now.format("%Y-%m-%d %H:%M:%S%.3f");
+        let log_line = format!("[{}] {} [{}]: {}\n", timestamp, level.as_str(), context, message);
+
+        if let Ok(mut state) = self.log_file.lock() {
+            let _ = state.writer.write_all(log_line.as_bytes());
+            state.bytes_written += log_line.len() as u64;
+
+            let due_for_flush = state.last_flush.elapsed() >= self.flush_interval;
+            let due_for_rotation = self.max_bytes > 0 && state.bytes_written >= self.max_bytes;
+
+            if due_for_flush || due_for_rotation {
+                let _ = state.writer.flush();
+                state.last_flush = Instant::now();
+            }
+
+            if due_for_rotation {
+                self.rotate(&mut state);
+            }
+        }
+
+        if let Ok(mut sink) = self.json_sink.lock() {
+            if let Some(writer) = sink.as_mut() {
+                let record = serde_json::json!({
+                    "ts": now.to_rfc3339(),
+                    "level": level.as_str(),
+                    "context": context,
+                    "message": message,
+                    "chunk_id": chunk_id,
+                });
+                if let Ok(line) = serde_json::to_string(&record) {
+                    let _ = writeln!(writer, "{}", line);
+                    let _ = writer.flush();
+                }
+            }
+        }
+    }
+
+    /// Rename `log_path` to `log_path.1`, shifting existing `.1..max_backups-1`
+    /// up by one and dropping anything past the ring's capacity, then reopen
+    /// a fresh file for subsequent writes.
+    fn rotate(&self, state: &mut LogState) {
+        let _ = state.writer.flush();
+
+        for i in (1..self.max_backups).rev() {
+            let from = backup_path(&self.log_path, i);
+            let to = backup_path(&self.log_path, i + 1);
+            if from.exists() {
+                let _ = fs::rename(&from, &to);
+            }
+        }
+        let _ = fs::rename(&self.log_path, backup_path(&self.log_path, 1));
+
+        if let Ok(file) = OpenOptions::new().create(true).append(true).open(&self.log_path) {
+            state.writer = BufWriter::new(file);
+            state.bytes_written = 0;
         }
     }
 
@@ -55,7 +184,7 @@ impl ErrorLogger {
         } else {
             qr_data.to_string()
         };
-        self.write_log("QR_DATA", &format!("CHUNK_{}", chunk_id), &preview);
+        self.write_log(LogLevel::QrData, &format!("CHUNK_{}", chunk_id), &preview, Some(chunk_id));
     }
 
     pub fn log_base64_error(&self, chunk_id: usize, data: &str, error: &str) {
@@ -64,11 +193,36 @@ impl ErrorLogger {
         } else {
             data.to_string()
         };
-        self.write_log("BASE64_ERROR", &format!("CHUNK_{}", chunk_id),
-                      &format!("Error: {} | Data: {}", error, preview));
+        self.write_log(LogLevel::Error, &format!("CHUNK_{}", chunk_id),
+                      &format!("Error: {} | Data: {}", error, preview), Some(chunk_id));
     }
 
     pub fn log_processing_phase(&self, phase: &str, details: &str) {
-        self.write_log("PHASE", phase, details);
+        self.write_log(LogLevel::Info, phase, details, None);
     }
-}
\ No newline at end of file
+
+    /// Flush both sinks immediately, regardless of the flush timer.
+    pub fn flush(&self) {
+        if let Ok(mut state) = self.log_file.lock() {
+            let _ = state.writer.flush();
+            state.last_flush = Instant::now();
+        }
+        if let Ok(mut sink) = self.json_sink.lock() {
+            if let Some(writer) = sink.as_mut() {
+                let _ = writer.flush();
+            }
+        }
+    }
+}
+
+impl Drop for ErrorLogger {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn backup_path(base: &Path, index: u32) -> PathBuf {
+    let mut name = base.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
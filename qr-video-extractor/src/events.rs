@@ -27,18 +27,30 @@ pub enum ProcessingEvent {
     ChunkStarted {
         chunk_id: usize,
         chunk_name: String,
+        /// Which broker worker slot picked up this chunk, so the TUI's
+        /// per-worker lane view can show what each worker is decoding.
+        worker_id: usize,
     },
     ChunkProgress {
         chunk_id: usize,
         frames_processed: usize,
         qr_codes_found: usize,
         status: String,
+        /// Smoothed frames-per-second from this chunk's own `RateEstimator`.
+        fps: f64,
+        /// Time remaining for this chunk at `fps`, or `None` when the total
+        /// frame count isn't known at this granularity (e.g. `--live-camera`)
+        /// or not enough samples have landed yet.
+        eta_secs: Option<u64>,
     },
     ChunkCompleted {
         chunk_id: usize,
         qr_codes_found: usize,
         jsonl_file: String,
         duration_ms: u64,
+        /// Worker slot that finished this chunk, so the TUI lane view can
+        /// clear its "currently decoding" slot.
+        worker_id: usize,
     },
     FileReconstructed {
         file_name: String,
@@ -78,6 +90,95 @@ pub enum ProcessingEvent {
         frames_processed: u64,
         total_frames: u64,
         qr_codes_found: usize,
+        /// Worker slot decoding this chunk, so the lane view's QR-per-second
+        /// rate tracks the right row.
+        worker_id: usize,
+        /// Smoothed frames-per-second from this chunk's own `RateEstimator`.
+        fps: f64,
+        /// `(total_frames - frames_processed) / fps`, or `None` until enough
+        /// samples exist to trust the rate.
+        eta_secs: Option<u64>,
+    },
+    /// Emitted once per run after the worker count is sized against
+    /// available system memory (an explicit `--threads` is used as given),
+    /// so the effective worker count a run actually used is visible even
+    /// when it differs from what was requested.
+    WorkerSizing {
+        requested_threads: usize,
+        effective_threads: usize,
+        available_memory_bytes: u64,
+        per_worker_estimate_bytes: u64,
+    },
+    /// Emitted once after a Ctrl-C triggers a graceful stop: in-flight
+    /// chunks have finished and flushed their JSONL, and `resume_command`
+    /// is the exact invocation that continues from here.
+    GracefulStop {
+        chunks_completed: usize,
+        chunks_total: usize,
+        resume_command: String,
+        /// Where this run's output lives, so a session report can still be
+        /// written for a stop that never reaches `FinalSummary`.
+        output_dir: String,
+    },
+    /// Emitted by a `ChunkBroker` each time a chunk's `work` fails but hasn't
+    /// yet exhausted `max_tries`, just before the retry's backoff delay -
+    /// distinct from `Error`, which only fires once a chunk gives up for
+    /// good, so a transient failure that recovers on retry is still visible.
+    ChunkRetry {
+        chunk_id: usize,
+        attempt: u32,
+        max_tries: u32,
+        reason: String,
+    },
+    /// Emitted once per `split_by_count`/`split_by_duration` call, right
+    /// after chunk boundaries are finalized (and, in `--split-mode keyframe`,
+    /// snapped onto keyframes), so the TUI can show each chunk's real
+    /// start/end offsets instead of guessing from `chunk_count`.
+    ChunkBoundariesPlanned {
+        boundaries: Vec<(usize, f64, f64)>,
+        keyframe_aligned: bool,
+    },
+    /// Emitted once by `--monitor` when every chunk it is watching transitions
+    /// to complete, so an operator (or a script watching the JSON-line
+    /// output) can tell "job is done" apart from "a chunk happened to stop
+    /// stalling".
+    MonitorJobComplete {
+        chunk_count: usize,
+        total_qr_codes: usize,
+        elapsed_secs: f64,
+    },
+    /// Granular progress while one chunk's JSONL is being read and combined
+    /// in Phase 3, fired periodically rather than just once per chunk so a
+    /// progress bar has something to animate on a large chunk.
+    ChunkCombineProgress {
+        chunk_id: usize,
+        frames_done: usize,
+        frames_total: usize,
+    },
+    /// Granular progress while one reconstructed file's bytes are being
+    /// written to disk in Phase 3.
+    FileWriteProgress {
+        file_name: String,
+        bytes_written: u64,
+        bytes_total: u64,
+    },
+    /// Emitted once Phase 3 knows how many complete files it is about to
+    /// reconstruct, so a progress bar can be sized before the first
+    /// `FileReconstructed` arrives.
+    ReconstructionStarted {
+        files_total: usize,
+    },
+    /// One line of ffmpeg/ffprobe stderr, forwarded as it's read instead of
+    /// only surfacing in the failure message `StringOrBytes::last_lines`
+    /// attaches when a chunk split fails outright - so a warning (a dropped
+    /// frame, a timestamp reset) is visible even when the tool still exits
+    /// successfully.
+    ExternalToolOutput {
+        tool: String,
+        /// `None` for invocations that aren't tied to one video chunk (e.g.
+        /// the whole-input keyframe probe).
+        chunk_id: Option<usize>,
+        line: String,
     },
 }
 
@@ -107,13 +208,14 @@ impl OutputHandler for ConsoleOutputHandler {
             ProcessingEvent::AllCompleted { total_duration_ms, files_extracted } => {
                 println!("🎉 All processing completed! Extracted {} files in {}ms", files_extracted, total_duration_ms);
             }
-            ProcessingEvent::ChunkStarted { chunk_id, chunk_name } => {
+            ProcessingEvent::ChunkStarted { chunk_id, chunk_name, .. } => {
                 println!("▶️  Started chunk {}: {}", chunk_id + 1, chunk_name);
             }
-            ProcessingEvent::ChunkProgress { chunk_id, frames_processed, qr_codes_found, status } => {
-                println!("⏳ Chunk {}: {} - {} frames, {} QR codes", chunk_id + 1, status, frames_processed, qr_codes_found);
+            ProcessingEvent::ChunkProgress { chunk_id, frames_processed, qr_codes_found, status, fps, eta_secs } => {
+                let eta = eta_secs.map(|s| format!(", ETA {}s", s)).unwrap_or_default();
+                println!("⏳ Chunk {}: {} - {} frames, {} QR codes ({:.1} f/s{})", chunk_id + 1, status, frames_processed, qr_codes_found, fps, eta);
             }
-            ProcessingEvent::ChunkCompleted { chunk_id, qr_codes_found, jsonl_file, duration_ms } => {
+            ProcessingEvent::ChunkCompleted { chunk_id, qr_codes_found, jsonl_file, duration_ms, .. } => {
                 println!("✅ Chunk {} completed: {} QR codes → {} ({}ms)", chunk_id + 1, qr_codes_found, jsonl_file, duration_ms);
             }
             ProcessingEvent::FileReconstructed { file_name, file_size, checksum_valid, output_path } => {
@@ -139,10 +241,180 @@ impl OutputHandler for ConsoleOutputHandler {
             ProcessingEvent::ModeTransition { from, to, reason } => {
                 eprintln!("{} ({}), switching from {} to {} mode...", reason, reason, from, to);
             }
-            ProcessingEvent::FrameProgress { chunk_id, frames_processed, total_frames, qr_codes_found } => {
+            ProcessingEvent::FrameProgress { chunk_id, frames_processed, total_frames, qr_codes_found, .. } => {
                 let progress = (*frames_processed as f64 / *total_frames as f64 * 100.0).min(100.0);
                 println!("Chunk {}: Frame {}/{} ({:.1}%) - {} QR codes", chunk_id + 1, frames_processed, total_frames, progress, qr_codes_found);
             }
+            ProcessingEvent::WorkerSizing { requested_threads, effective_threads, available_memory_bytes, per_worker_estimate_bytes } => {
+                if effective_threads < requested_threads {
+                    println!("⚠️  Using {} of {} requested threads ({} available, ~{} per worker)",
+                        effective_threads, requested_threads,
+                        format_bytes(*available_memory_bytes), format_bytes(*per_worker_estimate_bytes));
+                } else {
+                    println!("Using {} threads ({} available memory)", effective_threads, format_bytes(*available_memory_bytes));
+                }
+            }
+            ProcessingEvent::GracefulStop { chunks_completed, chunks_total, resume_command, .. } => {
+                println!("🛑 Stopped gracefully after {}/{} chunks", chunks_completed, chunks_total);
+                println!("   To continue: {}", resume_command);
+            }
+            ProcessingEvent::ChunkRetry { chunk_id, attempt, max_tries, reason } => {
+                println!("🔁 Chunk {} retrying ({}/{}): {}", chunk_id + 1, attempt, max_tries, reason);
+            }
+            ProcessingEvent::ChunkBoundariesPlanned { boundaries, keyframe_aligned } => {
+                let mode = if *keyframe_aligned { "keyframe-aligned" } else { "uniform" };
+                println!("✂️  Planned {} chunk boundaries ({})", boundaries.len(), mode);
+            }
+            ProcessingEvent::MonitorJobComplete { chunk_count, total_qr_codes, elapsed_secs } => {
+                println!("✅ All {} chunks complete ({} QR codes, {:.0}s elapsed)", chunk_count, total_qr_codes, elapsed_secs);
+            }
+            ProcessingEvent::ChunkCombineProgress { chunk_id, frames_done, frames_total } => {
+                if frames_done >= frames_total {
+                    println!("📄 Combined chunk {}: {} QR codes", chunk_id + 1, frames_total);
+                }
+            }
+            ProcessingEvent::FileWriteProgress { file_name, bytes_written, bytes_total } => {
+                if bytes_written >= bytes_total {
+                    println!("💾 Wrote {} ({} bytes)", file_name, bytes_total);
+                }
+            }
+            ProcessingEvent::ReconstructionStarted { files_total } => {
+                println!("Reconstructing {} file(s)...", files_total);
+            }
+            ProcessingEvent::ExternalToolOutput { tool, chunk_id, line } => {
+                let label = chunk_id.map(|id| format!("chunk {}", id + 1)).unwrap_or_else(|| "probe".to_string());
+                println!("🔧 [{} {}] {}", tool, label, line);
+            }
+        }
+    }
+}
+
+/// Layers an `indicatif` `MultiProgress` - one bar per JSONL chunk being
+/// combined, plus one overall bar tracking reconstructed files vs. total -
+/// on top of the same `ProcessingEvent` stream `ConsoleOutputHandler` prints
+/// as scrolling text. Falls back to `ConsoleOutputHandler` for every event
+/// the bars don't drive, so nothing is silently dropped.
+pub struct IndicatifOutputHandler {
+    multi: indicatif::MultiProgress,
+    chunk_bars: std::sync::Mutex<std::collections::HashMap<usize, indicatif::ProgressBar>>,
+    file_bar: std::sync::Mutex<Option<indicatif::ProgressBar>>,
+    overall_bar: std::sync::Mutex<Option<indicatif::ProgressBar>>,
+    /// Phase 2's per-chunk extraction spinners (lives separately from
+    /// `chunk_bars`, which Phase 3's `ChunkCombineProgress` uses - the two
+    /// phases never overlap in time, but keeping them apart means a chunk
+    /// id reused across phases can't hand one phase's half-finished bar to
+    /// the other).
+    extraction_bars: std::sync::Mutex<std::collections::HashMap<usize, indicatif::ProgressBar>>,
+    extraction_overall: std::sync::Mutex<Option<indicatif::ProgressBar>>,
+}
+
+impl IndicatifOutputHandler {
+    pub fn new() -> Self {
+        Self {
+            multi: indicatif::MultiProgress::new(),
+            chunk_bars: std::sync::Mutex::new(std::collections::HashMap::new()),
+            file_bar: std::sync::Mutex::new(None),
+            overall_bar: std::sync::Mutex::new(None),
+            extraction_bars: std::sync::Mutex::new(std::collections::HashMap::new()),
+            extraction_overall: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn bar_style() -> indicatif::ProgressStyle {
+        indicatif::ProgressStyle::with_template("{prefix:.bold} [{bar:30.cyan/blue}] {pos}/{len} {msg}")
+            .unwrap_or_else(|_| indicatif::ProgressStyle::default_bar())
+    }
+
+    fn spinner_style() -> indicatif::ProgressStyle {
+        indicatif::ProgressStyle::with_template("{prefix:.bold} {spinner} {msg}")
+            .unwrap_or_else(|_| indicatif::ProgressStyle::default_spinner())
+    }
+}
+
+impl OutputHandler for IndicatifOutputHandler {
+    fn handle_event(&self, event: &ProcessingEvent) {
+        match event {
+            ProcessingEvent::Progress { phase: 2, current, total, .. } => {
+                let mut overall = self.extraction_overall.lock().unwrap();
+                let bar = overall.get_or_insert_with(|| {
+                    let bar = self.multi.add(indicatif::ProgressBar::new(*total as u64));
+                    bar.set_style(Self::bar_style());
+                    bar.set_prefix("chunks");
+                    bar
+                });
+                bar.set_length(*total as u64);
+                bar.set_position(*current as u64);
+                if current >= total {
+                    bar.finish_with_message("extraction complete");
+                }
+            }
+            ProcessingEvent::ChunkStarted { chunk_id, chunk_name, .. } => {
+                let mut bars = self.extraction_bars.lock().unwrap();
+                let bar = bars.entry(*chunk_id).or_insert_with(|| {
+                    let bar = self.multi.add(indicatif::ProgressBar::new_spinner());
+                    bar.set_style(Self::spinner_style());
+                    bar.set_prefix(format!("chunk {}", chunk_id + 1));
+                    bar
+                });
+                bar.enable_steady_tick(std::time::Duration::from_millis(120));
+                bar.set_message(format!("starting ({})", chunk_name));
+            }
+            ProcessingEvent::ChunkProgress { chunk_id, frames_processed, qr_codes_found, status, .. } => {
+                if let Some(bar) = self.extraction_bars.lock().unwrap().get(chunk_id) {
+                    bar.set_message(format!("{} - {} frames, {} QR codes", status, frames_processed, qr_codes_found));
+                }
+            }
+            ProcessingEvent::ChunkCompleted { chunk_id, qr_codes_found, .. } => {
+                if let Some(bar) = self.extraction_bars.lock().unwrap().remove(chunk_id) {
+                    bar.finish_with_message(format!("{} QR codes", qr_codes_found));
+                }
+                ConsoleOutputHandler.handle_event(event);
+            }
+            ProcessingEvent::ChunkCombineProgress { chunk_id, frames_done, frames_total } => {
+                let mut bars = self.chunk_bars.lock().unwrap();
+                let bar = bars.entry(*chunk_id).or_insert_with(|| {
+                    let bar = self.multi.add(indicatif::ProgressBar::new(*frames_total as u64));
+                    bar.set_style(Self::bar_style());
+                    bar.set_prefix(format!("chunk {}", chunk_id + 1));
+                    bar
+                });
+                bar.set_length(*frames_total as u64);
+                bar.set_position(*frames_done as u64);
+                if frames_done >= frames_total {
+                    bar.finish_with_message("combined");
+                }
+            }
+            ProcessingEvent::ReconstructionStarted { files_total } => {
+                let mut overall = self.overall_bar.lock().unwrap();
+                let bar = self.multi.add(indicatif::ProgressBar::new(*files_total as u64));
+                bar.set_style(Self::bar_style());
+                bar.set_prefix("files");
+                *overall = Some(bar);
+            }
+            ProcessingEvent::FileReconstructed { file_name, .. } => {
+                if let Some(bar) = self.overall_bar.lock().unwrap().as_ref() {
+                    bar.set_message(file_name.clone());
+                    bar.inc(1);
+                }
+                ConsoleOutputHandler.handle_event(event);
+            }
+            ProcessingEvent::FileWriteProgress { file_name, bytes_written, bytes_total } => {
+                let mut file_bar = self.file_bar.lock().unwrap();
+                let bar = file_bar.get_or_insert_with(|| {
+                    let bar = self.multi.add(indicatif::ProgressBar::new(*bytes_total));
+                    bar.set_style(Self::bar_style());
+                    bar.set_prefix("writing");
+                    bar
+                });
+                bar.set_length(*bytes_total);
+                bar.set_message(file_name.clone());
+                bar.set_position(*bytes_written);
+                if bytes_written >= bytes_total {
+                    bar.finish_and_clear();
+                    *file_bar = None;
+                }
+            }
+            _ => ConsoleOutputHandler.handle_event(event),
         }
     }
 }
@@ -167,4 +439,15 @@ impl EventBus {
             callback(event.clone());
         }
     }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
 }
\ No newline at end of file
@@ -6,14 +6,17 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::hash::{Hash, Hasher};
-use std::io::BufWriter;
+use std::io::{BufWriter, Read, Write};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use crossbeam_queue::ArrayQueue;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
 
 use crate::events::{EventCallback, ProcessingEvent};
 use crate::qr_extraction::{QrCodeData, QrExtractionResults};
 use crate::error_logger::ErrorLogger;
+use crate::chunk_queue::{ChunkQueue, DoneManifest};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileMetadata {
@@ -23,15 +26,22 @@ pub struct FileMetadata {
     pub file_size: usize,
     pub chunks_count: usize,
     pub file_checksum: Option<String>,
+    /// Algorithm senders label per-chunk checksums with ("crc32" or
+    /// "sha256"); `None` when the sender attaches no per-chunk label.
+    #[serde(default)]
+    pub chunk_checksum_kind: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystematicChunk {
     pub chunk_index: usize,
     pub chunk_data: Vec<u8>,
+    /// Optional per-chunk integrity label (`crc32:<hex>`/`sha256:<hex>`, or
+    /// a bare hex digest sniffed the same way as `file_checksum`).
+    pub checksum: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataPacket {
     pub packet_id: usize,
     pub source_chunks: Vec<usize>,
@@ -48,6 +58,20 @@ pub struct ReconstructedFile {
     pub crc32: String,
     pub size: u64,
     pub file_path: String,
+    /// `false` when `metadata.file_checksum` was present but didn't match
+    /// the reconstructed bytes; `true` when it matched or was absent.
+    #[serde(default = "default_checksum_valid")]
+    pub checksum_valid: bool,
+    /// Set when content-hash dedup confirmed this file's bytes are
+    /// identical to another entry's - holds that entry's key, and
+    /// `file_path` is rewritten to point at its (canonical) copy instead of
+    /// a redundant one.
+    #[serde(default)]
+    pub alias_of: Option<String>,
+}
+
+fn default_checksum_valid() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,14 +81,31 @@ pub struct FinalReport {
     pub files: HashMap<String, ReconstructedFile>,
 }
 
+/// Where reconstructed files end up. `Loose` (the historical default)
+/// writes each file directly into `output_dir`; `Archive` packages them
+/// all into one `reconstructed.zip` instead; `Both` does both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    Loose,
+    Archive,
+    Both,
+}
+
 pub struct FileReconstructor {
     output_dir: PathBuf,
     active_files: HashMap<String, FileDecoder>,
     file_counter: usize,
     error_logger: Arc<ErrorLogger>,
+    strict_checksum_mode: bool,
+    output_mode: OutputMode,
+    /// `--restore-files` glob patterns; empty means restore everything.
+    restore_filters: Vec<String>,
+    /// `--remap` rules, in the order given: a file whose name starts with
+    /// `.0` is written under `.1` instead of `output_dir`.
+    remap_rules: Vec<(String, String)>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct FileDecoder {
     metadata: FileMetadata,
     chunks: HashMap<usize, Vec<u8>>,
@@ -73,6 +114,15 @@ struct FileDecoder {
     is_complete: bool,
 }
 
+/// On-disk shape of a `.qrstate` sidecar: a `FileDecoder` plus the
+/// `file_key` it was stored under, so loading it back doesn't depend on
+/// reversing the sidecar's (sanitized) file name.
+#[derive(Serialize, Deserialize)]
+struct PersistedFileState {
+    file_key: String,
+    decoder: FileDecoder,
+}
+
 impl FileReconstructor {
     pub fn new(output_dir: &PathBuf) -> Self {
         let log_path = output_dir.join("qr_processing.log");
@@ -92,6 +142,152 @@ impl FileReconstructor {
             active_files: HashMap::new(),
             file_counter: 0,
             error_logger,
+            strict_checksum_mode: false,
+            output_mode: OutputMode::Loose,
+            restore_filters: Vec::new(),
+            remap_rules: Vec::new(),
+        }
+    }
+
+    /// When `strict`, a checksum mismatch against `metadata.file_checksum`
+    /// aborts reconstruction instead of writing the file anyway and merely
+    /// recording the discrepancy in `FinalReport`.
+    pub fn with_strict_checksum_mode(mut self, strict: bool) -> Self {
+        self.strict_checksum_mode = strict;
+        self
+    }
+
+    pub fn with_output_mode(mut self, mode: OutputMode) -> Self {
+        self.output_mode = mode;
+        self
+    }
+
+    /// Restrict reconstruction to files whose embedded name matches at
+    /// least one `*`/`?` glob in `patterns` (`--restore-files`, repeatable).
+    /// An empty list (the default) restores everything, so recovering one
+    /// file from a huge archive doesn't pay the cost of decoding the rest.
+    pub fn with_restore_filters(mut self, patterns: Vec<String>) -> Self {
+        self.restore_filters = patterns;
+        self
+    }
+
+    /// Redirect files whose embedded name starts with `src_prefix` to
+    /// `dst_dir` instead of `output_dir` (`--remap src=dst`, repeatable).
+    /// Rules are tried in the order given; the first matching prefix wins.
+    pub fn with_remap_rules(mut self, rules: Vec<(String, String)>) -> Self {
+        self.remap_rules = rules;
+        self
+    }
+
+    /// Whether `file_name` should be reconstructed given `--restore-files`.
+    fn passes_restore_filter(&self, file_name: &str) -> bool {
+        self.restore_filters.is_empty()
+            || self.restore_filters.iter().any(|pattern| glob_match(pattern, file_name))
+    }
+
+    /// Where `file_name` should be written, honoring the first `--remap`
+    /// rule whose `src_prefix` it starts with.
+    fn resolve_output_path(&self, file_name: &str) -> PathBuf {
+        for (src_prefix, dst_dir) in &self.remap_rules {
+            if file_name.starts_with(src_prefix.as_str()) {
+                return PathBuf::from(dst_dir).join(file_name);
+            }
+        }
+        self.output_dir.join(file_name)
+    }
+
+    /// Like `new`, but also loads any `.qrstate` sidecars a previous run
+    /// left in `output_dir` and merges their recovered chunks into
+    /// `active_files`, so a second JSONL batch over the same video only
+    /// needs to supply what's still missing.
+    pub fn resume_from(output_dir: &PathBuf) -> Result<Self> {
+        let mut reconstructor = Self::new(output_dir);
+
+        let entries = match fs::read_dir(output_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(reconstructor),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("qrstate") {
+                continue;
+            }
+
+            let loaded = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| serde_json::from_str::<PersistedFileState>(&content).map_err(anyhow::Error::from));
+
+            match loaded {
+                Ok(persisted) => {
+                    reconstructor.error_logger.log_info(&format!(
+                        "Resumed {} from {}: {}/{} chunks already recovered",
+                        persisted.file_key, path.display(),
+                        persisted.decoder.received_chunks.len(), persisted.decoder.metadata.chunks_count
+                    ));
+
+                    // Keep `file_counter` past every resumed "file_NNN_..."
+                    // key so a fresh M: packet for an unrelated file can't
+                    // collide with one restored from disk.
+                    if let Some(counter) = persisted.file_key
+                        .strip_prefix("file_")
+                        .and_then(|rest| rest.split('_').next())
+                        .and_then(|n| n.parse::<usize>().ok())
+                    {
+                        reconstructor.file_counter = reconstructor.file_counter.max(counter);
+                    }
+
+                    reconstructor.active_files.insert(persisted.file_key, persisted.decoder);
+                }
+                Err(e) => {
+                    reconstructor.error_logger.log_warning("QRSTATE_LOAD", &format!("Failed to load {}: {}", path.display(), e));
+                }
+            }
+        }
+
+        Ok(reconstructor)
+    }
+
+    /// Outstanding chunk indices per active file, so a user knows exactly
+    /// which frames are still worth re-scanning instead of redoing the
+    /// whole video.
+    pub fn missing_chunks(&self) -> HashMap<String, Vec<usize>> {
+        self.active_files.iter()
+            .map(|(file_key, fd)| {
+                let missing: Vec<usize> = (0..fd.metadata.chunks_count)
+                    .filter(|i| !fd.chunks.contains_key(i))
+                    .collect();
+                (file_key.clone(), missing)
+            })
+            .collect()
+    }
+
+    fn qrstate_path(&self, file_key: &str) -> PathBuf {
+        let sanitized: String = file_key.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' { c } else { '_' })
+            .collect();
+        self.output_dir.join(format!("{}.qrstate", sanitized))
+    }
+
+    fn save_file_state(&self, file_key: &str, file_decoder: &FileDecoder) -> Result<()> {
+        let persisted = PersistedFileState {
+            file_key: file_key.to_string(),
+            decoder: file_decoder.clone(),
+        };
+        let content = serde_json::to_string_pretty(&persisted)?;
+        fs::write(self.qrstate_path(file_key), content)?;
+        Ok(())
+    }
+
+    /// Persist every active file's current progress to its `.qrstate`
+    /// sidecar. Called after routing so an interrupted run (or a later
+    /// batch fed the still-missing chunks) can pick up where this one left
+    /// off instead of starting over.
+    fn persist_active_files(&self) {
+        for (file_key, file_decoder) in &self.active_files {
+            if let Err(e) = self.save_file_state(file_key, file_decoder) {
+                self.error_logger.log_warning("QRSTATE_SAVE", &format!("Failed to persist state for {}: {}", file_key, e));
+            }
         }
     }
 
@@ -127,21 +323,28 @@ impl FileReconstructor {
 
         fs::create_dir_all(&self.output_dir)?;
 
-        let file_names: Vec<String> = self.active_files.keys().cloned().collect();
-        let _total_files = file_names.len();
+        let mut file_names: Vec<String> = self.active_files.keys().cloned().collect();
+        file_names.sort();
 
-        for (idx, file_name) in file_names.iter().enumerate() {
-            callback(ProcessingEvent::Progress {
-                phase: 3,
-                current: 3 + idx,
-                total: 6,
-                message: format!("Reconstructing file: {}", file_name),
-            });
+        callback(ProcessingEvent::Progress {
+            phase: 3,
+            current: 3,
+            total: 6,
+            message: format!("Reconstructing {} files...", file_names.len()),
+        });
+
+        let decoders: Vec<(String, FileDecoder)> = file_names.into_iter()
+            .filter_map(|name| self.active_files.remove(&name).map(|fd| (name, fd)))
+            .collect();
 
-            // Clone the file decoder to avoid borrow conflicts
-            if let Some(file_decoder) = self.active_files.remove(file_name) {
-                let reconstructed_file = self.reconstruct_file_owned(file_decoder, file_name)?;
-                final_report.files.insert(file_name.clone(), reconstructed_file);
+        for (file_name, outcome) in self.reconstruct_files_parallel(decoders, callback) {
+            match outcome {
+                Ok(reconstructed_file) => {
+                    final_report.files.insert(file_name, reconstructed_file);
+                }
+                Err(e) => {
+                    self.error_logger.log_error("FILE_RECONSTRUCTION", &format!("Failed to reconstruct {}: {}", file_name, e));
+                }
             }
         }
 
@@ -152,6 +355,8 @@ impl FileReconstructor {
             message: format!("Generated final report with {} files", final_report.files.len()),
         });
 
+        self.dedup_reconstructed_files(&mut final_report);
+        self.build_archive(&mut final_report)?;
         self.save_final_report(&final_report)?;
 
         Ok(final_report)
@@ -165,6 +370,23 @@ impl FileReconstructor {
     ) -> Result<FinalReport> {
         self.error_logger.log_processing_phase("JSONL_COMBINATION", "Starting JSONL combination and file reconstruction");
 
+        // The chunk_queue/done manifest (when present) is authoritative over
+        // what chunks this job planned and which actually finished, so a
+        // chunk whose JSONL is simply missing from the directory listing
+        // below gets flagged here instead of silently reconstructing with
+        // a gap in the data.
+        if let Some(queue) = ChunkQueue::load(output_dir)? {
+            let done = DoneManifest::load_or_create(output_dir)?;
+            let incomplete = done.pending(&queue);
+            if !incomplete.is_empty() {
+                self.error_logger.log_warning("INCOMPLETE_MANIFEST", &format!(
+                    "{} of {} planned chunks are not marked done in done.json: {:?}",
+                    incomplete.len(), queue.chunks.len(),
+                    incomplete.iter().map(|c| c.id + 1).collect::<Vec<_>>()
+                ));
+            }
+        }
+
         callback(ProcessingEvent::Progress {
             phase: 3,
             current: 1,
@@ -173,7 +395,7 @@ impl FileReconstructor {
         });
 
         // Step 1: Read all chunk JSONL files and combine QR data
-        let mut combined_qr_data = self.read_and_combine_jsonl_files(output_dir)?;
+        let mut combined_qr_data = self.read_and_combine_jsonl_files(output_dir, callback)?;
 
         // CRITICAL: Sort by frame number to process metadata packets first
         combined_qr_data.sort_by_key(|qr| qr.frame_number);
@@ -203,35 +425,14 @@ impl FileReconstructor {
             files: HashMap::new(),
         };
 
-        let total_files = self.active_files.len();
-        let mut processed_files = 0;
-
-        for (file_key, file_decoder) in self.active_files.clone() {
-            processed_files += 1;
-
-            callback(ProcessingEvent::Progress {
-                phase: 3,
-                current: 3 + processed_files,
-                total: 6,
-                message: format!("Reconstructing file {}/{}: {}", processed_files, total_files, file_decoder.metadata.file_name),
-            });
+        let mut file_keys: Vec<String> = self.active_files.keys().cloned().collect();
+        file_keys.sort();
 
+        let mut decoders = Vec::new();
+        for file_key in file_keys {
+            let Some(file_decoder) = self.active_files.remove(&file_key) else { continue };
             if file_decoder.is_complete {
-                match self.reconstruct_file_owned(file_decoder, &file_key) {
-                    Ok(reconstructed_file) => {
-                        callback(ProcessingEvent::FileReconstructed {
-                            file_name: file_key.clone(),
-                            file_size: reconstructed_file.size,
-                            checksum_valid: !reconstructed_file.qr_checksum.is_empty(),
-                            output_path: reconstructed_file.file_path.clone(),
-                        });
-
-                        final_report.files.insert(file_key.clone(), reconstructed_file);
-                    }
-                    Err(e) => {
-                        self.error_logger.log_error("FILE_RECONSTRUCTION", &format!("Failed to reconstruct {}: {}", file_key, e));
-                    }
-                }
+                decoders.push((file_key, file_decoder));
             } else {
                 self.error_logger.log_warning("INCOMPLETE_FILE", &format!("File {} is incomplete: {}/{} chunks",
                                                                          file_decoder.metadata.file_name,
@@ -240,6 +441,25 @@ impl FileReconstructor {
             }
         }
 
+        callback(ProcessingEvent::Progress {
+            phase: 3,
+            current: 3,
+            total: 6,
+            message: format!("Reconstructing {} complete files...", decoders.len()),
+        });
+        callback(ProcessingEvent::ReconstructionStarted { files_total: decoders.len() });
+
+        for (file_key, outcome) in self.reconstruct_files_parallel(decoders, callback) {
+            match outcome {
+                Ok(reconstructed_file) => {
+                    final_report.files.insert(file_key, reconstructed_file);
+                }
+                Err(e) => {
+                    self.error_logger.log_error("FILE_RECONSTRUCTION", &format!("Failed to reconstruct {}: {}", file_key, e));
+                }
+            }
+        }
+
         callback(ProcessingEvent::Progress {
             phase: 3,
             current: 6,
@@ -247,13 +467,15 @@ impl FileReconstructor {
             message: format!("Generated final report with {} files", final_report.files.len()),
         });
 
+        self.dedup_reconstructed_files(&mut final_report);
+        self.build_archive(&mut final_report)?;
         self.save_final_report(&final_report)?;
 
         Ok(final_report)
     }
 
     /// Read and combine all chunk JSONL files into a single QR data collection
-    fn read_and_combine_jsonl_files(&self, output_dir: &PathBuf) -> Result<Vec<QrCodeData>> {
+    fn read_and_combine_jsonl_files(&self, output_dir: &PathBuf, callback: &EventCallback) -> Result<Vec<QrCodeData>> {
         let mut combined_qr_data = Vec::new();
 
         // Read all chunk_*.jsonl files
@@ -266,18 +488,34 @@ impl FileReconstructor {
                     if name_str.starts_with("chunk_") && name_str.ends_with(".jsonl") {
                         self.error_logger.log_debug("JSONL_READ", &format!("Reading {}", name_str));
 
+                        let chunk_id = name_str
+                            .trim_start_matches("chunk_")
+                            .trim_end_matches(".jsonl")
+                            .parse::<usize>()
+                            .map(|n| n.saturating_sub(1))
+                            .unwrap_or(0);
+
                         let file_content = std::fs::read_to_string(&path)?;
-                        for line in file_content.lines() {
-                            if !line.trim().is_empty() {
-                                match serde_json::from_str::<QrCodeData>(line) {
-                                    Ok(qr_data) => {
-                                        combined_qr_data.push(qr_data);
-                                    }
-                                    Err(e) => {
-                                        self.error_logger.log_error("JSONL_PARSE", &format!("Failed to parse line in {}: {} | Line: {}", name_str, e, line));
-                                    }
+                        let lines: Vec<&str> = file_content.lines().filter(|l| !l.trim().is_empty()).collect();
+                        let frames_total = lines.len();
+
+                        for (i, line) in lines.iter().enumerate() {
+                            match serde_json::from_str::<QrCodeData>(line) {
+                                Ok(qr_data) => {
+                                    combined_qr_data.push(qr_data);
+                                }
+                                Err(e) => {
+                                    self.error_logger.log_error("JSONL_PARSE", &format!("Failed to parse line in {}: {} | Line: {}", name_str, e, line));
                                 }
                             }
+
+                            if (i + 1) % 500 == 0 || i + 1 == frames_total {
+                                callback(ProcessingEvent::ChunkCombineProgress {
+                                    chunk_id,
+                                    frames_done: i + 1,
+                                    frames_total,
+                                });
+                            }
                         }
                     }
                 }
@@ -295,26 +533,53 @@ impl FileReconstructor {
         callback: &EventCallback,
     ) -> Result<()> {
         let mut current_file_name: Option<String> = None;
+        // Set whenever the most recent `M:` named a file `--restore-files`
+        // excludes, so the `D:` packets that follow it are skipped quietly
+        // instead of logging a "no current file" warning per packet.
+        let mut skipping_filtered_file = false;
 
         for qr_data in qr_codes {
             if qr_data.data.starts_with("M:") {
                 match self.parse_metadata(&qr_data.data) {
                     Ok(metadata) => {
-                        self.file_counter += 1;
-                        let file_key = format!("file_{:03}_{}", self.file_counter, metadata.file_name);
-
-                        self.error_logger.log_info(&format!("New file detected: {} (size: {}, chunks: {})",
-                                                          metadata.file_name, metadata.file_size, metadata.chunks_count));
-
-                        let file_decoder = FileDecoder {
-                            metadata: metadata.clone(),
-                            chunks: HashMap::new(),
-                            received_chunks: HashSet::new(),
-                            coded_packets: Vec::new(),
-                            is_complete: false,
+                        if !self.passes_restore_filter(&metadata.file_name) {
+                            self.error_logger.log_debug("RESTORE_FILTER", &format!(
+                                "Skipping {}: does not match --restore-files filters", metadata.file_name));
+                            current_file_name = None;
+                            skipping_filtered_file = true;
+                            continue;
+                        }
+                        skipping_filtered_file = false;
+
+                        // A `.qrstate` sidecar (or an earlier M: packet in
+                        // this same batch) may already track this file -
+                        // reuse its decoder instead of resetting progress.
+                        let existing_key = self.active_files.iter()
+                            .find(|(_, fd)| fd.metadata.file_name == metadata.file_name)
+                            .map(|(key, _)| key.clone());
+
+                        let file_key = if let Some(key) = existing_key {
+                            self.error_logger.log_info(&format!("Resuming tracked file: {} ({})", metadata.file_name, key));
+                            key
+                        } else {
+                            self.file_counter += 1;
+                            let key = format!("file_{:03}_{}", self.file_counter, metadata.file_name);
+
+                            self.error_logger.log_info(&format!("New file detected: {} (size: {}, chunks: {})",
+                                                              metadata.file_name, metadata.file_size, metadata.chunks_count));
+
+                            let file_decoder = FileDecoder {
+                                metadata: metadata.clone(),
+                                chunks: HashMap::new(),
+                                received_chunks: HashSet::new(),
+                                coded_packets: Vec::new(),
+                                is_complete: false,
+                            };
+
+                            self.active_files.insert(key.clone(), file_decoder);
+                            key
                         };
 
-                        self.active_files.insert(file_key.clone(), file_decoder);
                         current_file_name = Some(file_key);
 
                         callback(ProcessingEvent::Progress {
@@ -346,7 +611,7 @@ impl FileReconstructor {
                     } else {
                         self.error_logger.log_warning("DATA_PACKET", &format!("No decoder found for file: {}", file_name));
                     }
-                } else {
+                } else if !skipping_filtered_file {
                     self.error_logger.log_warning("DATA_PACKET", "No current file name for data packet");
                 }
             } else {
@@ -354,6 +619,8 @@ impl FileReconstructor {
             }
         }
 
+        self.persist_active_files();
+
         Ok(())
     }
 
@@ -373,6 +640,11 @@ impl FileReconstructor {
         } else {
             None
         };
+        let chunk_checksum_kind = if parts.len() > 7 && !parts[7].is_empty() {
+            Some(parts[7].to_string())
+        } else {
+            None
+        };
 
         Ok(FileMetadata {
             version,
@@ -381,6 +653,7 @@ impl FileReconstructor {
             file_size,
             chunks_count,
             file_checksum,
+            chunk_checksum_kind,
         })
     }
 
@@ -390,6 +663,8 @@ impl FileReconstructor {
             return Err(anyhow!("Invalid data packet format"));
         }
 
+        let packet_id = parts[1].parse::<usize>().unwrap_or(0);
+
         // Handle both legacy and new formats like JavaScript
         let data_field_offset = if parts.len() >= 8 && parts[1].len() == 8 && parts[1].chars().all(|c| c.is_ascii_hexdigit()) {
             7  // New format with fileId
@@ -403,32 +678,82 @@ impl FileReconstructor {
 
         // Join remaining parts and split by pipe (like JavaScript)
         let all_data_part = parts[data_field_offset..].join(":");
-        let records: Vec<&str> = all_data_part.split('|').collect();
 
-        for record in records {
-            let chunk_parts: Vec<&str> = record.splitn(2, ':').collect();
-            if chunk_parts.len() == 2 {
-                if let Ok(chunk_index) = chunk_parts[0].parse::<usize>() {
-                    let chunk_data_b64 = chunk_parts[1];
-
-                    // Fix base64 padding like JavaScript
-                    let fixed_base64 = self.fix_base64_padding(chunk_data_b64);
+        if all_data_part.contains(',') {
+            // Fountain (XOR-coded) packet: "idx,idx,...:base64XorData" -
+            // covers multiple source chunks, recoverable only once all but
+            // one of them are known.
+            let split_at = all_data_part.find(':').unwrap_or(all_data_part.len());
+            let source_chunks: Vec<usize> = all_data_part[..split_at]
+                .split(',')
+                .filter_map(|s| s.parse().ok())
+                .collect();
+
+            let xor_data = if split_at < all_data_part.len() {
+                let fixed_base64 = self.fix_base64_padding(&all_data_part[split_at + 1..]);
+                match general_purpose::STANDARD.decode(&fixed_base64) {
+                    Ok(data) => Some(data),
+                    Err(e) => {
+                        self.error_logger.log_error("FOUNTAIN_DECODE", &format!("Failed to decode XOR data for packet {}: {}", packet_id, e));
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            if !source_chunks.is_empty() && xor_data.is_some() {
+                file_decoder.coded_packets.push(DataPacket {
+                    packet_id,
+                    source_chunks,
+                    systematic_data_chunks: Vec::new(),
+                    xor_data,
+                });
+                self.peel_coded_packets(file_decoder);
+            }
+        } else {
+            let records: Vec<&str> = all_data_part.split('|').collect();
+
+            for record in records {
+                // `chunkIndex:base64Data` or, when the sender attaches a
+                // per-chunk integrity label, `chunkIndex:base64Data:label`.
+                let chunk_parts: Vec<&str> = record.splitn(3, ':').collect();
+                if chunk_parts.len() >= 2 {
+                    if let Ok(chunk_index) = chunk_parts[0].parse::<usize>() {
+                        let chunk_data_b64 = chunk_parts[1];
+
+                        // Fix base64 padding like JavaScript
+                        let fixed_base64 = self.fix_base64_padding(chunk_data_b64);
+
+                        let chunk_data = match general_purpose::STANDARD.decode(&fixed_base64) {
+                            Ok(data) => {
+                                self.error_logger.log_debug("BASE64_DECODE", &format!("Successfully decoded chunk {}: {} bytes", chunk_index, data.len()));
+                                data
+                            }
+                            Err(e) => {
+                                self.error_logger.log_base64_error(chunk_index, chunk_data_b64, &e.to_string());
+                                // Skip invalid base64 data silently for TUI (like JavaScript)
+                                continue;
+                            }
+                        };
 
-                    let chunk_data = match general_purpose::STANDARD.decode(&fixed_base64) {
-                        Ok(data) => {
-                            self.error_logger.log_debug("BASE64_DECODE", &format!("Successfully decoded chunk {}: {} bytes", chunk_index, data.len()));
-                            data
-                        }
-                        Err(e) => {
-                            self.error_logger.log_base64_error(chunk_index, chunk_data_b64, &e.to_string());
-                            // Skip invalid base64 data silently for TUI (like JavaScript)
-                            continue;
+                        if let Some(label) = chunk_parts.get(2).filter(|l| !l.is_empty()) {
+                            if !self.verify_chunk_checksum(&chunk_data, label) {
+                                self.error_logger.log_warning("CHUNK_CHECKSUM", &format!(
+                                    "Chunk {} failed integrity check against label {} - leaving slot open for a later frame", chunk_index, label));
+                                continue;
+                            }
                         }
-                    };
 
-                    if chunk_index < file_decoder.metadata.chunks_count {
-                        file_decoder.chunks.insert(chunk_index, chunk_data);
-                        file_decoder.received_chunks.insert(chunk_index);
+                        // First *verified* chunk for an index wins; don't
+                        // let a later, unverified or differently-scanned
+                        // copy clobber data already accepted.
+                        if chunk_index < file_decoder.metadata.chunks_count
+                            && !file_decoder.chunks.contains_key(&chunk_index)
+                        {
+                            file_decoder.chunks.insert(chunk_index, chunk_data);
+                            file_decoder.received_chunks.insert(chunk_index);
+                        }
                     }
                 }
             }
@@ -441,6 +766,55 @@ impl FileReconstructor {
         Ok(())
     }
 
+    /// LT-style peeling over `coded_packets`: a packet whose `xor_data`
+    /// covers exactly one still-missing source chunk can be solved for that
+    /// chunk directly (XOR out every other covered chunk, which is already
+    /// known). Newly solved chunks can unblock other packets, so this keeps
+    /// sweeping until a full pass makes no further progress.
+    fn peel_coded_packets(&self, file_decoder: &mut FileDecoder) {
+        let mut progress = true;
+        while progress {
+            progress = false;
+            let mut i = file_decoder.coded_packets.len();
+
+            while i > 0 {
+                i -= 1;
+                let missing: Vec<usize> = file_decoder.coded_packets[i].source_chunks.iter()
+                    .filter(|idx| !file_decoder.chunks.contains_key(idx))
+                    .cloned()
+                    .collect();
+
+                if missing.is_empty() {
+                    // Every source chunk turned up some other way (e.g. a
+                    // later systematic record) - this equation is now moot.
+                    file_decoder.coded_packets.remove(i);
+                    continue;
+                }
+
+                if missing.len() == 1 {
+                    let packet = file_decoder.coded_packets.remove(i);
+                    let missing_idx = missing[0];
+                    if let Some(mut result) = packet.xor_data {
+                        for &idx in &packet.source_chunks {
+                            if idx != missing_idx {
+                                if let Some(chunk) = file_decoder.chunks.get(&idx) {
+                                    for j in 0..result.len().min(chunk.len()) {
+                                        result[j] ^= chunk[j];
+                                    }
+                                }
+                            }
+                        }
+
+                        self.error_logger.log_debug("FOUNTAIN_RECOVERY", &format!("Recovered chunk {} via peeling", missing_idx));
+                        file_decoder.chunks.insert(missing_idx, result);
+                        file_decoder.received_chunks.insert(missing_idx);
+                        progress = true;
+                    }
+                }
+            }
+        }
+    }
+
     /// Fix base64 padding issues (ported from JavaScript)
     fn fix_base64_padding(&self, base64: &str) -> String {
         // Remove any whitespace
@@ -454,13 +828,70 @@ impl FileReconstructor {
         cleaned
     }
 
+    /// Reconstruct many independent files concurrently over a bounded
+    /// worker pool (sized by `std::thread::available_parallelism`, capped
+    /// at one worker per file). Each `FileDecoder` is fully self-contained
+    /// once routing is done, so workers need only a shared `&self` -
+    /// `error_logger` is already `Arc`-backed and each worker writes a
+    /// distinct `output_dir`-relative path, so no lock is needed around
+    /// `reconstruct_file_owned` itself. Results are slotted back into the
+    /// same order `decoders` was given in, so the caller's `FinalReport`
+    /// doesn't depend on which worker happened to finish first.
+    fn reconstruct_files_parallel(
+        &self,
+        decoders: Vec<(String, FileDecoder)>,
+        callback: &EventCallback,
+    ) -> Vec<(String, Result<ReconstructedFile>)> {
+        let total = decoders.len();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        let num_workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(total);
+
+        let queue: ArrayQueue<(usize, String, FileDecoder)> = ArrayQueue::new(total);
+        for (idx, (file_key, file_decoder)) in decoders.into_iter().enumerate() {
+            let _ = queue.push((idx, file_key, file_decoder));
+        }
+
+        let results: Mutex<Vec<Option<(String, Result<ReconstructedFile>)>>> =
+            Mutex::new((0..total).map(|_| None).collect());
+
+        std::thread::scope(|scope| {
+            for _ in 0..num_workers {
+                scope.spawn(|| {
+                    while let Some((idx, file_key, file_decoder)) = queue.pop() {
+                        let outcome = self.reconstruct_file_owned(file_decoder, &file_key, callback);
+
+                        if let Ok(reconstructed) = &outcome {
+                            callback(ProcessingEvent::FileReconstructed {
+                                file_name: file_key.clone(),
+                                file_size: reconstructed.size,
+                                checksum_valid: reconstructed.checksum_valid,
+                                output_path: reconstructed.file_path.clone(),
+                            });
+                        }
+
+                        results.lock().unwrap()[idx] = Some((file_key, outcome));
+                    }
+                });
+            }
+        });
+
+        results.into_inner().unwrap().into_iter().flatten().collect()
+    }
+
     fn reconstruct_file_owned(
-        &mut self,
-        file_decoder: FileDecoder,
+        &self,
+        mut file_decoder: FileDecoder,
         file_name: &str,
+        callback: &EventCallback,
     ) -> Result<ReconstructedFile> {
         if !file_decoder.is_complete {
-            self.attempt_fountain_recovery(&file_decoder)?;
+            self.attempt_fountain_recovery(&mut file_decoder)?;
         }
 
         let mut file_data = Vec::new();
@@ -474,11 +905,51 @@ impl FileReconstructor {
 
         file_data.truncate(file_decoder.metadata.file_size);
 
-        let output_path = self.output_dir.join(&file_decoder.metadata.file_name);
-        fs::write(&output_path, &file_data)?;
-
         let checksums = self.calculate_checksums(&file_data, &file_decoder.metadata.file_checksum);
 
+        let checksum_valid = match &file_decoder.metadata.file_checksum {
+            Some(expected) => {
+                let (algorithm, expected_value) = self.detect_checksum_algorithm(expected);
+                // `calculate_checksums` formats crc32 without leading zeros.
+                let padded_crc32 = format!("{:0>8}", checksums.crc32);
+                let actual_value = match algorithm {
+                    "md5" => &checksums.md5,
+                    "sha1" => &checksums.sha1,
+                    "crc32" => &padded_crc32,
+                    _ => &checksums.sha256,
+                };
+                let matches = actual_value.eq_ignore_ascii_case(&expected_value);
+
+                if !matches {
+                    self.error_logger.log_error("CHECKSUM_MISMATCH", &format!(
+                        "{} ({}): expected {}, got {}", file_name, algorithm, expected_value, actual_value));
+
+                    callback(ProcessingEvent::FileReconstructed {
+                        file_name: file_name.to_string(),
+                        file_size: file_data.len() as u64,
+                        checksum_valid: false,
+                        output_path: String::new(),
+                    });
+
+                    if self.strict_checksum_mode {
+                        return Err(anyhow!(
+                            "Checksum mismatch for {}: expected {} ({}), got {} - refusing to write in strict mode",
+                            file_name, expected_value, algorithm, actual_value
+                        ));
+                    }
+                }
+
+                matches
+            }
+            None => true,
+        };
+
+        let output_path = self.resolve_output_path(&file_decoder.metadata.file_name);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.write_with_progress(&output_path, &file_data, file_name, callback)?;
+
         Ok(ReconstructedFile {
             qr_checksum: checksums.qr_checksum,
             md5: checksums.md5,
@@ -487,10 +958,93 @@ impl FileReconstructor {
             crc32: checksums.crc32,
             size: file_data.len() as u64,
             file_path: output_path.to_string_lossy().to_string(),
+            checksum_valid,
+            alias_of: None,
         })
     }
 
-    fn attempt_fountain_recovery(&self, _file_decoder: &FileDecoder) -> Result<()> {
+    /// Write `data` to `output_path` in fixed-size chunks, firing
+    /// `FileWriteProgress` after each one so a large file's write shows up
+    /// as incremental progress instead of a single opaque `fs::write` call.
+    fn write_with_progress(&self, output_path: &PathBuf, data: &[u8], file_name: &str, callback: &EventCallback) -> Result<()> {
+        const WRITE_CHUNK_SIZE: usize = 1 << 20; // 1 MiB
+
+        let file = File::create(output_path)?;
+        let mut writer = BufWriter::new(file);
+        let bytes_total = data.len() as u64;
+        let mut bytes_written = 0u64;
+
+        for chunk in data.chunks(WRITE_CHUNK_SIZE) {
+            writer.write_all(chunk)?;
+            bytes_written += chunk.len() as u64;
+
+            callback(ProcessingEvent::FileWriteProgress {
+                file_name: file_name.to_string(),
+                bytes_written,
+                bytes_total,
+            });
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Sniff the checksum algorithm from an `algo:hex` prefix if present,
+    /// otherwise from the hex string's length (crc32=8, md5=32, sha1=40,
+    /// sha256=64), defaulting to sha256 for anything unrecognized.
+    fn detect_checksum_algorithm(&self, checksum: &str) -> (&'static str, String) {
+        if let Some((prefix, value)) = checksum.split_once(':') {
+            let algorithm = match prefix.to_ascii_lowercase().as_str() {
+                "md5" => "md5",
+                "sha1" => "sha1",
+                "crc32" => "crc32",
+                _ => "sha256",
+            };
+            return (algorithm, value.to_string());
+        }
+
+        let algorithm = match checksum.len() {
+            8 => "crc32",
+            32 => "md5",
+            40 => "sha1",
+            _ => "sha256",
+        };
+        (algorithm, checksum.to_string())
+    }
+
+    /// Verify a decoded chunk's bytes against its per-chunk integrity label
+    /// (same `algo:hex`/bare-hex sniffing as `detect_checksum_algorithm`,
+    /// restricted to the cheap crc32/sha256 kinds chunk labels use).
+    fn verify_chunk_checksum(&self, chunk_data: &[u8], label: &str) -> bool {
+        let (algorithm, expected) = self.detect_checksum_algorithm(label);
+        let actual = match algorithm {
+            "crc32" => format!("{:08x}", crc32fast::hash(chunk_data)),
+            _ => {
+                use sha2::{Sha256, Digest};
+                let mut hasher = Sha256::new();
+                hasher.update(chunk_data);
+                format!("{:x}", hasher.finalize())
+            }
+        };
+        actual.eq_ignore_ascii_case(&expected)
+    }
+
+    fn attempt_fountain_recovery(&self, file_decoder: &mut FileDecoder) -> Result<()> {
+        if !file_decoder.coded_packets.is_empty() {
+            self.error_logger.log_info(&format!(
+                "Attempting fountain recovery for {}: {}/{} chunks, {} coded packets",
+                file_decoder.metadata.file_name,
+                file_decoder.received_chunks.len(),
+                file_decoder.metadata.chunks_count,
+                file_decoder.coded_packets.len()
+            ));
+            self.peel_coded_packets(file_decoder);
+        }
+
+        if file_decoder.received_chunks.len() >= file_decoder.metadata.chunks_count {
+            file_decoder.is_complete = true;
+        }
+
         Ok(())
     }
 
@@ -533,6 +1087,62 @@ impl FileReconstructor {
         }
     }
 
+    /// Re-read each reconstructed file back from disk and recompute its
+    /// SHA-256, catching corruption introduced between the in-memory
+    /// checksum check in `reconstruct_file_owned` and the bytes actually
+    /// landing on disk (partial writes, filesystem issues). Emits a
+    /// `ChecksumValidation` per file read back, plus a phase-3 `Error` for anything
+    /// mismatched or missing, instead of leaving corrupt output to be
+    /// discovered later. Returns the number of files that failed.
+    pub fn verify_reconstructed_files(&self, final_report: &FinalReport, callback: &EventCallback) -> Result<usize> {
+        let mut failures = 0usize;
+
+        for (file_key, reconstructed) in &final_report.files {
+            let path = PathBuf::from(&reconstructed.file_path);
+
+            let actual_data = match fs::read(&path) {
+                Ok(data) => data,
+                Err(e) => {
+                    failures += 1;
+                    let msg = format!("{}: missing on disk at {} ({})", file_key, reconstructed.file_path, e);
+                    self.error_logger.log_error("VERIFY_INCOMPLETE", &msg);
+                    callback(ProcessingEvent::Error { phase: 3, error: msg });
+                    continue;
+                }
+            };
+
+            let actual_sha256 = {
+                use sha2::{Sha256, Digest};
+                let mut hasher = Sha256::new();
+                hasher.update(&actual_data);
+                format!("{:x}", hasher.finalize())
+            };
+
+            let valid = actual_sha256.eq_ignore_ascii_case(&reconstructed.sha256)
+                && actual_data.len() as u64 == reconstructed.size;
+
+            callback(ProcessingEvent::ChecksumValidation {
+                file_name: file_key.clone(),
+                checksum_type: "sha256".to_string(),
+                expected: reconstructed.sha256.clone(),
+                actual: actual_sha256.clone(),
+                valid,
+            });
+
+            if !valid {
+                failures += 1;
+                let msg = format!(
+                    "{}: on-disk SHA-256 {} ({} bytes) does not match reconstructed {} ({} bytes)",
+                    file_key, actual_sha256, actual_data.len(), reconstructed.sha256, reconstructed.size
+                );
+                self.error_logger.log_error("VERIFY_MISMATCH", &msg);
+                callback(ProcessingEvent::Error { phase: 3, error: msg });
+            }
+        }
+
+        Ok(failures)
+    }
+
     fn save_final_report(&self, report: &FinalReport) -> Result<()> {
         let report_path = self.output_dir.join("integrity_report.json");
         let file = File::create(&report_path)?;
@@ -543,6 +1153,142 @@ impl FileReconstructor {
 
         Ok(())
     }
+
+    /// Collapse byte-identical reconstructed files down to one copy.
+    ///
+    /// Groups files by a cheap partial hash (crc32 of their first 64KiB),
+    /// then confirms true duplicates within a group via the full `sha256`
+    /// `calculate_checksums` already computed for each file - no second
+    /// full-file read. Within a confirmed group the lexicographically
+    /// first key is kept as canonical; every other key has its loose copy
+    /// deleted and its `FinalReport` entry rewritten to alias the
+    /// canonical one via `alias_of`.
+    fn dedup_reconstructed_files(&self, final_report: &mut FinalReport) {
+        let mut keys: Vec<String> = final_report.files.keys().cloned().collect();
+        keys.sort();
+
+        let mut by_partial_hash: HashMap<u32, Vec<String>> = HashMap::new();
+        for key in &keys {
+            let path = PathBuf::from(&final_report.files[key].file_path);
+            let partial_hash = File::open(&path).ok().map(|mut f| {
+                let mut buf = vec![0u8; 64 * 1024];
+                let n = f.read(&mut buf).unwrap_or(0);
+                crc32fast::hash(&buf[..n])
+            });
+
+            if let Some(hash) = partial_hash {
+                by_partial_hash.entry(hash).or_default().push(key.clone());
+            }
+        }
+
+        for group in by_partial_hash.values() {
+            if group.len() < 2 {
+                continue;
+            }
+
+            let mut by_sha256: HashMap<String, Vec<String>> = HashMap::new();
+            for key in group {
+                let sha256 = final_report.files[key].sha256.clone();
+                by_sha256.entry(sha256).or_default().push(key.clone());
+            }
+
+            for mut dup_keys in by_sha256.into_values() {
+                if dup_keys.len() < 2 {
+                    continue;
+                }
+                dup_keys.sort();
+
+                let canonical_key = dup_keys[0].clone();
+                let canonical_path = final_report.files[&canonical_key].file_path.clone();
+
+                for dup_key in dup_keys.iter().skip(1) {
+                    let dup_path = final_report.files[dup_key].file_path.clone();
+                    if dup_path != canonical_path {
+                        let _ = fs::remove_file(&dup_path);
+                    }
+                    self.error_logger.log_info(&format!(
+                        "Deduped {} as identical to {} (sha256 match)", dup_key, canonical_key
+                    ));
+
+                    if let Some(reconstructed) = final_report.files.get_mut(dup_key) {
+                        reconstructed.file_path = canonical_path.clone();
+                        reconstructed.alias_of = Some(canonical_key.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// When `output_mode` calls for an archive, pack every canonical
+    /// reconstructed file (already written loose by `reconstruct_file_owned`,
+    /// and deduped by `dedup_reconstructed_files`) plus `integrity_report.json`
+    /// into one `reconstructed.zip`, re-pointing each `FinalReport.files`
+    /// entry at its archive-internal path. Entries aliased by dedup are not
+    /// re-read or re-written; they're simply pointed at their canonical
+    /// entry's archive name. In `OutputMode::Archive` the loose copies are
+    /// then removed so the zip is the only artifact; `OutputMode::Both`
+    /// leaves them in place.
+    fn build_archive(&self, final_report: &mut FinalReport) -> Result<()> {
+        if self.output_mode == OutputMode::Loose {
+            return Ok(());
+        }
+
+        let archive_path = self.output_dir.join("reconstructed.zip");
+        let file = File::create(&archive_path)?;
+        let mut zip = ZipWriter::new(BufWriter::new(file));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mut loose_paths = Vec::new();
+        let mut archive_names: HashMap<String, String> = HashMap::new();
+
+        let keys: Vec<String> = final_report.files.keys().cloned().collect();
+
+        for key in &keys {
+            if final_report.files[key].alias_of.is_some() {
+                continue;
+            }
+
+            let reconstructed = final_report.files.get_mut(key).unwrap();
+            let loose_path = PathBuf::from(&reconstructed.file_path);
+            let archive_name = loose_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| reconstructed.file_path.clone());
+
+            zip.start_file(&archive_name, options)?;
+            let mut source = File::open(&loose_path)?;
+            std::io::copy(&mut source, &mut zip)?;
+
+            loose_paths.push(loose_path);
+            reconstructed.file_path = archive_name.clone();
+            archive_names.insert(key.clone(), archive_name);
+        }
+
+        for key in &keys {
+            let canonical_key = match &final_report.files[key].alias_of {
+                Some(canonical_key) => canonical_key.clone(),
+                None => continue,
+            };
+
+            if let Some(archive_name) = archive_names.get(&canonical_key).cloned() {
+                final_report.files.get_mut(key).unwrap().file_path = archive_name;
+            }
+        }
+
+        let report_json = serde_json::to_string_pretty(&*final_report)?;
+        zip.start_file("integrity_report.json", options)?;
+        zip.write_all(report_json.as_bytes())?;
+
+        zip.finish()?;
+        self.error_logger.log_info(&format!("Wrote archive {} with {} files", archive_path.display(), final_report.files.len()));
+
+        if self.output_mode == OutputMode::Archive {
+            for loose_path in loose_paths {
+                let _ = fs::remove_file(loose_path);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 struct FileChecksums {
@@ -551,4 +1297,33 @@ struct FileChecksums {
     sha1: String,
     sha256: String,
     crc32: String,
+}
+
+/// Minimal shell-style glob match supporting `*` (any run of characters)
+/// and `?` (exactly one character) - enough for `--restore-files` patterns
+/// like `*.mp4` or `logs/*` without pulling in a dedicated glob crate.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // DP table: `matches[i][j]` = does `pattern[..i]` match `text[..j]`.
+    let mut matches = vec![vec![false; text.len() + 1]; pattern.len() + 1];
+    matches[0][0] = true;
+    for i in 1..=pattern.len() {
+        if pattern[i - 1] == '*' {
+            matches[i][0] = matches[i - 1][0];
+        }
+    }
+
+    for i in 1..=pattern.len() {
+        for j in 1..=text.len() {
+            matches[i][j] = match pattern[i - 1] {
+                '*' => matches[i - 1][j] || matches[i][j - 1],
+                '?' => matches[i - 1][j - 1],
+                c => c == text[j - 1] && matches[i - 1][j - 1],
+            };
+        }
+    }
+
+    matches[pattern.len()][text.len()]
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::qr_extraction::{QrCodeData, QrExtractionResults};
+
+/// Render a self-contained HTML audit report for `results`: one table per
+/// chunk, grouped and sorted by frame number, so a user can see at a glance
+/// what was captured (and, via the thumbnail, whether it was actually the
+/// right QR code) without scanning raw JSONL by hand.
+pub fn render(results: &QrExtractionResults) -> String {
+    let mut by_chunk: BTreeMap<usize, Vec<&QrCodeData>> = BTreeMap::new();
+    for qr_data in &results.qr_codes {
+        by_chunk.entry(qr_data.chunk_id).or_default().push(qr_data);
+    }
+    for codes in by_chunk.values_mut() {
+        codes.sort_by_key(|c| c.frame_number);
+    }
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>QR Extraction Report</title>\n<style>\n");
+    html.push_str(STYLE);
+    html.push_str("</style>\n</head>\n<body>\n");
+
+    let _ = write!(
+        html,
+        "<h1>QR Extraction Report</h1>\n<p>{} chunk(s) processed, {} frame(s), {} code(s) decoded.</p>\n",
+        results.chunks_processed,
+        results.total_frames_processed,
+        results.qr_codes.len(),
+    );
+
+    for (chunk_id, codes) in &by_chunk {
+        let _ = write!(html, "<h2>Chunk {}</h2>\n<table>\n", chunk_id);
+        html.push_str("<tr><th>Frame</th><th>Dwell</th><th>Thumbnail</th><th>Content</th><th>Version</th><th>ECC</th></tr>\n");
+
+        for code in codes {
+            html.push_str("<tr>");
+            let _ = write!(html, "<td>{}</td>", code.frame_number);
+            if code.last_frame_number > code.frame_number {
+                let _ = write!(html, "<td>{}-{}</td>", code.frame_number, code.last_frame_number);
+            } else {
+                html.push_str("<td>-</td>");
+            }
+
+            match &code.thumbnail_base64 {
+                Some(b64) => {
+                    let _ = write!(html, "<td><img src=\"data:image/png;base64,{}\" alt=\"QR thumbnail\"></td>", b64);
+                }
+                None => html.push_str("<td>-</td>"),
+            }
+
+            match code.raw_payload_base64 {
+                Some(_) => {
+                    let raw_len = code.raw_payload().map(|b| b.len()).unwrap_or(0);
+                    let _ = write!(html, "<td class=\"binary\">binary, {} bytes</td>", raw_len);
+                }
+                None => {
+                    let _ = write!(html, "<td>{}</td>", escape_html(&code.data));
+                }
+            }
+
+            let _ = write!(html, "<td>{}</td>", code.version.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()));
+            let _ = write!(html, "<td>{}</td>", code.ecc_level.as_deref().unwrap_or("-"));
+            html.push_str("</tr>\n");
+        }
+
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2rem; }
+table { border-collapse: collapse; margin-bottom: 2rem; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+td.binary { color: #666; font-style: italic; }
+img { max-width: 96px; max-height: 96px; }
+";
@@ -0,0 +1,462 @@
+//! Live QR scanning straight off a V4L2 video device (e.g. a USB capture
+//! card pointed at a phone screen), skipping the "screen-record to a file,
+//! then run Phase 2 over it" round trip entirely.
+//!
+//! There is no `v4l`/`nix` dependency here: the ioctl surface this needs
+//! (`QUERYCAP`, `S_FMT`, `REQBUFS`, `QUERYBUF`, `QBUF`/`DQBUF`, `STREAMON`/`STREAMOFF`)
+//! is a small, decades-stable part of the Linux kernel ABI, and `ioctl`/
+//! `mmap`/`munmap` are already linked in via libc through `std` - so the
+//! structs below are hand-written mirrors of `<linux/videodev2.h>` rather
+//! than pulling in a whole crate for eight ioctl numbers.
+
+use anyhow::{anyhow, Result};
+use ffmpeg_next as ffmpeg;
+use std::collections::BTreeSet;
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::os::unix::io::RawFd;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::events::{EventCallback, ProcessingEvent};
+use crate::qr_extraction::QrCodeData;
+use crate::qr_extraction::QrExtractor;
+
+extern "C" {
+    fn open(path: *const i8, flags: c_int, ...) -> RawFd;
+    fn close(fd: RawFd) -> c_int;
+    fn ioctl(fd: RawFd, request: u64, argp: *mut c_void) -> c_int;
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: RawFd, offset: i64) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    fn __errno_location() -> *mut c_int;
+}
+
+const O_RDWR: c_int = 0o2;
+const PROT_READ: c_int = 0x1;
+const PROT_WRITE: c_int = 0x2;
+const MAP_SHARED: c_int = 0x1;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+const V4L2_BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
+const V4L2_MEMORY_MMAP: u32 = 1;
+const V4L2_FIELD_NONE: u32 = 1;
+
+fn last_errno() -> c_int {
+    unsafe { *__errno_location() }
+}
+
+// Linux's `_IOC`/`_IOR`/`_IOW`/`_IOWR` encoding (x86/arm/aarch64 layout -
+// mips/parisc/powerpc use a different bit split, not a realistic target
+// here): direction in the top 2 bits, `sizeof(T)` in the next 14, the
+// ioctl "type" magic in the next 8, and the command number in the low 8.
+const fn ioc(dir: u64, ty: u8, nr: u8, size: usize) -> u64 {
+    (dir << 30) | ((ty as u64) << 8) | (nr as u64) | ((size as u64) << 16)
+}
+fn ior<T>(ty: u8, nr: u8) -> u64 {
+    ioc(2, ty, nr, std::mem::size_of::<T>())
+}
+fn iow<T>(ty: u8, nr: u8) -> u64 {
+    ioc(1, ty, nr, std::mem::size_of::<T>())
+}
+fn iowr<T>(ty: u8, nr: u8) -> u64 {
+    ioc(3, ty, nr, std::mem::size_of::<T>())
+}
+
+const fn fourcc(a: u8, b: u8, c: u8, d: u8) -> u32 {
+    (a as u32) | ((b as u32) << 8) | ((c as u32) << 16) | ((d as u32) << 24)
+}
+
+fn fourcc_to_string(code: u32) -> String {
+    let bytes = code.to_le_bytes();
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct V4l2Capability {
+    driver: [u8; 16],
+    card: [u8; 32],
+    bus_info: [u8; 32],
+    version: u32,
+    capabilities: u32,
+    device_caps: u32,
+    reserved: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct V4l2PixFormat {
+    width: u32,
+    height: u32,
+    pixelformat: u32,
+    field: u32,
+    bytesperline: u32,
+    sizeimage: u32,
+    colorspace: u32,
+    priv_: u32,
+    flags: u32,
+    ycbcr_enc: u32,
+    quantization: u32,
+    xfer_func: u32,
+}
+
+/// `struct v4l2_format`'s `fmt` member is a union reserved as 200 bytes on
+/// the wire, 8-byte aligned on 64-bit targets because its `win` variant
+/// holds pointers; we only ever fill in the `pix` variant, so the rest is
+/// padding.
+#[repr(C, align(8))]
+struct V4l2FormatUnion {
+    pix: V4l2PixFormat,
+    _pad: [u8; 200 - std::mem::size_of::<V4l2PixFormat>()],
+}
+
+#[repr(C)]
+struct V4l2Format {
+    type_: u32,
+    fmt: V4l2FormatUnion,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct V4l2RequestBuffers {
+    count: u32,
+    type_: u32,
+    memory: u32,
+    reserved: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct V4l2Timecode {
+    type_: u32,
+    flags: u32,
+    frames: u8,
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    userbits: [u8; 4],
+}
+
+/// Mirrors `struct v4l2_buffer`; the `m` union only ever carries the mmap
+/// `offset` variant here (`V4L2_MEMORY_MMAP`), padded out to the 8 bytes a
+/// 64-bit `unsigned long userptr` would occupy in the other variant.
+#[repr(C)]
+#[derive(Default)]
+struct V4l2Buffer {
+    index: u32,
+    type_: u32,
+    bytesused: u32,
+    flags: u32,
+    field: u32,
+    timestamp_sec: i64,
+    timestamp_usec: i64,
+    timecode: V4l2Timecode,
+    sequence: u32,
+    memory: u32,
+    m_offset: u32,
+    _m_pad: u32,
+    length: u32,
+    reserved2: u32,
+    request_fd: i32,
+}
+
+/// A single mmap'd capture buffer handed to/from the kernel via
+/// `VIDIOC_QBUF`/`VIDIOC_DQBUF`.
+struct MappedBuffer {
+    ptr: *mut c_void,
+    length: usize,
+}
+
+impl Drop for MappedBuffer {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() && self.ptr != MAP_FAILED {
+            unsafe {
+                munmap(self.ptr, self.length);
+            }
+        }
+    }
+}
+
+/// How to open and configure the capture device before streaming starts.
+pub struct LiveCaptureConfig {
+    pub device_path: String,
+    pub width: u32,
+    pub height: u32,
+    /// Requested pixel format, e.g. `*b"YUYV"`. The driver is free to
+    /// substitute a different one it actually supports; we surface that as
+    /// an error rather than silently decoding the wrong layout.
+    pub fourcc: [u8; 4],
+    pub buffer_count: u32,
+    pub timeout: Duration,
+}
+
+impl LiveCaptureConfig {
+    pub fn new(device_path: impl Into<String>) -> Self {
+        Self {
+            device_path: device_path.into(),
+            width: 1280,
+            height: 720,
+            fourcc: *b"YUYV",
+            buffer_count: 4,
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+struct V4l2Device {
+    fd: RawFd,
+    buffers: Vec<MappedBuffer>,
+}
+
+impl V4l2Device {
+    fn open(config: &LiveCaptureConfig) -> Result<(Self, V4l2PixFormat)> {
+        let path = CString::new(config.device_path.as_str())
+            .map_err(|e| anyhow!("Invalid device path: {}", e))?;
+        let fd = unsafe { open(path.as_ptr(), O_RDWR) };
+        if fd < 0 {
+            return Err(anyhow!(
+                "Failed to open {}: errno {}",
+                config.device_path,
+                last_errno()
+            ));
+        }
+        let mut device = V4l2Device { fd, buffers: Vec::new() };
+
+        let mut cap = V4l2Capability::default();
+        if unsafe { ioctl(fd, ior::<V4l2Capability>(b'V', 0), &mut cap as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_QUERYCAP failed on {}: errno {}", config.device_path, last_errno()));
+        }
+
+        let requested_fourcc = fourcc(config.fourcc[0], config.fourcc[1], config.fourcc[2], config.fourcc[3]);
+        let mut fmt = V4l2Format {
+            type_: V4L2_BUF_TYPE_VIDEO_CAPTURE,
+            fmt: V4l2FormatUnion {
+                pix: V4l2PixFormat {
+                    width: config.width,
+                    height: config.height,
+                    pixelformat: requested_fourcc,
+                    field: V4L2_FIELD_NONE,
+                    ..Default::default()
+                },
+                _pad: [0u8; 200 - std::mem::size_of::<V4l2PixFormat>()],
+            },
+        };
+        if unsafe { ioctl(fd, iowr::<V4l2Format>(b'V', 5), &mut fmt as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_S_FMT failed on {}: errno {}", config.device_path, last_errno()));
+        }
+
+        let negotiated = fmt.fmt.pix;
+        if negotiated.pixelformat != requested_fourcc {
+            return Err(anyhow!(
+                "camera gave format {}, expected {}",
+                fourcc_to_string(negotiated.pixelformat),
+                fourcc_to_string(requested_fourcc),
+            ));
+        }
+
+        let mut reqbufs = V4l2RequestBuffers {
+            count: config.buffer_count,
+            type_: V4L2_BUF_TYPE_VIDEO_CAPTURE,
+            memory: V4L2_MEMORY_MMAP,
+            ..Default::default()
+        };
+        if unsafe { ioctl(fd, iowr::<V4l2RequestBuffers>(b'V', 8), &mut reqbufs as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_REQBUFS failed on {}: errno {}", config.device_path, last_errno()));
+        }
+
+        for index in 0..reqbufs.count {
+            let mut buf = V4l2Buffer {
+                index,
+                type_: V4L2_BUF_TYPE_VIDEO_CAPTURE,
+                memory: V4L2_MEMORY_MMAP,
+                ..Default::default()
+            };
+            if unsafe { ioctl(fd, iowr::<V4l2Buffer>(b'V', 9), &mut buf as *mut _ as *mut c_void) } < 0 {
+                return Err(anyhow!("VIDIOC_QUERYBUF failed on buffer {}: errno {}", index, last_errno()));
+            }
+
+            let ptr = unsafe {
+                mmap(
+                    std::ptr::null_mut(),
+                    buf.length as usize,
+                    PROT_READ | PROT_WRITE,
+                    MAP_SHARED,
+                    fd,
+                    buf.m_offset as i64,
+                )
+            };
+            if ptr == MAP_FAILED {
+                return Err(anyhow!("mmap failed for buffer {}: errno {}", index, last_errno()));
+            }
+            device.buffers.push(MappedBuffer { ptr, length: buf.length as usize });
+
+            if unsafe { ioctl(fd, iowr::<V4l2Buffer>(b'V', 15), &mut buf as *mut _ as *mut c_void) } < 0 {
+                return Err(anyhow!("VIDIOC_QBUF failed queuing buffer {}: errno {}", index, last_errno()));
+            }
+        }
+
+        Ok((device, negotiated))
+    }
+
+    fn stream_on(&self) -> Result<()> {
+        let mut buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE as c_int;
+        if unsafe { ioctl(self.fd, iow::<c_int>(b'V', 18), &mut buf_type as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_STREAMON failed: errno {}", last_errno()));
+        }
+        Ok(())
+    }
+
+    fn stream_off(&self) -> Result<()> {
+        let mut buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE as c_int;
+        if unsafe { ioctl(self.fd, iow::<c_int>(b'V', 19), &mut buf_type as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_STREAMOFF failed: errno {}", last_errno()));
+        }
+        Ok(())
+    }
+
+    /// Dequeue one filled buffer, hand its bytes to `handler`, then
+    /// re-queue it so the kernel can reuse it for the next frame.
+    fn capture_one_frame(&self, handler: impl FnOnce(&[u8])) -> Result<()> {
+        let mut buf = V4l2Buffer {
+            type_: V4L2_BUF_TYPE_VIDEO_CAPTURE,
+            memory: V4L2_MEMORY_MMAP,
+            ..Default::default()
+        };
+        if unsafe { ioctl(self.fd, iowr::<V4l2Buffer>(b'V', 17), &mut buf as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_DQBUF failed: errno {}", last_errno()));
+        }
+
+        let mapped = self.buffers.get(buf.index as usize)
+            .ok_or_else(|| anyhow!("Kernel returned out-of-range buffer index {}", buf.index))?;
+        let slice = unsafe { std::slice::from_raw_parts(mapped.ptr as *const u8, buf.bytesused as usize) };
+        handler(slice);
+
+        if unsafe { ioctl(self.fd, iowr::<V4l2Buffer>(b'V', 15), &mut buf as *mut _ as *mut c_void) } < 0 {
+            return Err(anyhow!("VIDIOC_QBUF failed re-queuing buffer {}: errno {}", buf.index, last_errno()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for V4l2Device {
+    fn drop(&mut self) {
+        self.stream_off().ok();
+        self.buffers.clear();
+        unsafe {
+            close(self.fd);
+        }
+    }
+}
+
+/// Map a V4L2 FourCC onto the `ffmpeg` pixel format it's laid out as, so a
+/// captured buffer can be wrapped in an `ffmpeg::frame::Video` and pushed
+/// through the existing scaler/detection pipeline unchanged.
+fn ffmpeg_pixel_format(v4l2_fourcc: u32) -> Result<ffmpeg::format::Pixel> {
+    match v4l2_fourcc {
+        f if f == fourcc(b'Y', b'U', b'Y', b'V') => Ok(ffmpeg::format::Pixel::YUYV422),
+        f if f == fourcc(b'R', b'G', b'B', b'3') => Ok(ffmpeg::format::Pixel::RGB24),
+        other => Err(anyhow!(
+            "Unsupported camera pixel format {} - only YUYV and RGB3 are wired up",
+            fourcc_to_string(other)
+        )),
+    }
+}
+
+/// Open `config.device_path`, stream frames off it, run each one through
+/// `extractor`'s shared `extract_qr_from_frame` detection pipeline, and
+/// stop once every id in `target_chunk_ids` has shown up in a decoded
+/// `QrCodeData.chunk_id`, or `config.timeout` elapses - whichever is first.
+pub fn scan_live_camera(
+    extractor: &QrExtractor,
+    config: &LiveCaptureConfig,
+    target_chunk_ids: &BTreeSet<usize>,
+    callback: &EventCallback,
+) -> Result<Vec<QrCodeData>> {
+    if !Path::new(&config.device_path).exists() {
+        return Err(anyhow!("Camera device {} does not exist", config.device_path));
+    }
+
+    let (device, pix_format) = V4l2Device::open(config)?;
+    let pixel_format = ffmpeg_pixel_format(pix_format.pixelformat)?;
+    device.stream_on()?;
+
+    callback(ProcessingEvent::ChunkStarted {
+        chunk_id: 0,
+        chunk_name: config.device_path.clone(),
+        worker_id: 0,
+    });
+
+    let mut scaler = ffmpeg::software::scaling::Context::get(
+        pixel_format,
+        pix_format.width,
+        pix_format.height,
+        ffmpeg::format::Pixel::RGB24,
+        pix_format.width,
+        pix_format.height,
+        ffmpeg::software::scaling::Flags::BILINEAR,
+    )
+    .map_err(|e| anyhow!("Failed to create scaler: {}", e))?;
+
+    let mut results: Vec<QrCodeData> = Vec::new();
+    let mut seen_chunk_ids: BTreeSet<usize> = BTreeSet::new();
+    let mut frame_number = 0u64;
+    let start = Instant::now();
+    // No fixed frame count for a live, unbounded capture, so `eta_secs`
+    // always reports `None` here - only `fps` is meaningful.
+    let mut rate = crate::progress_estimator::RateEstimator::new();
+
+    while start.elapsed() < config.timeout {
+        if target_chunk_ids.is_subset(&seen_chunk_ids) && !target_chunk_ids.is_empty() {
+            break;
+        }
+
+        let mut frame = ffmpeg::frame::Video::new(pixel_format, pix_format.width, pix_format.height);
+        let bytesperline = pix_format.bytesperline as usize;
+
+        device.capture_one_frame(|raw| {
+            let dst_stride = frame.stride(0);
+            let dst = frame.data_mut(0);
+            let row_bytes = dst_stride.min(bytesperline).min(raw.len());
+            for row in 0..pix_format.height as usize {
+                let src_start = row * bytesperline;
+                let dst_start = row * dst_stride;
+                if src_start + row_bytes <= raw.len() && dst_start + row_bytes <= dst.len() {
+                    dst[dst_start..dst_start + row_bytes].copy_from_slice(&raw[src_start..src_start + row_bytes]);
+                }
+            }
+        })?;
+
+        let chunk_id = target_chunk_ids.iter().next().copied().unwrap_or(0);
+        if let Ok(qr_data) = extractor.extract_qr_from_frame(&mut scaler, &frame, frame_number, chunk_id) {
+            if !qr_data.is_empty() {
+                for qr in &qr_data {
+                    seen_chunk_ids.insert(qr.chunk_id);
+                }
+                rate.record(frame_number);
+                callback(ProcessingEvent::ChunkProgress {
+                    chunk_id,
+                    frames_processed: frame_number as usize,
+                    qr_codes_found: results.len() + qr_data.len(),
+                    status: format!("capturing {}", config.device_path),
+                    fps: rate.fps(),
+                    eta_secs: None,
+                });
+                results.extend(qr_data);
+            }
+        }
+
+        frame_number += 1;
+    }
+
+    device.stream_off().ok();
+
+    callback(ProcessingEvent::ChunkCompleted {
+        chunk_id: 0,
+        qr_codes_found: results.len(),
+        jsonl_file: String::new(),
+        duration_ms: start.elapsed().as_millis() as u64,
+        worker_id: 0,
+    });
+
+    Ok(results)
+}
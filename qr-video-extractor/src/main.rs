@@ -1,10 +1,12 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 mod tui;
 mod video;
 mod qr_extraction;
+mod html_report;
 mod file_reconstruction;
 mod events;
 mod error_logger;
@@ -12,6 +14,17 @@ mod resume_state;
 mod resume_controller;
 mod error_handler;
 mod completion_detector;
+mod chunk_queue;
+mod pipeline_control;
+mod chunk_broker;
+mod session_report;
+mod web_event_sink;
+mod progress_estimator;
+mod memory_supervisor;
+#[cfg(feature = "mp4-native")]
+mod mp4_demux;
+#[cfg(target_os = "linux")]
+mod live_capture;
 
 use tui::TuiManager;
 use video::VideoProcessor;
@@ -20,7 +33,19 @@ use file_reconstruction::FileReconstructor;
 use events::{EventCallback, ProcessingEvent, ConsoleOutputHandler, OutputHandler};
 use resume_controller::{ResumeController, ResumePoint};
 use error_handler::ErrorHandler;
-use completion_detector::CompletionDetector;
+use completion_detector::{CompletionDetector, ProgressMonitor};
+use chunk_queue::{ChunkQueue, DoneManifest};
+use pipeline_control::PipelineControl;
+
+/// How `create_video_chunks` picks chunk boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SplitMode {
+    /// Cut at `i * duration / chunk_count`, regardless of keyframes.
+    Uniform,
+    /// Probe keyframe timestamps and snap each boundary to the nearest one
+    /// at or before it.
+    Keyframe,
+}
 
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
@@ -40,10 +65,27 @@ struct Args {
     #[arg(short, long)]
     duration_per_chunk: Option<f64>,
 
+    /// How chunk boundaries are chosen: `uniform` cuts strictly at
+    /// `i * duration / chunk_count`; `keyframe` probes keyframe timestamps
+    /// first and snaps each boundary onto the nearest one at or before it,
+    /// so a `-c copy` split never cuts mid-GOP and loses QR codes at a seam.
+    #[arg(long, value_enum, default_value = "uniform")]
+    split_mode: SplitMode,
+
     /// Skip frames (process every Nth frame) - 0 for maximum quality
     #[arg(short, long, default_value_t = 0)]
     skip: usize,
 
+    /// Adaptive frame sampling: skip QR decoding on frames whose thumbnail
+    /// hash is within `adaptive_threshold` of the previous decoded frame
+    #[arg(long)]
+    adaptive: bool,
+
+    /// Hamming-distance threshold (out of 64 bits) below which a frame is
+    /// treated as a duplicate of the previous one in `--adaptive` mode
+    #[arg(long, default_value_t = 4)]
+    adaptive_threshold: u32,
+
     /// Maximum number of threads to use
     #[arg(short, long)]
     threads: Option<usize>,
@@ -79,6 +121,74 @@ struct Args {
     /// Run only Phase 3 (file reconstruction) using existing JSONL files
     #[arg(long)]
     phase3_only: bool,
+
+    /// Streaming mode: read from stdin (or a path given as `-`) and process
+    /// fixed-duration segments via ffmpeg's own segment muxer as they
+    /// complete, instead of requiring the whole input up front
+    #[arg(long)]
+    stream: bool,
+
+    /// Watch an in-progress or completed run and alert on stalled chunks
+    /// instead of processing video directly
+    #[arg(long)]
+    monitor: bool,
+
+    /// How often `--monitor` re-checks chunk progress, in seconds
+    #[arg(long, default_value_t = 10)]
+    polling_interval: u64,
+
+    /// `--monitor` flags a chunk as stalled once it goes this many seconds
+    /// with no new frames while still incomplete
+    #[arg(long, default_value_t = 120)]
+    chunk_timeout: u64,
+
+    /// `--monitor` flags the whole job once it has run this many seconds
+    /// without every chunk reaching completion
+    #[arg(long, default_value_t = 3600)]
+    job_timeout: u64,
+
+    /// After `--phase3-only` reconstruction, re-read every output file from disk and
+    /// recompute its SHA-256 to catch write corruption, reporting any
+    /// mismatched or incomplete files instead of leaving them undetected
+    #[arg(long)]
+    verify: bool,
+
+    /// Restrict `--phase3-only` reconstruction to files whose embedded name matches
+    /// this `*`/`?` glob (repeatable; default is to restore everything)
+    #[arg(long = "restore-files")]
+    restore_files: Vec<String>,
+
+    /// Redirect files whose embedded name starts with `<src-prefix>` to
+    /// `<dst-dir>` instead of `--output` in `--phase3-only` runs (repeatable,
+    /// format `src=dst`)
+    #[arg(long)]
+    remap: Vec<String>,
+
+    /// Attempts allowed per chunk (Phase 1 splitting and Phase 2 QR
+    /// extraction) before the chunk broker gives up on it and moves on
+    #[arg(long, default_value_t = 3)]
+    max_tries: u32,
+
+    /// Scan a live V4L2 camera device (e.g. `/dev/video0`) instead of
+    /// processing a video file - Linux only
+    #[arg(long)]
+    live_camera: Option<String>,
+
+    /// Logical chunk ids the live scan is watching for; it stops as soon as
+    /// every one has been seen in a decoded QR code (repeatable)
+    #[arg(long = "live-target-chunk")]
+    live_target_chunks: Vec<usize>,
+
+    /// Give up on the live scan after this many seconds with the target
+    /// chunk ids still incomplete
+    #[arg(long, default_value_t = 60)]
+    live_timeout: u64,
+
+    /// Serve every ProcessingEvent over HTTP on this address (e.g.
+    /// `127.0.0.1:9090`), so a remote dashboard can follow a headless run
+    /// via `GET /events` (SSE) and `GET /status` (latest snapshot)
+    #[arg(long)]
+    web_events: Option<String>,
 }
 
 fn main() -> Result<()> {
@@ -105,10 +215,32 @@ fn main() -> Result<()> {
         return run_phase3_only(&args);
     }
 
-    // Validate input file is provided when not testing
-    let input_path = args.input.ok_or_else(|| {
-        anyhow::anyhow!("Input video file path is required. Use --help for usage information.")
-    })?;
+    // Watch an existing/in-progress run for stalled chunks if requested
+    if args.monitor {
+        return run_monitor_mode(&args);
+    }
+
+    // Scan a live camera device instead of a video file if requested
+    #[cfg(target_os = "linux")]
+    if let Some(device_path) = args.live_camera.clone() {
+        return run_live_camera_mode(&args, device_path);
+    }
+    #[cfg(not(target_os = "linux"))]
+    if args.live_camera.is_some() {
+        return Err(anyhow::anyhow!("--live-camera is only supported on Linux (V4L2)"));
+    }
+
+    // Validate input file is provided when not testing. In --stream mode,
+    // input is optional: omitting it (or passing `-`) means read stdin.
+    let input_path = match args.input.clone() {
+        Some(path) => path,
+        None if args.stream => PathBuf::from("-"),
+        None => {
+            return Err(anyhow::anyhow!(
+                "Input video file path is required. Use --help for usage information."
+            ))
+        }
+    };
 
     let chunk_count = args.chunks.unwrap_or_else(|| {
         std::cmp::max(num_cpus::get() / 2, 4)
@@ -122,7 +254,10 @@ fn main() -> Result<()> {
         output: args.output,
         chunks: args.chunks,
         duration_per_chunk: args.duration_per_chunk,
+        split_mode: args.split_mode,
         skip: args.skip,
+        adaptive: args.adaptive,
+        adaptive_threshold: args.adaptive_threshold,
         threads: args.threads,
         text_only: args.text_only,
         verbose: args.verbose,
@@ -132,6 +267,19 @@ fn main() -> Result<()> {
         resume: args.resume,
         check_status: args.check_status,
         phase3_only: args.phase3_only,
+        stream: args.stream,
+        monitor: args.monitor,
+        polling_interval: args.polling_interval,
+        chunk_timeout: args.chunk_timeout,
+        job_timeout: args.job_timeout,
+        verify: args.verify,
+        restore_files: args.restore_files.clone(),
+        remap: args.remap.clone(),
+        max_tries: args.max_tries,
+        web_events: args.web_events.clone(),
+        live_camera: args.live_camera.clone(),
+        live_target_chunks: args.live_target_chunks.clone(),
+        live_timeout: args.live_timeout,
     };
 
     if validated_args.text_only {
@@ -149,6 +297,7 @@ fn run_tui_mode(args: &Args, chunk_count: usize, thread_count: usize) -> Result<
         Ok(mut tui) => {
             let callback = tui.get_callback();
             let error_callback = tui.get_callback();
+            let control = tui.get_control();
 
             // Clone args for the background thread
             let args_clone = Args {
@@ -156,7 +305,10 @@ fn run_tui_mode(args: &Args, chunk_count: usize, thread_count: usize) -> Result<
                 output: args.output.clone(),
                 chunks: args.chunks,
                 duration_per_chunk: args.duration_per_chunk,
+                split_mode: args.split_mode,
                 skip: args.skip,
+                adaptive: args.adaptive,
+                adaptive_threshold: args.adaptive_threshold,
                 threads: args.threads,
                 text_only: args.text_only,
                 verbose: args.verbose,
@@ -166,11 +318,24 @@ fn run_tui_mode(args: &Args, chunk_count: usize, thread_count: usize) -> Result<
                 resume: args.resume,
                 check_status: args.check_status,
         phase3_only: args.phase3_only,
+        stream: args.stream,
+        monitor: args.monitor,
+        polling_interval: args.polling_interval,
+        chunk_timeout: args.chunk_timeout,
+        job_timeout: args.job_timeout,
+        verify: args.verify,
+        restore_files: args.restore_files.clone(),
+        remap: args.remap.clone(),
+        max_tries: args.max_tries,
+        web_events: args.web_events.clone(),
+        live_camera: args.live_camera.clone(),
+        live_target_chunks: args.live_target_chunks.clone(),
+        live_timeout: args.live_timeout,
             };
 
             // Start processing in a background thread
             std::thread::spawn(move || {
-                if let Err(e) = process_video_with_callback(&args_clone, chunk_count, thread_count, callback) {
+                if let Err(e) = process_video_with_callback(&args_clone, chunk_count, thread_count, callback, control) {
                     error_callback(ProcessingEvent::SystemError {
                         context: "Background processing".to_string(),
                         error: e.to_string(),
@@ -209,6 +374,7 @@ fn run_tui_mode_forced(args: &Args, chunk_count: usize, thread_count: usize) ->
         Ok(mut tui) => {
             let callback = tui.get_callback();
             let error_callback = tui.get_callback();
+            let control = tui.get_control();
 
             // Clone args for the background thread
             let args_clone = Args {
@@ -216,7 +382,10 @@ fn run_tui_mode_forced(args: &Args, chunk_count: usize, thread_count: usize) ->
                 output: args.output.clone(),
                 chunks: args.chunks,
                 duration_per_chunk: args.duration_per_chunk,
+                split_mode: args.split_mode,
                 skip: args.skip,
+                adaptive: args.adaptive,
+                adaptive_threshold: args.adaptive_threshold,
                 threads: args.threads,
                 text_only: args.text_only,
                 verbose: args.verbose,
@@ -226,11 +395,24 @@ fn run_tui_mode_forced(args: &Args, chunk_count: usize, thread_count: usize) ->
                 resume: args.resume,
                 check_status: args.check_status,
         phase3_only: args.phase3_only,
+        stream: args.stream,
+        monitor: args.monitor,
+        polling_interval: args.polling_interval,
+        chunk_timeout: args.chunk_timeout,
+        job_timeout: args.job_timeout,
+        verify: args.verify,
+        restore_files: args.restore_files.clone(),
+        remap: args.remap.clone(),
+        max_tries: args.max_tries,
+        web_events: args.web_events.clone(),
+        live_camera: args.live_camera.clone(),
+        live_target_chunks: args.live_target_chunks.clone(),
+        live_timeout: args.live_timeout,
             };
 
             // Start processing in a background thread
             std::thread::spawn(move || {
-                if let Err(e) = process_video_with_callback(&args_clone, chunk_count, thread_count, callback) {
+                if let Err(e) = process_video_with_callback(&args_clone, chunk_count, thread_count, callback, control) {
                     error_callback(ProcessingEvent::SystemError {
                         context: "Background processing".to_string(),
                         error: e.to_string(),
@@ -260,7 +442,9 @@ fn run_text_mode(args: &Args, chunk_count: usize, thread_count: usize) -> Result
         ConsoleOutputHandler.handle_event(&event);
     });
 
-    process_video_with_callback(&args, chunk_count, thread_count, callback)
+    // Text mode has no interactive keybindings to pause the pipeline, so
+    // this control handle simply stays unused (never toggled).
+    process_video_with_callback(&args, chunk_count, thread_count, callback, PipelineControl::new())
 }
 
 fn process_video_with_callback(
@@ -268,12 +452,50 @@ fn process_video_with_callback(
     chunk_count: usize,
     thread_count: usize,
     callback: EventCallback,
+    control: Arc<PipelineControl>,
 ) -> Result<()> {
+    // Mirror every event to a remote SSE dashboard in addition to whatever
+    // `callback` already does, so `--web-events` works the same whether the
+    // run is local TUI, `--text-only`, or `--stream`.
+    let callback: EventCallback = if let Some(addr) = args.web_events.clone() {
+        let web_sink = web_event_sink::WebEventSink::spawn(addr)?;
+        let web_callback = web_sink.callback();
+        Box::new(move |event: ProcessingEvent| {
+            callback(event.clone());
+            web_callback(event);
+        })
+    } else {
+        callback
+    };
+
+    if args.stream {
+        return process_video_streaming(args, thread_count, callback, control);
+    }
+
     // Initialize logging for the entire process
     let log_path = args.output.join("processing.log");
     let process_logger = crate::error_logger::ErrorLogger::new(&log_path.to_string_lossy())
         .unwrap_or_else(|_| crate::error_logger::ErrorLogger::new("/tmp/processing.log").unwrap());
 
+    // Every chunk retry/abandon from Phase 1/2's `ChunkBroker`s is also
+    // logged here, independent of whatever's listening to `callback`.
+    let error_handler = Arc::new(ErrorHandler::new(&args.output)?);
+
+    // A Ctrl-C no longer kills the process mid-write: it requests a
+    // graceful stop that in-flight chunk workers observe between chunks.
+    if let Err(e) = pipeline_control::install_ctrlc_handler(Arc::clone(&control)) {
+        process_logger.log_warning("SIGNAL_HANDLER", &format!("Could not install Ctrl-C handler: {}", e));
+    }
+
+    // Wrapping in `Arc` (rather than threading a borrow through every call
+    // site below) lets the memory supervisor hold its own handle to the
+    // same callback/control/error_handler for the life of this run; the
+    // `Fn` call sugar and deref coercion below still resolve through the
+    // `Arc` exactly like `ErrorHandler`'s own `Option<Arc<EventCallback>>`
+    // already does, so no other call site in this function needs to change.
+    let callback: Arc<EventCallback> = Arc::new(callback);
+    memory_supervisor::spawn(Arc::clone(&callback), Arc::clone(&control), Arc::clone(&error_handler));
+
     process_logger.log_info(&format!("=== PROCESSING STARTED === Version: 0.1.0"));
     process_logger.log_info(&format!("Input: {}", args.input.as_ref().unwrap().display()));
     process_logger.log_info(&format!("Output: {}", args.output.display()));
@@ -289,16 +511,9 @@ fn process_video_with_callback(
     // CRITICAL: Preserve files when resuming, ask confirmation when cleaning
     if !args.resume {
         // Count existing files before potential cleaning
-        let mut existing_jsonl_count = 0;
-        let mut existing_chunk_count = 0;
-        for i in 1..=50 {
-            if args.output.join(format!("chunk_{:03}.jsonl", i)).exists() {
-                existing_jsonl_count += 1;
-            }
-            if args.output.join(format!("chunk_{:03}.mp4", i)).exists() {
-                existing_chunk_count += 1;
-            }
-        }
+        let (existing_jsonl_files, existing_chunk_files) = chunk_queue::scan_existing_chunk_files(&args.output);
+        let existing_jsonl_count = existing_jsonl_files.len();
+        let existing_chunk_count = existing_chunk_files.len();
 
         if existing_jsonl_count > 0 || existing_chunk_count > 0 {
             // ASK FOR CONFIRMATION BEFORE CLEANING
@@ -330,37 +545,34 @@ fn process_video_with_callback(
 
         process_logger.log_info("FRESH START: Cleaning target folder for new processing");
 
-        // Remove existing files from previous runs
-        for i in 1..=50 { // Clean up to 50 possible chunks
-            let chunk_file = args.output.join(format!("chunk_{:03}.mp4", i));
-            if chunk_file.exists() {
-                std::fs::remove_file(&chunk_file).ok();
-            }
-            let jsonl_file = args.output.join(format!("chunk_{:03}.jsonl", i));
-            if jsonl_file.exists() {
-                std::fs::remove_file(&jsonl_file).ok();
-            }
+        // Remove existing files from previous runs, whatever chunk ids they cover
+        for chunk_file in &existing_chunk_files {
+            std::fs::remove_file(chunk_file).ok();
+        }
+        for jsonl_file in &existing_jsonl_files {
+            std::fs::remove_file(jsonl_file).ok();
         }
 
         let old_report = args.output.join("integrity_report.json");
         if old_report.exists() {
             std::fs::remove_file(&old_report).ok();
         }
+        let old_queue = args.output.join("chunk_queue.json");
+        if old_queue.exists() {
+            std::fs::remove_file(&old_queue).ok();
+        }
+        let old_done = args.output.join("done.json");
+        if old_done.exists() {
+            std::fs::remove_file(&old_done).ok();
+        }
     } else {
         // RESUME MODE - ABSOLUTELY NO CLEANING
         process_logger.log_info("🔄 RESUME MODE: Preserving ALL existing files for incremental processing");
 
         // Count preserved files
-        let mut preserved_jsonl = 0;
-        let mut preserved_chunks = 0;
-        for i in 1..=50 {
-            if args.output.join(format!("chunk_{:03}.jsonl", i)).exists() {
-                preserved_jsonl += 1;
-            }
-            if args.output.join(format!("chunk_{:03}.mp4", i)).exists() {
-                preserved_chunks += 1;
-            }
-        }
+        let (preserved_jsonl_files, preserved_chunk_files) = chunk_queue::scan_existing_chunk_files(&args.output);
+        let preserved_jsonl = preserved_jsonl_files.len();
+        let preserved_chunks = preserved_chunk_files.len();
 
         process_logger.log_info(&format!("PRESERVED: {} JSONL files, {} chunk files for resume processing", preserved_jsonl, preserved_chunks));
 
@@ -372,7 +584,9 @@ fn process_video_with_callback(
     let input_path = args.input.as_ref().ok_or_else(|| {
         anyhow::anyhow!("Input video file path is required")
     })?;
-    let mut video_processor = VideoProcessor::new(input_path)?;
+    let mut video_processor = VideoProcessor::new(input_path)?
+        .with_max_tries(args.max_tries)
+        .with_error_handler(Arc::clone(&error_handler));
     let video_info = video_processor.get_video_info(&callback)?;
 
     callback(ProcessingEvent::Progress {
@@ -384,6 +598,33 @@ fn process_video_with_callback(
                         video_info.fps, video_info.duration),
     });
 
+    // An explicit `--threads` is the user overriding our judgment, not a
+    // ceiling for us to second-guess - only auto-size (and clamp to
+    // available memory) when they left it unset.
+    let per_worker_estimate = resume_state::estimate_per_worker_bytes(video_info.width, video_info.height, 4);
+    let available_memory = resume_state::available_memory_bytes();
+    let requested_threads = thread_count;
+    let thread_count = match args.threads {
+        Some(explicit) => explicit,
+        None => resume_state::determine_workers(chunk_count, per_worker_estimate, available_memory)
+            .min(thread_count),
+    };
+
+    callback(ProcessingEvent::WorkerSizing {
+        requested_threads,
+        effective_threads: thread_count,
+        available_memory_bytes: available_memory,
+        per_worker_estimate_bytes: per_worker_estimate,
+    });
+    process_logger.log_info(&format!(
+        "Worker sizing: {} threads (available memory {} bytes, ~{} bytes/worker)",
+        thread_count, available_memory, per_worker_estimate
+    ));
+
+    if args.split_mode == SplitMode::Keyframe {
+        video_processor.probe_keyframe_times(&callback)?;
+    }
+
     let chunks = if let Some(duration) = args.duration_per_chunk {
         video_processor.split_by_duration(duration, &args.output, &callback)?
     } else {
@@ -407,11 +648,61 @@ fn process_video_with_callback(
     // Create output directory for JSONL files
     std::fs::create_dir_all(&args.output)?;
 
-    // Phase 2: Extract QR codes and create individual chunk JSONL files
-    let qr_extractor = QrExtractor::new(thread_count, args.skip);
-    process_logger.log_info(&format!("Starting QR extraction with {} threads, skip_frames: {}", thread_count, args.skip));
+    // Persist the chunk plan so resume logic never has to re-derive chunk
+    // count (or guess a bound) from the filesystem, and load whichever
+    // chunks a previous run already marked done.
+    let chunk_queue = ChunkQueue::from_video_chunks(&chunks, &args.output);
+    chunk_queue.save(&args.output)?;
+    let done_manifest = DoneManifest::load_or_create(&args.output)?;
+
+    let chunks_to_process: Vec<_> = if args.resume {
+        let pending_ids: std::collections::HashSet<usize> = done_manifest
+            .pending(&chunk_queue)
+            .iter()
+            .map(|entry| entry.id)
+            .collect();
+        chunks.iter().filter(|c| pending_ids.contains(&c.id)).cloned().collect()
+    } else {
+        chunks.clone()
+    };
 
-    let qr_results = qr_extractor.extract_from_chunks(&chunks, &args.output, &callback)?;
+    process_logger.log_info(&format!(
+        "{} of {} chunks already done, processing {} remaining",
+        chunk_queue.chunks.len() - chunks_to_process.len(), chunk_queue.chunks.len(), chunks_to_process.len()
+    ));
+
+    // Phase 2: Extract QR codes and create individual chunk JSONL files
+    let qr_extractor = QrExtractor::new(thread_count, args.skip)
+        .with_adaptive_sampling(args.adaptive, args.adaptive_threshold)
+        .with_control(Arc::clone(&control))
+        .with_max_tries(args.max_tries)
+        .with_error_handler(Arc::clone(&error_handler));
+    process_logger.log_info(&format!(
+        "Starting QR extraction with {} threads, skip_frames: {}, adaptive: {} (threshold: {})",
+        thread_count, args.skip, args.adaptive, args.adaptive_threshold
+    ));
+
+    let qr_results = qr_extractor.extract_from_chunks(&chunks_to_process, &args.output, &callback)?;
+
+    if control.is_stopping() {
+        let remaining = DoneManifest::load_or_create(&args.output)?.pending(&chunk_queue).len();
+        let chunks_completed = chunk_queue.chunks.len().saturating_sub(remaining);
+        let resume_command = format!(
+            "./target/release/qr-video-files --resume {} --chunks {} --threads {}",
+            args.input.as_ref().unwrap().display(), chunk_count, thread_count
+        );
+
+        process_logger.log_processing_phase("PHASE_2", &format!(
+            "STOPPED by Ctrl-C after {}/{} chunks", chunks_completed, chunk_queue.chunks.len()
+        ));
+        callback(ProcessingEvent::GracefulStop {
+            chunks_completed,
+            chunks_total: chunk_queue.chunks.len(),
+            resume_command,
+            output_dir: args.output.display().to_string(),
+        });
+        return Ok(());
+    }
 
     process_logger.log_processing_phase("PHASE_2", &format!("COMPLETED - {} QR codes extracted", qr_results.qr_codes.len()));
 
@@ -452,6 +743,102 @@ fn process_video_with_callback(
     Ok(())
 }
 
+/// Streaming mode: instead of splitting a whole seekable file up front,
+/// hand ffmpeg's own segment muxer a pipe (stdin, or a growing file) and
+/// feed each fixed-duration segment to the QR extractor as soon as it
+/// closes. Phase 3 (file reconstruction) still runs once at the end over
+/// every JSONL produced so far, rather than per-segment.
+fn process_video_streaming(
+    args: &Args,
+    thread_count: usize,
+    callback: EventCallback,
+    control: Arc<PipelineControl>,
+) -> Result<()> {
+    let log_path = args.output.join("processing.log");
+    let process_logger = crate::error_logger::ErrorLogger::new(&log_path.to_string_lossy())
+        .unwrap_or_else(|_| crate::error_logger::ErrorLogger::new("/tmp/processing.log").unwrap());
+
+    if let Err(e) = pipeline_control::install_ctrlc_handler(Arc::clone(&control)) {
+        process_logger.log_warning("SIGNAL_HANDLER", &format!("Could not install Ctrl-C handler: {}", e));
+    }
+
+    std::fs::create_dir_all(&args.output)?;
+
+    let error_handler = Arc::new(ErrorHandler::new(&args.output)?);
+
+    let callback: Arc<EventCallback> = Arc::new(callback);
+    memory_supervisor::spawn(Arc::clone(&callback), Arc::clone(&control), Arc::clone(&error_handler));
+
+    let duration_per_chunk = args.duration_per_chunk.unwrap_or(10.0);
+    let input_path = args.input.clone().unwrap_or_else(|| PathBuf::from("-"));
+
+    callback(ProcessingEvent::PhaseStarted {
+        phase: 1,
+        description: "Streaming Segment Capture".to_string(),
+    });
+    process_logger.log_processing_phase("PHASE_1", &format!(
+        "Streaming from {} in {:.1}s segments", input_path.display(), duration_per_chunk
+    ));
+
+    let segmenter = VideoProcessor::spawn_streaming_segmenter(&input_path, &args.output, duration_per_chunk)?;
+    let segment_rx = VideoProcessor::watch_streaming_segments(segmenter, args.output.clone(), duration_per_chunk);
+
+    callback(ProcessingEvent::PhaseCompleted { phase: 1, duration_ms: 0 });
+
+    callback(ProcessingEvent::PhaseStarted {
+        phase: 2,
+        description: "Incremental Segment Processing".to_string(),
+    });
+    process_logger.log_processing_phase("PHASE_2", "Started incremental segment processing");
+
+    let qr_extractor = QrExtractor::new(thread_count, args.skip)
+        .with_adaptive_sampling(args.adaptive, args.adaptive_threshold)
+        .with_control(Arc::clone(&control))
+        .with_max_tries(args.max_tries)
+        .with_error_handler(Arc::clone(&error_handler));
+
+    let mut qr_codes_total = 0usize;
+    let mut segments_processed = 0usize;
+
+    for segment in segment_rx.iter() {
+        if control.is_stopping() {
+            break;
+        }
+        let results = qr_extractor.extract_from_chunks(std::slice::from_ref(&segment), &args.output, &callback)?;
+        qr_codes_total += results.qr_codes.len();
+        segments_processed += 1;
+    }
+
+    process_logger.log_processing_phase("PHASE_2", &format!(
+        "COMPLETED - {} segments, {} QR codes extracted", segments_processed, qr_codes_total
+    ));
+    callback(ProcessingEvent::PhaseCompleted { phase: 2, duration_ms: 0 });
+
+    callback(ProcessingEvent::PhaseStarted {
+        phase: 3,
+        description: "JSONL Combination & File Reconstruction".to_string(),
+    });
+    process_logger.log_processing_phase("PHASE_3", "Started JSONL combination and file reconstruction");
+
+    let reconstructor = FileReconstructor::new(&args.output);
+    let final_report = reconstructor.process_combined_jsonl_files(&args.output, &callback)?;
+
+    process_logger.log_processing_phase("PHASE_3", &format!("COMPLETED - {} files reconstructed", final_report.files.len()));
+    callback(ProcessingEvent::PhaseCompleted { phase: 3, duration_ms: 0 });
+
+    callback(ProcessingEvent::AllCompleted {
+        total_duration_ms: 0,
+        files_extracted: final_report.files.len(),
+    });
+    callback(ProcessingEvent::FinalSummary {
+        files_count: final_report.files.len(),
+        output_dir: args.output.display().to_string(),
+        total_duration_ms: 0,
+    });
+
+    Ok(())
+}
+
 fn run_tui_demo() -> Result<()> {
     use std::thread;
 
@@ -556,6 +943,7 @@ fn simulate_8_chunk_processing(callback: EventCallback) {
         callback(ProcessingEvent::ChunkStarted {
             chunk_id: i,
             chunk_name: format!("chunk_{:03}.mp4", i + 1),
+            worker_id: i % 4,
         });
         thread::sleep(Duration::from_millis(150));
     }
@@ -563,20 +951,27 @@ fn simulate_8_chunk_processing(callback: EventCallback) {
     // Simulate parallel processing with random completion times
     let chunk_processing_times = vec![1200, 1500, 1100, 1800, 1300, 1400, 1600, 1000];
     let chunk_qr_counts = vec![150, 143, 167, 89, 134, 156, 121, 178];
+    const DEMO_TOTAL_FRAMES: u64 = 450;
+    let mut chunk_rates: Vec<progress_estimator::RateEstimator> =
+        (0..8).map(|_| progress_estimator::RateEstimator::new()).collect();
 
     // Simulate progress updates
     for step in 0..15 {
         for i in 0..8 {
             let progress = (step + 1) as f64 / 15.0;
-            let frames = (progress * 450.0) as usize;
+            let frames = (progress * DEMO_TOTAL_FRAMES as f64) as usize;
             let qrs = (progress * chunk_qr_counts[i] as f64) as usize;
 
             if step * 100 < chunk_processing_times[i] {
+                chunk_rates[i].record(frames as u64);
+                let remaining = DEMO_TOTAL_FRAMES.saturating_sub(frames as u64);
                 callback(ProcessingEvent::ChunkProgress {
                     chunk_id: i,
                     frames_processed: frames,
                     qr_codes_found: qrs,
                     status: format!("Processing frame {}", frames),
+                    fps: chunk_rates[i].fps(),
+                    eta_secs: chunk_rates[i].eta_secs(remaining),
                 });
             }
         }
@@ -592,6 +987,7 @@ fn simulate_8_chunk_processing(callback: EventCallback) {
             qr_codes_found: chunk_qr_counts[chunk_id],
             jsonl_file: format!("chunk_{:03}.jsonl", chunk_id + 1),
             duration_ms: chunk_processing_times[chunk_id] as u64,
+            worker_id: chunk_id % 4,
         });
     }
 
@@ -653,7 +1049,11 @@ fn check_completion_status(args: &Args) -> Result<()> {
         let dummy_callback: EventCallback = Box::new(|_| {});
         let video_info = video_processor.get_video_info(&dummy_callback)?;
 
-        let chunk_count = args.chunks.unwrap_or_else(|| std::cmp::max(num_cpus::get() / 2, 4));
+        // Prefer the persisted chunk plan over re-deriving a guessed count,
+        // so status reflects how the video was actually split.
+        let chunk_count = ChunkQueue::load(output_dir)?
+            .map(|queue| queue.chunks.len())
+            .unwrap_or_else(|| args.chunks.unwrap_or_else(|| std::cmp::max(num_cpus::get() / 2, 4)));
         let detector = CompletionDetector::new(
             video_info.total_frames,
             video_info.duration,
@@ -712,6 +1112,88 @@ fn check_completion_status(args: &Args) -> Result<()> {
     Ok(())
 }
 
+/// Watch an existing run's JSONL output for stalled chunks, modeled on a
+/// classic setup-ceremony monitor: poll every chunk's progress at
+/// `--polling-interval`, alert on `--chunk-timeout`/`--job-timeout`, and
+/// print both a human-readable line and a machine-readable JSON line per
+/// alert so the process can run unattended alongside `--resume`.
+fn run_monitor_mode(args: &Args) -> Result<()> {
+    println!("👀 Monitoring for stalled chunks (Ctrl-C to stop)...");
+    println!("=====================================================");
+
+    let output_dir = &args.output;
+    let input_path = args.input.clone()
+        .ok_or_else(|| anyhow::anyhow!("--monitor requires an input video file to read chunk/frame counts from"))?;
+
+    let mut video_processor = VideoProcessor::new(&input_path)?;
+    let dummy_callback: EventCallback = Box::new(|_| {});
+    let video_info = video_processor.get_video_info(&dummy_callback)?;
+
+    let chunk_count = ChunkQueue::load(output_dir)?
+        .map(|queue| queue.chunks.len())
+        .unwrap_or_else(|| args.chunks.unwrap_or_else(|| std::cmp::max(num_cpus::get() / 2, 4)));
+
+    let detector = CompletionDetector::new(
+        video_info.total_frames,
+        video_info.duration,
+        video_info.fps,
+        chunk_count,
+        args.skip,
+        output_dir,
+    )?;
+
+    let json_callback: EventCallback = Box::new(|event| {
+        if let Ok(line) = serde_json::to_string(&event) {
+            println!("{}", line);
+        }
+    });
+
+    let mut monitor = ProgressMonitor::new(
+        chunk_count,
+        std::time::Duration::from_secs(args.polling_interval),
+        std::time::Duration::from_secs(args.chunk_timeout),
+        std::time::Duration::from_secs(args.job_timeout),
+        output_dir,
+    )?
+    .with_callback(json_callback);
+
+    loop {
+        let alerts = monitor.poll_once(&detector, output_dir)?;
+        for alert in &alerts {
+            println!("⚠️  {:?}: {}", alert.severity, alert.message);
+        }
+
+        if monitor.is_job_complete() {
+            break;
+        }
+
+        std::thread::sleep(monitor.polling_interval());
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn run_live_camera_mode(args: &Args, device_path: String) -> Result<()> {
+    use live_capture::LiveCaptureConfig;
+    use std::collections::BTreeSet;
+
+    println!("📷 Scanning live camera: {}", device_path);
+    println!("======================================");
+
+    let target_chunk_ids: BTreeSet<usize> = args.live_target_chunks.iter().copied().collect();
+    let mut config = LiveCaptureConfig::new(device_path);
+    config.timeout = std::time::Duration::from_secs(args.live_timeout);
+
+    let extractor = QrExtractor::new(args.threads.unwrap_or_else(|| num_cpus::get()), args.skip);
+
+    let callback: EventCallback = Box::new(|event| ConsoleOutputHandler.handle_event(&event));
+    let qr_codes = live_capture::scan_live_camera(&extractor, &config, &target_chunk_ids, &callback)?;
+
+    println!("✅ Captured {} QR code(s) from {}", qr_codes.len(), config.device_path);
+    Ok(())
+}
+
 fn run_phase3_only(args: &Args) -> Result<()> {
     println!("🔧 Running Phase 3 Only - File Reconstruction");
     println!("==============================================");
@@ -719,13 +1201,8 @@ fn run_phase3_only(args: &Args) -> Result<()> {
     let output_dir = &args.output;
 
     // Check if JSONL files exist
-    let mut jsonl_count = 0;
-    for i in 1..=20 {
-        let jsonl_file = output_dir.join(format!("chunk_{:03}.jsonl", i));
-        if jsonl_file.exists() {
-            jsonl_count += 1;
-        }
-    }
+    let (existing_jsonl_files, _) = chunk_queue::scan_existing_chunk_files(output_dir);
+    let jsonl_count = existing_jsonl_files.len();
 
     if jsonl_count == 0 {
         println!("❌ No JSONL files found in {}", output_dir.display());
@@ -735,9 +1212,12 @@ fn run_phase3_only(args: &Args) -> Result<()> {
 
     println!("✅ Found {} JSONL files in {}", jsonl_count, output_dir.display());
 
-    // Create a minimal callback for console output
-    let callback: EventCallback = Box::new(|event| {
-        ConsoleOutputHandler.handle_event(&event);
+    // Drive a progress-bar subsystem for this console-only path instead of
+    // the scrolling text `ConsoleOutputHandler` normally prints, since Phase
+    // 3 is the one entry point most often run unattended from a terminal.
+    let output_handler = events::IndicatifOutputHandler::new();
+    let callback: EventCallback = Box::new(move |event| {
+        output_handler.handle_event(&event);
     });
 
     // Initialize logging
@@ -754,7 +1234,13 @@ fn run_phase3_only(args: &Args) -> Result<()> {
     });
 
     // Run Phase 3 file reconstruction
-    let reconstructor = FileReconstructor::new(output_dir);
+    let remap_rules: Vec<(String, String)> = args.remap.iter()
+        .filter_map(|rule| rule.split_once('=').map(|(src, dst)| (src.to_string(), dst.to_string())))
+        .collect();
+
+    let reconstructor = FileReconstructor::new(output_dir)
+        .with_restore_filters(args.restore_files.clone())
+        .with_remap_rules(remap_rules);
     let final_report = reconstructor.process_combined_jsonl_files(output_dir, &callback)?;
 
     process_logger.log_info(&format!("Phase 3 completed: {} files reconstructed", final_report.files.len()));
@@ -764,6 +1250,18 @@ fn run_phase3_only(args: &Args) -> Result<()> {
         duration_ms: 0,
     });
 
+    if args.verify {
+        println!("\n🔍 Verifying reconstructed files on disk...");
+        let failures = reconstructor.verify_reconstructed_files(&final_report, &callback)?;
+        if failures > 0 {
+            process_logger.log_error("VERIFY", &format!("{} file(s) failed verification", failures));
+            println!("❌ {} file(s) failed verification", failures);
+        } else {
+            process_logger.log_info("Verification passed: all files match their recorded checksums");
+            println!("✅ All {} files verified", final_report.files.len());
+        }
+    }
+
     callback(ProcessingEvent::FinalSummary {
         files_count: final_report.files.len(),
         output_dir: output_dir.display().to_string(),
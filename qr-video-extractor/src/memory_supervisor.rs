@@ -0,0 +1,75 @@
+use crate::error_handler::{check_memory_usage, ErrorHandler};
+use crate::events::{EventCallback, ProcessingEvent};
+use crate::pipeline_control::PipelineControl;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Resident-memory percentage at which we pause dispatching new chunk work
+/// (`PipelineControl::set_paused`), the same back-channel Ctrl-C already
+/// uses - in-flight chunks still finish and flush normally.
+const HIGH_WATER_PCT: f64 = 85.0;
+
+/// Percentage the usage has to fall back below before we resume dispatch,
+/// so the supervisor doesn't flap pause/resume right at the threshold.
+const LOW_WATER_PCT: f64 = 70.0;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Spawn a detached background thread that polls resident memory and
+/// throttles chunk dispatch around `HIGH_WATER_PCT`/`LOW_WATER_PCT`, for the
+/// duration of one `process_video_with_callback`/`process_video_streaming`
+/// run. Best-effort: if the thread fails to spawn, processing proceeds
+/// without throttling rather than aborting over a feature it can live
+/// without.
+pub fn spawn(callback: Arc<EventCallback>, control: Arc<PipelineControl>, error_handler: Arc<ErrorHandler>) {
+    let result = std::thread::Builder::new()
+        .name("memory-supervisor".to_string())
+        .spawn(move || run(&callback, &control, &error_handler));
+
+    if let Err(e) = result {
+        error_handler.log_debug("MEMORY_SUPERVISOR", &format!("Failed to spawn supervisor thread: {}", e));
+    }
+}
+
+fn run(callback: &EventCallback, control: &PipelineControl, error_handler: &ErrorHandler) {
+    let mut throttled = false;
+
+    while !control.is_stopping() {
+        std::thread::sleep(POLL_INTERVAL);
+        if control.is_stopping() {
+            return;
+        }
+
+        let (resident_bytes, percentage) = match check_memory_usage() {
+            Ok(usage) => usage,
+            // Can't read memory on this platform/process right now - skip
+            // this tick rather than throttling on stale or guessed data.
+            Err(_) => continue,
+        };
+
+        if !throttled && percentage >= HIGH_WATER_PCT {
+            throttled = true;
+            control.set_paused(true);
+            error_handler.handle_resource_exhaustion(
+                "memory",
+                &format!(
+                    "Resident memory at {:.1}% ({} bytes) - pausing new chunk dispatch until it falls below {:.1}%",
+                    percentage, resident_bytes, LOW_WATER_PCT
+                ),
+            );
+            callback(ProcessingEvent::ModeTransition {
+                from: "running".to_string(),
+                to: "throttled".to_string(),
+                reason: format!("memory usage {:.1}% crossed the {:.1}% high-water mark", percentage, HIGH_WATER_PCT),
+            });
+        } else if throttled && percentage <= LOW_WATER_PCT {
+            throttled = false;
+            control.set_paused(false);
+            callback(ProcessingEvent::ModeTransition {
+                from: "throttled".to_string(),
+                to: "running".to_string(),
+                reason: format!("memory usage {:.1}% fell back below the {:.1}% low-water mark", percentage, LOW_WATER_PCT),
+            });
+        }
+    }
+}
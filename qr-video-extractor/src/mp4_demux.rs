@@ -0,0 +1,567 @@
+//! Pure-Rust mp4 box reader/writer, compiled in only under the
+//! `mp4-native` feature. Lets `VideoProcessor` derive `VideoInfo` and cut
+//! `.mp4` chunks for the common H.264/H.265-in-mp4 case without an
+//! `ffprobe`/`ffmpeg` subprocess at all - modeled loosely on re_mp4's
+//! zero-copy box walker, but narrowed to exactly what `video.rs` needs:
+//! `moov`/`trak`/`mdia`/`stbl` for probing, plus rewriting the video
+//! track's sample tables to produce a boundary-aligned, stream-copied
+//! chunk.
+//!
+//! Deliberately out of scope (same spirit as `video.rs`'s `get_video_info`
+//! `(1920, 1080)` fallback): audio/subtitle tracks, edit
+//! lists (`elst`), composition-time offsets (`ctts`), and 64-bit box
+//! sizes (`largesize`) on anything but the top-level `mdat`. A video that
+//! needs any of those falls back to the external ffmpeg path in
+//! `video.rs`.
+
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::video::VideoInfo;
+
+const BOX_HEADER_SIZE: u64 = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct BoxHeader {
+    kind: [u8; 4],
+    /// Absolute offset of the box, header included.
+    start: u64,
+    /// Total size of the box, header included.
+    size: u64,
+}
+
+impl BoxHeader {
+    fn payload_start(&self) -> u64 {
+        self.start + BOX_HEADER_SIZE
+    }
+
+    fn payload_len(&self) -> u64 {
+        self.size - BOX_HEADER_SIZE
+    }
+}
+
+fn read_box_header(f: &mut File) -> Result<Option<BoxHeader>> {
+    let start = f.stream_position()?;
+    let mut buf = [0u8; 8];
+    if let Err(e) = f.read_exact(&mut buf) {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            return Ok(None);
+        }
+        return Err(e.into());
+    }
+
+    let declared_size = u32::from_be_bytes(buf[0..4].try_into().unwrap()) as u64;
+    let mut kind = [0u8; 4];
+    kind.copy_from_slice(&buf[4..8]);
+
+    let size = if declared_size == 1 && &kind == b"mdat" {
+        // 64-bit `largesize` follows the type. `mdat` is only ever skipped
+        // over, never read through `payload_start`, so its longer header
+        // needs no further handling.
+        let mut large = [0u8; 8];
+        f.read_exact(&mut large)?;
+        u64::from_be_bytes(large)
+    } else if declared_size == 1 {
+        return Err(anyhow!("64-bit box sizes are not supported by the native mp4 reader"));
+    } else if declared_size == 0 {
+        f.seek(SeekFrom::End(0))? - start
+    } else {
+        declared_size
+    };
+
+    Ok(Some(BoxHeader { kind, start, size }))
+}
+
+/// Find the first direct child of kind `target` inside `[parent_start,
+/// parent_end)`, which must already enclose only box children with no
+/// leading full-box header (true for `moov`/`trak`/`mdia`/`minf`/`stbl`).
+fn find_child(f: &mut File, parent_start: u64, parent_end: u64, target: &[u8; 4]) -> Result<Option<BoxHeader>> {
+    f.seek(SeekFrom::Start(parent_start))?;
+    loop {
+        let pos = f.stream_position()?;
+        if pos >= parent_end {
+            return Ok(None);
+        }
+        let Some(header) = read_box_header(f)? else { return Ok(None) };
+        if &header.kind == target {
+            return Ok(Some(header));
+        }
+        f.seek(SeekFrom::Start(header.start + header.size))?;
+    }
+}
+
+fn read_payload(f: &mut File, header: &BoxHeader) -> Result<Vec<u8>> {
+    f.seek(SeekFrom::Start(header.payload_start()))?;
+    let mut buf = vec![0u8; header.payload_len() as usize];
+    f.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_be_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+/// The handful of `stbl` child tables needed to enumerate every sample of
+/// a track: its file offset, size, and presentation duration.
+struct SampleTable {
+    timescale: u32,
+    sample_sizes: Vec<u32>,
+    sample_durations: Vec<u32>,
+    sample_offsets: Vec<u64>,
+    /// 1-based sample numbers that are sync (keyframe) samples. `None`
+    /// means every sample is a sync sample (no `stss` box - common for
+    /// intra-only streams).
+    sync_samples: Option<Vec<u32>>,
+}
+
+fn parse_stts(payload: &[u8]) -> Vec<(u32, u32)> {
+    let entry_count = read_u32(payload, 4) as usize;
+    (0..entry_count)
+        .map(|i| {
+            let base = 8 + i * 8;
+            (read_u32(payload, base), read_u32(payload, base + 4))
+        })
+        .collect()
+}
+
+fn parse_stsz(payload: &[u8]) -> Vec<u32> {
+    let uniform_size = read_u32(payload, 4);
+    let sample_count = read_u32(payload, 8) as usize;
+    if uniform_size != 0 {
+        return vec![uniform_size; sample_count];
+    }
+    (0..sample_count).map(|i| read_u32(payload, 12 + i * 4)).collect()
+}
+
+fn parse_stsc(payload: &[u8]) -> Vec<(u32, u32, u32)> {
+    let entry_count = read_u32(payload, 4) as usize;
+    (0..entry_count)
+        .map(|i| {
+            let base = 8 + i * 12;
+            (read_u32(payload, base), read_u32(payload, base + 4), read_u32(payload, base + 8))
+        })
+        .collect()
+}
+
+fn parse_chunk_offsets(header: &BoxHeader, payload: &[u8]) -> Vec<u64> {
+    let entry_count = read_u32(payload, 4) as usize;
+    if &header.kind == b"co64" {
+        (0..entry_count).map(|i| read_u64(payload, 8 + i * 8)).collect()
+    } else {
+        (0..entry_count).map(|i| read_u32(payload, 8 + i * 4) as u64).collect()
+    }
+}
+
+fn parse_stss(payload: &[u8]) -> Vec<u32> {
+    let entry_count = read_u32(payload, 4) as usize;
+    (0..entry_count).map(|i| read_u32(payload, 8 + i * 4)).collect()
+}
+
+fn samples_per_chunk_at(stsc: &[(u32, u32, u32)], chunk_number: u32) -> u32 {
+    stsc.iter()
+        .rev()
+        .find(|(first_chunk, _, _)| *first_chunk <= chunk_number)
+        .map(|(_, spc, _)| *spc)
+        .unwrap_or(1)
+}
+
+/// Locate the video `trak` (first track whose `hdlr` reports `vide`) and
+/// read its `mdhd` timescale plus `stbl` sample table.
+fn read_video_track(f: &mut File, moov: &BoxHeader) -> Result<(BoxHeader, u32, SampleTable)> {
+    let moov_end = moov.start + moov.size;
+    let mut search_start = moov.payload_start();
+
+    loop {
+        let Some(trak) = find_child(f, search_start, moov_end, b"trak")? else {
+            return Err(anyhow!("no video track found in moov"));
+        };
+
+        let mdia = find_child(f, trak.payload_start(), trak.start + trak.size, b"mdia")?
+            .ok_or_else(|| anyhow!("trak is missing mdia"))?;
+        let mdia_end = mdia.start + mdia.size;
+
+        let hdlr = find_child(f, mdia.payload_start(), mdia_end, b"hdlr")?
+            .ok_or_else(|| anyhow!("mdia is missing hdlr"))?;
+        let hdlr_payload = read_payload(f, &hdlr)?;
+        let is_video = hdlr_payload.get(8..12) == Some(b"vide".as_slice());
+
+        if !is_video {
+            search_start = trak.start + trak.size;
+            continue;
+        }
+
+        let mdhd = find_child(f, mdia.payload_start(), mdia_end, b"mdhd")?
+            .ok_or_else(|| anyhow!("mdia is missing mdhd"))?;
+        let mdhd_payload = read_payload(f, &mdhd)?;
+        let timescale = read_u32(&mdhd_payload, 12);
+
+        let minf = find_child(f, mdia.payload_start(), mdia_end, b"minf")?
+            .ok_or_else(|| anyhow!("mdia is missing minf"))?;
+        let stbl = find_child(f, minf.payload_start(), minf.start + minf.size, b"stbl")?
+            .ok_or_else(|| anyhow!("minf is missing stbl"))?;
+        let stbl_end = stbl.start + stbl.size;
+
+        let stts = find_child(f, stbl.payload_start(), stbl_end, b"stts")?
+            .ok_or_else(|| anyhow!("stbl is missing stts"))?;
+        let stsz = find_child(f, stbl.payload_start(), stbl_end, b"stsz")?
+            .ok_or_else(|| anyhow!("stbl is missing stsz"))?;
+        let stsc = find_child(f, stbl.payload_start(), stbl_end, b"stsc")?
+            .ok_or_else(|| anyhow!("stbl is missing stsc"))?;
+        let stco = match find_child(f, stbl.payload_start(), stbl_end, b"stco")? {
+            Some(h) => h,
+            None => find_child(f, stbl.payload_start(), stbl_end, b"co64")?
+                .ok_or_else(|| anyhow!("stbl is missing stco/co64"))?,
+        };
+        let stss = find_child(f, stbl.payload_start(), stbl_end, b"stss")?;
+
+        let stts_entries = parse_stts(&read_payload(f, &stts)?);
+        let sample_sizes = parse_stsz(&read_payload(f, &stsz)?);
+        let stsc_entries = parse_stsc(&read_payload(f, &stsc)?);
+        let chunk_offsets = parse_chunk_offsets(&stco, &read_payload(f, &stco)?);
+        let sync_samples = stss.map(|h| read_payload(f, &h)).transpose()?.map(|p| parse_stss(&p));
+
+        let sample_durations: Vec<u32> = stts_entries
+            .iter()
+            .flat_map(|&(count, delta)| std::iter::repeat(delta).take(count as usize))
+            .collect();
+
+        let mut sample_offsets = Vec::with_capacity(sample_sizes.len());
+        let mut sample_idx = 0usize;
+        for (chunk_i, &chunk_offset) in chunk_offsets.iter().enumerate() {
+            let spc = samples_per_chunk_at(&stsc_entries, (chunk_i + 1) as u32);
+            let mut running = chunk_offset;
+            for _ in 0..spc {
+                if sample_idx >= sample_sizes.len() {
+                    break;
+                }
+                sample_offsets.push(running);
+                running += sample_sizes[sample_idx] as u64;
+                sample_idx += 1;
+            }
+        }
+
+        if sample_offsets.len() != sample_sizes.len() || sample_offsets.len() != sample_durations.len() {
+            return Err(anyhow!("stbl sample tables disagree on sample count"));
+        }
+
+        return Ok((
+            trak,
+            timescale,
+            SampleTable { timescale, sample_sizes, sample_durations, sample_offsets, sync_samples },
+        ));
+    }
+}
+
+fn read_tkhd_dimensions(f: &mut File, trak: &BoxHeader) -> Result<(u32, u32)> {
+    let tkhd = find_child(f, trak.payload_start(), trak.start + trak.size, b"tkhd")?
+        .ok_or_else(|| anyhow!("trak is missing tkhd"))?;
+    let payload = read_payload(f, &tkhd)?;
+    // Width/height are the last two 4-byte 16.16 fixed-point fields,
+    // regardless of version (only the time fields before them change size).
+    let width = read_u32(&payload, payload.len() - 8) >> 16;
+    let height = read_u32(&payload, payload.len() - 4) >> 16;
+    Ok((width, height))
+}
+
+fn find_moov(f: &mut File, file_size: u64) -> Result<BoxHeader> {
+    f.seek(SeekFrom::Start(0))?;
+    loop {
+        let pos = f.stream_position()?;
+        if pos >= file_size {
+            return Err(anyhow!("no moov box found"));
+        }
+        let Some(header) = read_box_header(f)? else { return Err(anyhow!("no moov box found")) };
+        if &header.kind == b"moov" {
+            return Ok(header);
+        }
+        f.seek(SeekFrom::Start(header.start + header.size))?;
+    }
+}
+
+/// Derive `VideoInfo` by reading `moov`/`trak`/`mdia`/`stbl` directly,
+/// instead of probing the file with `ffprobe`.
+pub fn probe_video_info(path: &Path) -> Result<VideoInfo> {
+    let mut f = File::open(path)?;
+    let file_size = f.metadata()?.len();
+
+    let moov = find_moov(&mut f, file_size)?;
+    let (trak, timescale, table) = read_video_track(&mut f, &moov)?;
+    let (width, height) = read_tkhd_dimensions(&mut f, &trak)?;
+
+    let total_frames = table.sample_durations.len() as u64;
+    let duration = table.sample_durations.iter().map(|&d| d as u64).sum::<u64>() as f64 / timescale as f64;
+    let fps = if duration > 0.0 { total_frames as f64 / duration } else { 0.0 };
+
+    Ok(VideoInfo {
+        width,
+        height,
+        fps,
+        duration,
+        total_frames,
+        file_size,
+    })
+}
+
+/// Build a 32-bit-size full box: 4-byte size, 4-byte type, 4-byte
+/// version+flags (always zero here), then `body`.
+fn full_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(12 + body.len());
+    out.extend_from_slice(&(12u32 + body.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(&[0, 0, 0, 0]);
+    out.extend_from_slice(body);
+    out
+}
+
+fn plain_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + body.len());
+    out.extend_from_slice(&(8u32 + body.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(body);
+    out
+}
+
+/// Rewrite one `[start_time, end_time)` slice of `input`'s video track to
+/// `output`, stream-copying the sliced samples' bytes verbatim and
+/// regenerating just enough of `moov` (`mvhd`/`tkhd`/`mdhd` durations, and
+/// `stts`/`stsz`/`stco`/`stsc`/`stss`) for the result to be a standalone,
+/// valid mp4. The slice is snapped to the nearest preceding sync sample at
+/// both ends, mirroring `VideoProcessor::probe_keyframe_times` snapping
+/// the arithmetic boundaries used by the external ffmpeg path.
+pub fn split_chunk(input: &Path, output: &Path, start_time: f64, end_time: f64) -> Result<()> {
+    let mut src = File::open(input)?;
+    let file_size = src.metadata()?.len();
+
+    let moov = find_moov(&mut src, file_size)?;
+    let (trak, timescale, table) = read_video_track(&mut src, &moov)?;
+    let (width, height) = read_tkhd_dimensions(&mut src, &trak)?;
+
+    let is_sync = |sample_number: u32| -> bool {
+        match &table.sync_samples {
+            Some(list) => list.binary_search(&sample_number).is_ok(),
+            None => true,
+        }
+    };
+
+    let mut cumulative = vec![0u64; table.sample_durations.len() + 1];
+    for (i, &d) in table.sample_durations.iter().enumerate() {
+        cumulative[i + 1] = cumulative[i] + d as u64;
+    }
+
+    let start_target = (start_time * timescale as f64).round() as u64;
+    let end_target = (end_time * timescale as f64).round() as u64;
+
+    let mut start_index = cumulative
+        .iter()
+        .rposition(|&t| t <= start_target)
+        .unwrap_or(0)
+        .min(table.sample_durations.len().saturating_sub(1));
+    while start_index > 0 && !is_sync((start_index + 1) as u32) {
+        start_index -= 1;
+    }
+
+    let mut end_index = cumulative.iter().rposition(|&t| t <= end_target).unwrap_or(table.sample_durations.len());
+    if end_index <= start_index {
+        end_index = table.sample_durations.len();
+    }
+    while end_index > start_index && end_index < table.sample_durations.len() && !is_sync((end_index + 1) as u32) {
+        end_index -= 1;
+    }
+
+    let slice = start_index..end_index;
+    if slice.is_empty() {
+        return Err(anyhow!("requested chunk range contains no samples"));
+    }
+
+    let sizes = &table.sample_sizes[slice.clone()];
+    let durations = &table.sample_durations[slice.clone()];
+    let offsets = &table.sample_offsets[slice.clone()];
+
+    // mdat: copy each sample's bytes verbatim, back to back.
+    let mut mdat_body = Vec::with_capacity(sizes.iter().map(|&s| s as usize).sum());
+    for (&offset, &size) in offsets.iter().zip(sizes.iter()) {
+        src.seek(SeekFrom::Start(offset))?;
+        let mut buf = vec![0u8; size as usize];
+        src.read_exact(&mut buf)?;
+        mdat_body.extend_from_slice(&buf);
+    }
+
+    // stts: run-length encode the sliced durations.
+    let mut stts_entries: Vec<(u32, u32)> = Vec::new();
+    for &d in durations {
+        match stts_entries.last_mut() {
+            Some((count, delta)) if *delta == d => *count += 1,
+            _ => stts_entries.push((1, d)),
+        }
+    }
+    let mut stts_body = Vec::new();
+    stts_body.extend_from_slice(&(stts_entries.len() as u32).to_be_bytes());
+    for (count, delta) in &stts_entries {
+        stts_body.extend_from_slice(&count.to_be_bytes());
+        stts_body.extend_from_slice(&delta.to_be_bytes());
+    }
+    let stts_box = full_box(b"stts", &stts_body);
+
+    // stsz: explicit per-sample sizes (uniform_size = 0).
+    let mut stsz_body = Vec::new();
+    stsz_body.extend_from_slice(&0u32.to_be_bytes());
+    stsz_body.extend_from_slice(&(sizes.len() as u32).to_be_bytes());
+    for &s in sizes {
+        stsz_body.extend_from_slice(&s.to_be_bytes());
+    }
+    let stsz_box = full_box(b"stsz", &stsz_body);
+
+    // stsc: one chunk per sample, so stco can list each sample's offset
+    // directly - simplest table that's still valid, at the cost of an
+    // entry per sample rather than per physical chunk.
+    let mut stsc_body = Vec::new();
+    stsc_body.extend_from_slice(&1u32.to_be_bytes());
+    stsc_body.extend_from_slice(&1u32.to_be_bytes());
+    stsc_body.extend_from_slice(&1u32.to_be_bytes());
+    stsc_body.extend_from_slice(&1u32.to_be_bytes());
+    let stsc_box = full_box(b"stsc", &stsc_body);
+
+    // stss: renumber the sync samples that survive into the slice.
+    let stss_box = table.sync_samples.as_ref().map(|list| {
+        let renumbered: Vec<u32> = list
+            .iter()
+            .filter(|&&n| (n as usize) > start_index && (n as usize) <= end_index)
+            .map(|&n| n - start_index as u32)
+            .collect();
+        let mut body = Vec::new();
+        body.extend_from_slice(&(renumbered.len() as u32).to_be_bytes());
+        for n in renumbered {
+            body.extend_from_slice(&n.to_be_bytes());
+        }
+        full_box(b"stss", &body)
+    });
+
+    // moov/mvhd+trak/tkhd+trak/mdia/mdhd duration, in this track's
+    // timescale - only the `stco` offsets below wait for the mdat offset.
+    let sample_count = sizes.len();
+    let chunk_duration_units: u32 = durations.iter().sum();
+
+    let mut moov_body = Vec::new();
+
+    // mvhd: minimal, timescale matches the track's so duration lines up 1:1.
+    let mut mvhd_body = vec![0u8; 100];
+    mvhd_body[12..16].copy_from_slice(&timescale.to_be_bytes());
+    mvhd_body[16..20].copy_from_slice(&chunk_duration_units.to_be_bytes());
+    mvhd_body[20..24].copy_from_slice(&0x00010000u32.to_be_bytes()); // rate 1.0
+    mvhd_body[24..26].copy_from_slice(&0x0100u16.to_be_bytes()); // volume 1.0
+    mvhd_body[96..100].copy_from_slice(&2u32.to_be_bytes()); // next_track_ID
+    // mvhd_body and tkhd_body already start with their version/flags.
+    moov_body.extend_from_slice(&plain_box(b"mvhd", &mvhd_body));
+
+    let mut tkhd_body = vec![0u8; 84];
+    tkhd_body[0] = 0;
+    tkhd_body[3] = 0x07; // enabled | in_movie | in_preview
+    tkhd_body[12..16].copy_from_slice(&1u32.to_be_bytes()); // track_ID
+    tkhd_body[20..24].copy_from_slice(&chunk_duration_units.to_be_bytes());
+    // identity matrix
+    let identity: [u32; 9] = [0x00010000, 0, 0, 0, 0x00010000, 0, 0, 0, 0x40000000];
+    for (i, v) in identity.iter().enumerate() {
+        tkhd_body[40 + i * 4..44 + i * 4].copy_from_slice(&v.to_be_bytes());
+    }
+    tkhd_body[76..80].copy_from_slice(&(width << 16).to_be_bytes());
+    tkhd_body[80..84].copy_from_slice(&(height << 16).to_be_bytes());
+    let tkhd_box = plain_box(b"tkhd", &tkhd_body);
+
+    let mut mdhd_body = vec![0u8; 20];
+    mdhd_body[8..12].copy_from_slice(&timescale.to_be_bytes());
+    mdhd_body[12..16].copy_from_slice(&chunk_duration_units.to_be_bytes());
+    mdhd_body[16..18].copy_from_slice(&0x55c4u16.to_be_bytes()); // "und"
+    let mdhd_box = full_box(b"mdhd", &mdhd_body);
+
+    let mut hdlr_body = vec![0u8; 24];
+    hdlr_body[4..8].copy_from_slice(b"vide");
+    let hdlr_box = full_box(b"hdlr", &hdlr_body);
+
+    // `stco` is written with zeroed offsets first, since the absolute mdat
+    // position isn't known until `ftyp`+`moov` are fully assembled; the
+    // zero-filled bytes are then patched in place below, at the offset
+    // recorded while building `moov_box` (every ancestor here is a
+    // `plain_box`, an 8-byte header with no version/flags, so the offset
+    // is just the sum of each level's preceding sibling boxes).
+    let mut stco_body = Vec::new();
+    stco_body.extend_from_slice(&(sample_count as u32).to_be_bytes());
+    for _ in 0..sample_count {
+        stco_body.extend_from_slice(&0u32.to_be_bytes());
+    }
+    let stco_box = full_box(b"stco", &stco_body);
+    let stco_entries_offset_in_stco_box = BOX_HEADER_SIZE as usize + 8; // box header + version/flags+count
+
+    let vmhd_box = plain_box(b"vmhd", &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut stbl_content = Vec::new();
+    stbl_content.extend_from_slice(&stts_box);
+    stbl_content.extend_from_slice(&stsz_box);
+    stbl_content.extend_from_slice(&stsc_box);
+    let stco_offset_in_stbl_box = BOX_HEADER_SIZE as usize + stbl_content.len();
+    stbl_content.extend_from_slice(&stco_box);
+    if let Some(stss) = &stss_box {
+        stbl_content.extend_from_slice(stss);
+    }
+    let stbl_box = plain_box(b"stbl", &stbl_content);
+
+    let mut minf_content = Vec::new();
+    minf_content.extend_from_slice(&vmhd_box);
+    let stbl_offset_in_minf_box = BOX_HEADER_SIZE as usize + minf_content.len();
+    minf_content.extend_from_slice(&stbl_box);
+    let minf_box = plain_box(b"minf", &minf_content);
+
+    let mut mdia_content = Vec::new();
+    mdia_content.extend_from_slice(&mdhd_box);
+    mdia_content.extend_from_slice(&hdlr_box);
+    let minf_offset_in_mdia_box = BOX_HEADER_SIZE as usize + mdia_content.len();
+    mdia_content.extend_from_slice(&minf_box);
+    let mdia_box = plain_box(b"mdia", &mdia_content);
+
+    let mut trak_content = Vec::new();
+    trak_content.extend_from_slice(&tkhd_box);
+    let mdia_offset_in_trak_box = BOX_HEADER_SIZE as usize + trak_content.len();
+    trak_content.extend_from_slice(&mdia_box);
+    let trak_box = plain_box(b"trak", &trak_content);
+
+    let trak_offset_in_moov_box = BOX_HEADER_SIZE as usize + moov_body.len();
+    moov_body.extend_from_slice(&trak_box);
+    let mut moov_box = plain_box(b"moov", &moov_body);
+
+    let ftyp_box = plain_box(b"ftyp", b"isom\0\0\x02\0isomiso2avc1mp41");
+
+    let stco_entries_offset_in_moov_box = trak_offset_in_moov_box
+        + mdia_offset_in_trak_box
+        + minf_offset_in_mdia_box
+        + stbl_offset_in_minf_box
+        + stco_offset_in_stbl_box
+        + stco_entries_offset_in_stco_box;
+
+    // Now that every earlier box's size is fixed, the mdat payload starts
+    // right after `ftyp` + `moov`, so absolute sample offsets can be
+    // computed and patched directly into the zero-filled `stco` entries
+    // already embedded in `moov_box`.
+    let mdat_start = (ftyp_box.len() + moov_box.len()) as u64 + BOX_HEADER_SIZE;
+    let mut running = mdat_start;
+    for (i, &size) in sizes.iter().enumerate() {
+        let entry_start = stco_entries_offset_in_moov_box + i * 4;
+        moov_box[entry_start..entry_start + 4].copy_from_slice(&(running as u32).to_be_bytes());
+        running += size as u64;
+    }
+
+    let mdat_box_header = (mdat_body.len() as u32 + BOX_HEADER_SIZE as u32).to_be_bytes();
+
+    let mut out = File::create(output)?;
+    out.write_all(&ftyp_box)?;
+    out.write_all(&moov_box)?;
+    out.write_all(&mdat_box_header)?;
+    out.write_all(b"mdat")?;
+    out.write_all(&mdat_body)?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Back-channel from the TUI thread to the worker threads: a shared,
+/// lock-free pause flag workers poll between chunks, so a long multi-minute
+/// run can be paused/resumed interactively instead of being fire-and-forget.
+pub struct PipelineControl {
+    paused: AtomicBool,
+    stopping: AtomicBool,
+}
+
+impl PipelineControl {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            paused: AtomicBool::new(false),
+            stopping: AtomicBool::new(false),
+        })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Flip the pause flag and return the new state.
+    pub fn toggle_paused(&self) -> bool {
+        let new_value = !self.is_paused();
+        self.set_paused(new_value);
+        new_value
+    }
+
+    /// Block the calling worker thread while paused, polling at a short
+    /// interval so a resume is picked up quickly without busy-spinning.
+    pub fn wait_if_paused(&self) {
+        while self.is_paused() {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Whether a graceful stop (e.g. Ctrl-C) has been requested. Workers
+    /// check this before picking up a new chunk; chunks already in flight
+    /// are left to finish and flush normally.
+    pub fn is_stopping(&self) -> bool {
+        self.stopping.load(Ordering::Relaxed)
+    }
+
+    pub fn request_stop(&self) {
+        self.stopping.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Install a SIGINT/Ctrl-C handler that requests a stop on `control` instead
+/// of letting the process be killed mid-write. Safe to call once per
+/// process; installing a second handler returns an error.
+pub fn install_ctrlc_handler(control: Arc<PipelineControl>) -> Result<()> {
+    ctrlc::set_handler(move || {
+        control.request_stop();
+    })
+    .map_err(|e| anyhow::anyhow!("Failed to install Ctrl-C handler: {}", e))
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// How far back a rate estimate looks before blending into the EMA -
+/// mirrors Av1an's `update_progress_bar_estimates` and vspipe's rolling FPS
+/// report: a short window of raw samples smoothed by an exponential moving
+/// average, so one slow or fast tick doesn't whipsaw the ETA.
+const WINDOW_SECS: f64 = 10.0;
+
+/// Tracks a sliding window of `(Instant, frames_processed)` samples and
+/// blends the windowed rate into an EMA, so a caller can report a smoothed
+/// frames-per-second and derive an ETA from it. One estimator per chunk
+/// (reset on `ChunkStarted`) or one global estimator, depending on caller.
+#[derive(Debug, Default)]
+pub struct RateEstimator {
+    samples: VecDeque<(Instant, u64)>,
+    ema_fps: f64,
+}
+
+impl RateEstimator {
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            ema_fps: 0.0,
+        }
+    }
+
+    /// Drop the window and EMA, for a fresh chunk whose rate has nothing to
+    /// do with whatever came before it.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.ema_fps = 0.0;
+    }
+
+    /// Record a new cumulative `frames_processed` total and recompute the
+    /// smoothed rate. Call once per progress tick.
+    pub fn record(&mut self, frames_processed: u64) {
+        let now = Instant::now();
+        self.samples.push_back((now, frames_processed));
+        while let Some(&(t, _)) = self.samples.front() {
+            if now.duration_since(t).as_secs_f64() > WINDOW_SECS {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if let (Some(&(oldest_t, oldest_frames)), Some(&(newest_t, newest_frames))) =
+            (self.samples.front(), self.samples.back())
+        {
+            let dt = newest_t.duration_since(oldest_t).as_secs_f64();
+            // Clamped by the `dt > 0.0` / `newest_frames > oldest_frames`
+            // guards below, so a single sample (or two in the same instant)
+            // never divides by zero.
+            if dt > 0.0 && newest_frames > oldest_frames {
+                let window_fps = (newest_frames - oldest_frames) as f64 / dt;
+                self.ema_fps = if self.ema_fps > 0.0 {
+                    0.7 * self.ema_fps + 0.3 * window_fps
+                } else {
+                    window_fps
+                };
+            }
+        }
+    }
+
+    /// Smoothed frames-per-second, or `0.0` until enough samples exist.
+    pub fn fps(&self) -> f64 {
+        self.ema_fps
+    }
+
+    /// Seconds remaining to process `remaining_frames` at the current rate,
+    /// or `None` until the estimator has a usable rate.
+    pub fn eta_secs(&self, remaining_frames: u64) -> Option<u64> {
+        if self.ema_fps > 0.0 {
+            Some((remaining_frames as f64 / self.ema_fps).round() as u64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Combine each chunk's own `(fps, remaining_frames)` into one global ETA.
+/// Chunks run in parallel, so the system's combined throughput is the sum
+/// of each chunk's own rate - a chunk that's already finished (0 frames
+/// remaining) or hasn't reported a rate yet simply contributes nothing,
+/// which naturally weights the total toward whichever chunks still have
+/// work left. Returns `None` until at least one chunk has a usable rate.
+pub fn aggregate_eta_secs(per_chunk: &[(f64, u64)]) -> Option<u64> {
+    let total_remaining: u64 = per_chunk.iter().map(|&(_, remaining)| remaining).sum();
+    if total_remaining == 0 {
+        return Some(0);
+    }
+    let total_fps: f64 = per_chunk.iter().filter(|&&(_, remaining)| remaining > 0).map(|&(fps, _)| fps).sum();
+    if total_fps > 0.0 {
+        Some((total_remaining as f64 / total_fps).round() as u64)
+    } else {
+        None
+    }
+}
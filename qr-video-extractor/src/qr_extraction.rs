@@ -1,20 +1,142 @@
 use anyhow::{anyhow, Result};
 use ffmpeg_next as ffmpeg;
 use image::{ImageBuffer, Rgb};
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::io::{BufReader, Read};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use thiserror::Error;
 
 use crate::events::{EventCallback, ProcessingEvent};
 use crate::video::VideoChunk;
+use crate::chunk_queue::DoneManifest;
+use crate::pipeline_control::PipelineControl;
+use crate::chunk_broker::ChunkBroker;
+use crate::error_handler::ErrorHandler;
+
+/// Distinct failure modes behind what used to be blanket `anyhow!` strings,
+/// so a batch driver (`extract_from_chunks`'s `ChunkBroker`, Phase 3
+/// combining) can tell a corrupt frame it should skip apart from a broken
+/// output path it should abort the run over.
+#[derive(Debug, Error)]
+pub enum QrExtractError {
+    #[error("failed to scale frame: {0}")]
+    Scale(String),
+    #[error("failed to build an image buffer from decoded frame data")]
+    FrameBuffer,
+    #[error("QR decode failed: {0}")]
+    Decode(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("ffmpeg probe failed: {0}")]
+    FfmpegProbe(String),
+}
+
+impl QrExtractError {
+    /// `true` for errors scoped to one frame or chunk - a batch driver can
+    /// log it and move on. `false` for errors that mean the run itself
+    /// cannot produce correct output (a missing/unwritable output path, a
+    /// corrupt JSONL it can no longer trust) and should abort instead.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            QrExtractError::Scale(_) => true,
+            QrExtractError::FrameBuffer => true,
+            QrExtractError::Decode(_) => true,
+            QrExtractError::Io(_) => false,
+            QrExtractError::Serialize(_) => false,
+            QrExtractError::FfmpegProbe(_) => false,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QrCodeData {
+    /// First frame this payload was decoded on.
     pub frame_number: u64,
     pub data: String,
     pub chunk_id: usize,
+    /// Last frame this exact payload was still on screen for, so
+    /// reassembly knows its full on-screen dwell window rather than just
+    /// the one frame it happened to be decoded on. Deserializes as 0 from
+    /// JSONL written before this field existed (`load_chunk_from_jsonl`
+    /// raises that to `frame_number`); equals `frame_number` for any
+    /// payload only ever seen on a single decoded frame.
+    #[serde(default)]
+    pub last_frame_number: u64,
+    /// Base64-encoded raw payload bytes, present only when the symbol's
+    /// payload was not valid UTF-8 (binary framing formats). `data` is
+    /// empty in that case so consumers don't mistake lossy text for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_payload_base64: Option<String>,
+    /// QR version (1-40) reported by the decoder, when available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    /// Error-correction level reported by the decoder, when available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ecc_level: Option<String>,
+    /// The symbol's four corner points (x, y) in frame pixel coordinates,
+    /// so consumers can disambiguate multiple codes in the same frame by
+    /// position instead of just decode order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corners: Option<[(f64, f64); 4]>,
+    /// A cropped, base64-encoded PNG thumbnail of the symbol's bounding box,
+    /// for `save_to_html`'s visual audit report. Only populated alongside
+    /// `corners`, since cropping needs the same decoder-reported quad.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_base64: Option<String>,
+}
+
+impl QrCodeData {
+    /// Decode `raw_payload_base64` back to bytes, for a binary-payload
+    /// symbol that couldn't be represented as UTF-8 text in `data`.
+    pub fn raw_payload(&self) -> Option<Vec<u8>> {
+        use base64::{engine::general_purpose, Engine as _};
+        self.raw_payload_base64
+            .as_deref()
+            .and_then(|b64| general_purpose::STANDARD.decode(b64).ok())
+    }
+
+    fn from_decoded(decoded: DecodedQr, frame_number: u64, chunk_id: usize) -> Self {
+        QrCodeData {
+            frame_number,
+            data: decoded.data,
+            chunk_id,
+            last_frame_number: frame_number,
+            raw_payload_base64: decoded.raw_bytes.map(|bytes| {
+                use base64::{engine::general_purpose, Engine as _};
+                general_purpose::STANDARD.encode(bytes)
+            }),
+            version: decoded.version,
+            ecc_level: decoded.ecc_level,
+            corners: decoded.corners,
+            thumbnail_base64: decoded.thumbnail_base64,
+        }
+    }
+}
+
+/// One decoded QR symbol plus the metadata `rqrr`/`quircs` report for it -
+/// shared scaffolding so `detect_qr_codes_rqrr` and `detect_qr_codes_quircs`
+/// can report position and binary payloads instead of a bare `String`.
+struct DecodedQr {
+    /// UTF-8 payload text; empty when the payload was binary and only
+    /// `raw_bytes` carries its content.
+    data: String,
+    raw_bytes: Option<Vec<u8>>,
+    version: Option<i32>,
+    ecc_level: Option<String>,
+    corners: Option<[(f64, f64); 4]>,
+    thumbnail_base64: Option<String>,
+}
+
+/// One grayscale-converted frame in flight between the decode thread and a
+/// QR-detection worker in `extract_qr_in_memory`'s bounded pipeline.
+struct DecodedFrame {
+    frame_number: u64,
+    width: u32,
+    height: u32,
+    luma_data: Vec<u8>,
 }
 
 #[derive(Debug)]
@@ -25,9 +147,48 @@ pub struct QrExtractionResults {
     pub processing_time_ms: u64,
 }
 
+/// How densely a chunk's frames are decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMode {
+    /// Sample a handful of frames per chunk - fine when the same QR payload
+    /// is displayed for several seconds at a time.
+    Sampled,
+    /// Decode every frame (or the first `max_frames` of them, if set) and
+    /// deduplicate identical payloads, keeping only the first frame each one
+    /// appeared on. For data-transfer videos where every frame carries a
+    /// distinct payload and sampling would throw most of it away.
+    Dense { max_frames: Option<u32> },
+}
+
+/// How a chunk's frames are decoded off disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeBackend {
+    /// Today's default: ffmpeg writes one PNG per frame to a temp dir, which
+    /// `image` then reads back in (`extract_qr_simple_external`). No libav
+    /// dev headers needed at build time, at the cost of disk churn.
+    SubprocessPng,
+    /// No intermediate files and no libav linkage: ffmpeg streams raw rgb24
+    /// frames over its stdout pipe, read directly into an `ImageBuffer`.
+    SubprocessRawVideo,
+    /// Decodes in-process via the `ffmpeg-next`/libav bindings instead of
+    /// spawning an `ffmpeg` binary at all - requires libav dev headers at
+    /// build time.
+    Native,
+}
+
 pub struct QrExtractor {
     thread_count: usize,
     skip_frames: usize,
+    adaptive_sampling: bool,
+    adaptive_threshold: u32,
+    control: Option<Arc<PipelineControl>>,
+    max_tries: u32,
+    extraction_mode: ExtractionMode,
+    force: bool,
+    memory_limit_mb: Option<u64>,
+    timeout_secs: Option<u64>,
+    decode_backend: DecodeBackend,
+    error_handler: Option<Arc<ErrorHandler>>,
 }
 
 impl QrExtractor {
@@ -35,9 +196,84 @@ impl QrExtractor {
         Self {
             thread_count,
             skip_frames,
+            adaptive_sampling: false,
+            adaptive_threshold: 4,
+            control: None,
+            max_tries: 3,
+            extraction_mode: ExtractionMode::Sampled,
+            force: false,
+            memory_limit_mb: None,
+            timeout_secs: None,
+            decode_backend: DecodeBackend::SubprocessPng,
+            error_handler: None,
         }
     }
 
+    /// Attempts allowed per chunk, via the `ChunkBroker`, before it's given
+    /// up on and reported as a failure instead of retried.
+    pub fn with_max_tries(mut self, max_tries: u32) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// Route every retried or abandoned chunk through `handler`'s
+    /// `processing.log`, in addition to the `ProcessingEvent`s already sent
+    /// over `callback`.
+    pub fn with_error_handler(mut self, handler: Arc<ErrorHandler>) -> Self {
+        self.error_handler = Some(handler);
+        self
+    }
+
+    /// Enable adaptive frame sampling: a frame whose 64-bit thumbnail hash is
+    /// within `threshold` Hamming distance of the previously *decoded* frame
+    /// is treated as a duplicate QR and skipped without running the decoder.
+    pub fn with_adaptive_sampling(mut self, enabled: bool, threshold: u32) -> Self {
+        self.adaptive_sampling = enabled;
+        self.adaptive_threshold = threshold;
+        self
+    }
+
+    /// Wire up the TUI's pause/resume back-channel: each worker blocks
+    /// between chunks while the pipeline is paused.
+    pub fn with_control(mut self, control: Arc<PipelineControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Select `Sampled` (fast, a handful of frames per chunk) vs `Dense`
+    /// (every frame, deduplicated) extraction. Defaults to `Sampled`.
+    pub fn with_extraction_mode(mut self, mode: ExtractionMode) -> Self {
+        self.extraction_mode = mode;
+        self
+    }
+
+    /// Ignore any already-written `chunk_NNN.jsonl` files and reprocess
+    /// every chunk from scratch, instead of resuming past them.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Guard rail for corrupt or adversarial inputs: `memory_limit_mb` runs
+    /// the external ffmpeg subprocess under a `systemd-run` cgroup memory
+    /// cap where that tool is available (a plain spawn otherwise), and
+    /// `timeout_secs` kills the child and treats the chunk as empty instead
+    /// of letting a hung decode stall the whole worker pool.
+    pub fn with_resource_limits(mut self, memory_limit_mb: Option<u64>, timeout_secs: Option<u64>) -> Self {
+        self.memory_limit_mb = memory_limit_mb;
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
+    /// Choose how chunk frames are decoded. Defaults to `SubprocessPng`
+    /// (today's behavior); `SubprocessRawVideo` avoids the temp-file churn
+    /// without needing libav, and `Native` needs libav dev headers at build
+    /// time but skips spawning an `ffmpeg` binary at all.
+    pub fn with_decode_backend(mut self, backend: DecodeBackend) -> Self {
+        self.decode_backend = backend;
+        self
+    }
+
     pub fn extract_from_chunks(
         &self,
         chunks: &[VideoChunk],
@@ -54,93 +290,159 @@ impl QrExtractor {
             message: format!("Starting parallel processing of {} chunks...", total_chunks),
         });
 
-        let results = Arc::new(Mutex::new(Vec::new()));
-        let processed_count = Arc::new(Mutex::new(0));
-
-        let chunk_refs: Vec<_> = chunks.iter().collect();
-        let results_ref = Arc::clone(&results);
-        let processed_ref = Arc::clone(&processed_count);
+        let results = Mutex::new(Vec::new());
+        let processed_count = Mutex::new(0usize);
+        let done_manifest = Mutex::new(
+            DoneManifest::load_or_create(output_dir).unwrap_or_default(),
+        );
+
+        // Av1an-style done-tracking: a chunk whose JSONL already exists and
+        // parses cleanly was finished by an earlier, interrupted run - skip
+        // re-extracting it and load its `QrCodeData` back from disk instead.
+        // `--force` (via `with_force`) ignores this and reprocesses everything.
+        let mut to_process: Vec<VideoChunk> = Vec::new();
+        let mut already_done = 0usize;
+
+        for chunk in chunks {
+            let jsonl_path = output_dir.join(format!("chunk_{:03}.jsonl", chunk.id + 1));
+            if !self.force && jsonl_path.exists() {
+                if let Ok(qr_codes) = self.load_chunk_from_jsonl(&jsonl_path) {
+                    let qr_count = qr_codes.len();
+                    results.lock().unwrap().extend(qr_codes);
+                    *processed_count.lock().unwrap() += 1;
+                    already_done += 1;
+                    done_manifest.lock().unwrap().mark_done(output_dir, chunk.id, qr_count).ok();
+
+                    callback(ProcessingEvent::ChunkCompleted {
+                        chunk_id: chunk.id,
+                        qr_codes_found: qr_count,
+                        jsonl_file: format!("chunk_{:03}.jsonl", chunk.id + 1),
+                        duration_ms: 0,
+                        worker_id: 0,
+                    });
+                    continue;
+                }
+                // JSONL exists but didn't parse cleanly - treat it as
+                // incomplete and fall through to reprocessing the chunk.
+            }
+            to_process.push(chunk.clone());
+        }
 
-        // Use a thread-safe callback for parallel processing
-        let callback_ref = Arc::new(callback);
+        if already_done > 0 {
+            callback(ProcessingEvent::Progress {
+                phase: 2,
+                current: already_done,
+                total: total_chunks,
+                message: format!("Resuming: {} of {} chunks already extracted, skipping", already_done, total_chunks),
+            });
+        }
 
-        chunk_refs.into_par_iter().for_each(|chunk| {
-            let cb = Arc::clone(&callback_ref);
-            let chunk_start_time = std::time::Instant::now();
+        let items: Vec<(usize, VideoChunk)> = to_process.iter().cloned().map(|c| (c.id, c)).collect();
+
+        // Dispatched across a `ChunkBroker` instead of a flat rayon
+        // `par_iter`: a chunk whose extraction or JSONL write fails is
+        // retried with backoff instead of just logging an error and moving
+        // on, so a transient ffmpeg hiccup doesn't need a full `--resume` run.
+        let broker = ChunkBroker::new(self.thread_count, self.max_tries);
+
+        let work = |_chunk_id: usize, chunk: &VideoChunk, worker_id: usize| -> Result<Option<(Vec<QrCodeData>, usize)>> {
+            if let Some(control) = &self.control {
+                control.wait_if_paused();
+                // A graceful stop only skips chunks not yet started; any
+                // chunk already past this point finishes and flushes normally.
+                if control.is_stopping() {
+                    return Ok(None);
+                }
+            }
 
-            // Report start of chunk processing
-            cb(ProcessingEvent::ChunkStarted {
+            callback(ProcessingEvent::ChunkStarted {
                 chunk_id: chunk.id,
                 chunk_name: chunk.path.file_name()
                     .and_then(|name| name.to_str())
                     .unwrap_or("unknown")
                     .to_string(),
+                worker_id,
             });
 
-            match self.extract_chunk_to_qr_data(chunk) {
-                Ok(chunk_results) => {
-                    let qr_count = chunk_results.len();
-                    let duration_ms = chunk_start_time.elapsed().as_millis() as u64;
-
-                    // Save chunk results to individual JSONL file in output directory
-                    let jsonl_filename = format!("chunk_{:03}.jsonl", chunk.id + 1);
-                    let jsonl_path = output_dir.join(&jsonl_filename);
-
-                    match self.save_chunk_to_jsonl(&chunk_results, &jsonl_path.to_string_lossy()) {
-                        Ok(_) => {
-                            // Ensure file is fully written and synced
-                            std::thread::sleep(std::time::Duration::from_millis(10));
-
-                            cb(ProcessingEvent::ChunkCompleted {
-                                chunk_id: chunk.id,
-                                qr_codes_found: qr_count,
-                                jsonl_file: jsonl_filename.clone(),
-                                duration_ms,
-                            });
-
-                            // Verify file exists (silent for TUI)
-                            if !jsonl_path.exists() {
-                                cb(ProcessingEvent::Error {
-                                    phase: 2,
-                                    error: format!("JSONL file not found after save: {}", jsonl_filename),
-                                });
-                            }
-                        }
-                        Err(e) => {
-                            cb(ProcessingEvent::Error {
-                                phase: 2,
-                                error: format!("Failed to save JSONL for chunk {}: {}", chunk.id + 1, e),
-                            });
-                        }
-                    }
+            let chunk_start_time = std::time::Instant::now();
+            let chunk_results = self.extract_chunk_to_qr_data(chunk)?;
+            let qr_count = chunk_results.len();
+            let duration_ms = chunk_start_time.elapsed().as_millis() as u64;
 
-                    // Add to global results
-                    {
-                        let mut results_guard = results_ref.lock().unwrap();
-                        results_guard.extend(chunk_results);
-                    }
+            // Save chunk results to individual JSONL file in output directory
+            let jsonl_filename = format!("chunk_{:03}.jsonl", chunk.id + 1);
+            let jsonl_path = output_dir.join(&jsonl_filename);
+            self.save_chunk_to_jsonl(&chunk_results, &jsonl_path.to_string_lossy())?;
 
-                    let current = {
-                        let mut count = processed_ref.lock().unwrap();
-                        *count += 1;
-                        *count
-                    };
-
-                    cb(ProcessingEvent::Progress {
-                        phase: 2,
-                        current,
-                        total: total_chunks,
-                        message: format!("Completed {} of {} chunks ({} QR codes total)", current, total_chunks, qr_count),
-                    });
-                }
-                Err(e) => {
-                    cb(ProcessingEvent::Error {
-                        phase: 2,
-                        error: format!("Failed to process chunk {}: {}", chunk.id + 1, e),
-                    });
-                }
+            // Ensure file is fully written and synced
+            std::thread::sleep(std::time::Duration::from_millis(10));
+
+            if !jsonl_path.exists() {
+                return Err(anyhow!("JSONL file not found after save: {}", jsonl_filename));
             }
-        });
+
+            // Record completion in done.json so a later --resume skips this
+            // chunk instead of re-decoding it.
+            done_manifest.lock().unwrap().mark_done(output_dir, chunk.id, qr_count)?;
+
+            callback(ProcessingEvent::ChunkCompleted {
+                chunk_id: chunk.id,
+                qr_codes_found: qr_count,
+                jsonl_file: jsonl_filename.clone(),
+                duration_ms,
+                worker_id,
+            });
+
+            Ok(Some((chunk_results, qr_count)))
+        };
+
+        let on_success = |_chunk_id: usize, _chunk: &VideoChunk, result: Option<(Vec<QrCodeData>, usize)>| {
+            let Some((chunk_results, qr_count)) = result else {
+                return; // skipped: pipeline was stopping
+            };
+
+            {
+                let mut results_guard = results.lock().unwrap();
+                results_guard.extend(chunk_results);
+            }
+
+            let current = {
+                let mut count = processed_count.lock().unwrap();
+                *count += 1;
+                *count
+            };
+
+            callback(ProcessingEvent::Progress {
+                phase: 2,
+                current,
+                total: total_chunks,
+                message: format!("Completed {} of {} chunks ({} QR codes total)", current, total_chunks, qr_count),
+            });
+        };
+
+        let on_failure = |chunk_id: usize, _chunk: &VideoChunk, error: &str, attempts: u32| {
+            if let Some(handler) = &self.error_handler {
+                handler.handle_ffmpeg_error(chunk_id, "extract", error);
+            }
+            callback(ProcessingEvent::Error {
+                phase: 2,
+                error: format!("Chunk {} failed after {} attempt(s): {}", chunk_id + 1, attempts, error),
+            });
+        };
+
+        let on_retry = |chunk_id: usize, _chunk: &VideoChunk, attempt: u32, max_tries: u32, reason: &str| {
+            if let Some(handler) = &self.error_handler {
+                handler.handle_ffmpeg_error(chunk_id, "extract", reason);
+            }
+            callback(ProcessingEvent::ChunkRetry {
+                chunk_id,
+                attempt,
+                max_tries,
+                reason: reason.to_string(),
+            });
+        };
+
+        broker.run(items, work, on_success, on_failure, on_retry);
 
         // CRITICAL: Wait for all JSONL files to be fully written and verify they exist
         std::thread::sleep(std::time::Duration::from_millis(100)); // Allow file system sync
@@ -160,6 +462,19 @@ impl QrExtractor {
             message: format!("Verified {}/{} JSONL files written to disk", verified_chunks, total_chunks),
         });
 
+        // Merge and sort every chunk's JSONL into one combined file, same as
+        // each individual worker's output but ordered by frame_number across
+        // chunk boundaries - `QrExtractionResults.qr_codes` below already has
+        // the in-memory equivalent, but this gives callers an on-disk copy
+        // without re-running Phase 2.
+        let combined_path = output_dir.join("combined.jsonl");
+        if let Err(e) = self.combine_chunk_jsonl_files(total_chunks, output_dir, &combined_path) {
+            callback(ProcessingEvent::Error {
+                phase: 2,
+                error: format!("Failed to write combined JSONL: {}", e),
+            });
+        }
+
         let final_results = {
             let results_guard = results.lock().unwrap();
             results_guard.clone()
@@ -188,9 +503,32 @@ impl QrExtractor {
     }
 
     fn extract_chunk_to_qr_data(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
-        // Use external FFmpeg + zbar approach to avoid hanging
-        let qr_results = self.extract_qr_external(&chunk)?;
-        Ok(qr_results)
+        match self.decode_backend {
+            // Use external FFmpeg + zbar approach to avoid hanging
+            DecodeBackend::SubprocessPng => self.extract_qr_external(chunk),
+            DecodeBackend::SubprocessRawVideo => self.extract_qr_subprocess_rawvideo(chunk),
+            DecodeBackend::Native => self.extract_qr_native_ffmpeg(chunk),
+        }
+    }
+
+    /// Cheap perceptual fingerprint for adaptive sampling: downscale to an
+    /// 8x8 grayscale thumbnail and set one bit per pixel above the
+    /// thumbnail's mean brightness, yielding a 64-bit average-hash that two
+    /// visually-identical frames hash to (near-)identically regardless of
+    /// compression noise.
+    fn thumbnail_hash(path: &std::path::Path) -> Option<u64> {
+        let img = image::open(path).ok()?;
+        let thumbnail = image::imageops::thumbnail(&img.to_luma8(), 8, 8);
+        let pixels: Vec<u32> = thumbnail.pixels().map(|p| p.0[0] as u32).collect();
+        let mean = pixels.iter().sum::<u32>() / pixels.len() as u32;
+
+        let mut hash = 0u64;
+        for (i, &pixel) in pixels.iter().enumerate() {
+            if pixel > mean {
+                hash |= 1 << i;
+            }
+        }
+        Some(hash)
     }
 
     fn extract_qr_external(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
@@ -198,24 +536,107 @@ impl QrExtractor {
         self.extract_qr_simple_external(chunk)
     }
 
-    fn extract_qr_simple_external(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
+    /// Whether `systemd-run` is on PATH and usable, so `ffmpeg_command` can
+    /// fall back to a plain spawn on platforms (e.g. macOS, containers
+    /// without systemd) where it isn't.
+    fn systemd_run_available() -> bool {
+        use std::process::{Command, Stdio};
+        Command::new("systemd-run")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+
+    /// Build the ffmpeg invocation, wrapped in `systemd-run --scope --user
+    /// -p MemoryMax=<N>M --` when `memory_limit_mb` is set and the tool is
+    /// available - inspired by render_video's systemd-run sandbox wrapper.
+    fn ffmpeg_command(&self, args: &[String]) -> std::process::Command {
         use std::process::Command;
+
+        if let Some(mb) = self.memory_limit_mb {
+            if Self::systemd_run_available() {
+                let mut command = Command::new("systemd-run");
+                command.args(["--scope", "--user", "-p", &format!("MemoryMax={}M", mb), "--", "ffmpeg"]);
+                command.args(args);
+                return command;
+            }
+        }
+
+        let mut command = Command::new("ffmpeg");
+        command.args(args);
+        command
+    }
+
+    /// Run `ffmpeg_command(args)` to completion, enforcing `timeout_secs` by
+    /// polling `try_wait` instead of blocking on `Command::output()` - a
+    /// hung or adversarial input gets killed and reported as an error
+    /// rather than stalling the chunk's worker thread indefinitely.
+    fn run_ffmpeg_with_timeout(&self, args: &[String]) -> Result<std::process::Output> {
+        use std::process::Stdio;
+        use std::time::{Duration, Instant};
+
+        let mut command = self.ffmpeg_command(args);
+        command.stdout(Stdio::piped()).stderr(Stdio::null());
+        let mut child = command.spawn()?;
+
+        let timeout = self.timeout_secs.map(Duration::from_secs);
+        let start = Instant::now();
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                let mut stdout = Vec::new();
+                if let Some(mut out) = child.stdout.take() {
+                    out.read_to_end(&mut stdout).ok();
+                }
+                return Ok(std::process::Output { status, stdout, stderr: Vec::new() });
+            }
+
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(anyhow!("ffmpeg timed out after {:?} - likely a corrupt or adversarial input", timeout));
+                }
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    fn extract_qr_simple_external(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
         use std::fs;
 
         let temp_dir = format!("temp_frames_{}", chunk.id);
         fs::create_dir_all(&temp_dir)?;
 
+        // Dense mode decodes every frame (so the %06d pattern has room to
+        // grow), Sampled keeps the old 1-frame-every-2-seconds, 5-frame cap.
+        let frame_pattern = match self.extraction_mode {
+            ExtractionMode::Sampled => format!("{}/frame_%03d.png", temp_dir),
+            ExtractionMode::Dense { .. } => format!("{}/frame_%06d.png", temp_dir),
+        };
+
+        let mut args: Vec<String> = vec!["-i".to_string(), chunk.path.to_string_lossy().to_string()];
+        match self.extraction_mode {
+            ExtractionMode::Sampled => {
+                args.extend([
+                    "-vf".to_string(), "fps=0.5".to_string(), // Sample 1 frame every 2 seconds
+                    "-frames:v".to_string(), "5".to_string(), // Limit to 5 frames per chunk
+                ]);
+            }
+            ExtractionMode::Dense { max_frames } => {
+                if let Some(max_frames) = max_frames {
+                    args.extend(["-frames:v".to_string(), max_frames.to_string()]);
+                }
+            }
+        }
+        args.extend(["-y".to_string(), "-loglevel".to_string(), "quiet".to_string(), frame_pattern]);
+
         // Extract frames using external ffmpeg with fast settings
-        let extract_cmd = Command::new("ffmpeg")
-            .args([
-                "-i", &chunk.path.to_string_lossy(),
-                "-vf", "fps=0.5", // Sample 1 frame every 2 seconds
-                "-frames:v", "5", // Limit to 5 frames per chunk
-                "-y",
-                "-loglevel", "quiet",
-                &format!("{}/frame_%03d.png", temp_dir)
-            ])
-            .output();
+        let extract_cmd = self.run_ffmpeg_with_timeout(&args);
 
         match extract_cmd {
             Ok(output) if output.status.success() => {
@@ -231,29 +652,80 @@ impl QrExtractor {
             }
         }
 
-        // Process frames immediately and clean up as we go
+        // Process frames in capture order (frame_%03d.png sorts lexicographically
+        // the same as chronologically) and clean up as we go
+        let mut frame_paths: Vec<_> = fs::read_dir(&temp_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("png"))
+                    .collect()
+            })
+            .unwrap_or_else(|_| Vec::new());
+        frame_paths.sort();
+
         let mut qr_results = Vec::new();
-        if let Ok(entries) = fs::read_dir(&temp_dir) {
-            for (frame_idx, entry) in entries.enumerate() {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) == Some("png") {
-                        // Process QR codes from this frame
-                        if let Ok(qr_codes) = self.extract_qr_from_image(&path) {
-                            for qr_data in qr_codes {
-                                qr_results.push(QrCodeData {
-                                    frame_number: frame_idx as u64,
-                                    data: qr_data,
-                                    chunk_id: chunk.id,
-                                });
+        let mut previous_hash: Option<u64> = None;
+        // Dense mode only: frame rate and capture/display rate rarely match,
+        // so the same payload decodes on a run of consecutive frames - keep
+        // just the first frame each distinct payload appeared on.
+        // Maps a payload already pushed into `qr_results` to its index, so a
+        // repeat sighting in Dense mode can extend that entry's dwell window
+        // (`last_frame_number`) instead of being dropped or re-pushed.
+        let mut seen_payloads: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+        for (frame_idx, path) in frame_paths.into_iter().enumerate() {
+            let is_duplicate = if self.adaptive_sampling && frame_idx > 0 {
+                match (previous_hash, Self::thumbnail_hash(&path)) {
+                    (Some(prev), Some(current)) => {
+                        let duplicate = (prev ^ current).count_ones() <= self.adaptive_threshold;
+                        if !duplicate {
+                            previous_hash = Some(current);
+                        }
+                        duplicate
+                    }
+                    (_, current) => {
+                        previous_hash = current;
+                        false
+                    }
+                }
+            } else {
+                if self.adaptive_sampling {
+                    previous_hash = Self::thumbnail_hash(&path);
+                }
+                false
+            };
+
+            if !is_duplicate {
+                // Process QR codes from this frame
+                if let Ok(qr_codes) = self.extract_qr_from_image(&path) {
+                    for qr_data in qr_codes {
+                        if matches!(self.extraction_mode, ExtractionMode::Dense { .. }) {
+                            if let Some(&existing_idx) = seen_payloads.get(&qr_data) {
+                                qr_results[existing_idx].last_frame_number = frame_idx as u64;
+                                continue; // still on screen - extend its dwell window
                             }
+                            seen_payloads.insert(qr_data.clone(), qr_results.len());
                         }
 
-                        // ✅ Delete frame immediately after processing
-                        fs::remove_file(&path).ok();
+                        qr_results.push(QrCodeData {
+                            frame_number: frame_idx as u64,
+                            data: qr_data,
+                            chunk_id: chunk.id,
+                            last_frame_number: frame_idx as u64,
+                            raw_payload_base64: None,
+                            version: None,
+                            ecc_level: None,
+                            corners: None,
+                            thumbnail_base64: None,
+                        });
                     }
                 }
             }
+
+            // ✅ Delete frame immediately after processing (or skipping)
+            fs::remove_file(&path).ok();
         }
 
         // ✅ Clean up temp directory
@@ -263,20 +735,24 @@ impl QrExtractor {
     }
 
     fn extract_qr_streaming(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
-        use std::process::{Command, Stdio};
         use std::io::BufReader;
+        use std::process::Stdio;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let args: Vec<String> = [
+            "-i", &chunk.path.to_string_lossy(),
+            "-vf", "fps=1", // Sample 1 frame per second for speed
+            "-f", "image2pipe",
+            "-vcodec", "png",
+            "-frames:v", "10", // Limit frames for memory efficiency
+            "-loglevel", "quiet",
+            "pipe:1",
+        ].map(|s| s.to_string()).to_vec();
 
         // Extract frames to stdout and process immediately (no temp files)
-        let mut cmd = Command::new("ffmpeg")
-            .args([
-                "-i", &chunk.path.to_string_lossy(),
-                "-vf", "fps=1", // Sample 1 frame per second for speed
-                "-f", "image2pipe",
-                "-vcodec", "png",
-                "-frames:v", "10", // Limit frames for memory efficiency
-                "-loglevel", "quiet",
-                "pipe:1"
-            ])
+        let mut command = self.ffmpeg_command(&args);
+        let mut cmd = command
             .stdout(Stdio::piped())
             .stderr(Stdio::null())
             .spawn()?;
@@ -284,6 +760,26 @@ impl QrExtractor {
         let stdout = cmd.stdout.take().ok_or_else(|| anyhow!("Failed to capture stdout"))?;
         let mut reader = BufReader::new(stdout);
 
+        // A stalled/adversarial stream would otherwise block the read loop
+        // below forever - kill the child after `timeout_secs` instead of
+        // stalling the whole worker pool. A kill between frames reports this
+        // chunk as empty; one mid-frame returns a truncated-stream error.
+        let cmd = Arc::new(Mutex::new(cmd));
+        let timed_out = Arc::new(AtomicBool::new(false));
+        if let Some(timeout_secs) = self.timeout_secs {
+            let watchdog_cmd = Arc::clone(&cmd);
+            let watchdog_timed_out = Arc::clone(&timed_out);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_secs(timeout_secs));
+                if let Ok(mut child) = watchdog_cmd.lock() {
+                    if matches!(child.try_wait(), Ok(None)) {
+                        let _ = child.kill();
+                        watchdog_timed_out.store(true, Ordering::SeqCst);
+                    }
+                }
+            });
+        }
+
         let mut qr_results = Vec::new();
         let mut frame_number = 0u64;
         let mut png_buffer = Vec::new();
@@ -307,35 +803,55 @@ impl QrExtractor {
             }
         }
 
-        let _ = cmd.wait(); // Wait for FFmpeg to finish
+        let _ = cmd.lock().unwrap().wait(); // Wait for FFmpeg to finish
+
+        if timed_out.load(Ordering::SeqCst) {
+            return Ok(Vec::new());
+        }
 
         Ok(qr_results)
     }
 
+    /// Read exactly one PNG frame from the `image2pipe` stream: the 8-byte
+    /// signature, then every chunk (4-byte big-endian length + 4-byte type +
+    /// `length` data bytes + 4-byte CRC) up to and including `IEND`. Reading
+    /// each field's exact size via `read_exact` - rather than one fixed-size
+    /// guess - means we never over-read into the next frame, so no residual
+    /// buffer needs to be carried between calls for frame boundaries to stay
+    /// aligned; `reader` simply picks up where the previous call left off.
+    /// Returns `Ok(false)` once the pipe is exhausted between frames.
     fn read_png_frame(&self, reader: &mut BufReader<std::process::ChildStdout>, buffer: &mut Vec<u8>) -> Result<bool> {
         buffer.clear();
 
-        // PNG signature: 89 50 4E 47 0D 0A 1A 0A
-        let png_signature = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
         let mut signature_buffer = [0u8; 8];
 
-        // Try to read PNG signature
-        match reader.read_exact(&mut signature_buffer) {
-            Ok(_) => {
-                if signature_buffer == png_signature {
-                    buffer.extend_from_slice(&signature_buffer);
-                    // Read rest of PNG file (simplified - would need proper PNG parsing)
-                    let mut temp_buffer = vec![0u8; 1024 * 1024]; // 1MB buffer
-                    if let Ok(bytes_read) = reader.read(&mut temp_buffer) {
-                        buffer.extend_from_slice(&temp_buffer[..bytes_read]);
-                        return Ok(true);
-                    }
-                }
-            }
-            Err(_) => return Ok(false), // End of stream
+        if reader.read_exact(&mut signature_buffer).is_err() {
+            return Ok(false); // End of stream, between frames
+        }
+        if signature_buffer != PNG_SIGNATURE {
+            return Err(anyhow!("Expected PNG signature in image2pipe stream, got {:02x?}", signature_buffer));
         }
+        buffer.extend_from_slice(&signature_buffer);
+
+        loop {
+            let mut chunk_header = [0u8; 8]; // 4-byte length + 4-byte type
+            reader.read_exact(&mut chunk_header)
+                .map_err(|e| anyhow!("Truncated PNG stream while reading chunk header: {}", e))?;
+            buffer.extend_from_slice(&chunk_header);
+
+            let length = u32::from_be_bytes(chunk_header[0..4].try_into().unwrap()) as usize;
+            let chunk_type = &chunk_header[4..8];
 
-        Ok(false)
+            let mut data_and_crc = vec![0u8; length + 4]; // chunk data + CRC
+            reader.read_exact(&mut data_and_crc)
+                .map_err(|e| anyhow!("Truncated PNG stream while reading chunk body: {}", e))?;
+            buffer.extend_from_slice(&data_and_crc);
+
+            if chunk_type == b"IEND" {
+                return Ok(true);
+            }
+        }
     }
 
     fn extract_qr_from_png_data(&self, png_data: &[u8], frame_number: u64, chunk_id: usize) -> Result<Vec<QrCodeData>> {
@@ -357,6 +873,12 @@ impl QrExtractor {
                     frame_number,
                     data: content,
                     chunk_id,
+                    last_frame_number: frame_number,
+                    raw_payload_base64: None,
+                    version: None,
+                    ecc_level: None,
+                    corners: None,
+                    thumbnail_base64: None,
                 });
             }
         }
@@ -365,11 +887,75 @@ impl QrExtractor {
         Ok(qr_codes)
     }
 
+    /// Decode `chunk` in-process on one thread and run QR detection on a
+    /// pool of `thread_count` workers, handing frames across as plain owned
+    /// `DecodedFrame`s through a bounded channel.
     fn extract_qr_in_memory(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
+        // Bounded at a handful of frames: the decode thread blocks on send()
+        // once it's full, so RAM stays flat regardless of chunk length
+        // instead of growing with a `Vec<QrCodeData>` accumulated up front.
+        const FRAME_QUEUE_CAPACITY: usize = 8;
+
+        let (frame_tx, frame_rx) = crossbeam_channel::bounded::<DecodedFrame>(FRAME_QUEUE_CAPACITY);
+        let chunk_id = chunk.id;
+        let chunk_path = chunk.path.clone();
+        let skip_frames = self.skip_frames;
+        let results = Mutex::new(Vec::new());
+
+        let decode_result: Result<()> = std::thread::scope(|scope| {
+            let decode_handle = scope.spawn(move || {
+                Self::decode_frames_into_queue(&chunk_path, skip_frames, frame_tx)
+            });
+
+            // Detection runs on its own pool so decode and QR scanning
+            // overlap instead of alternating sequentially per frame.
+            for _ in 0..self.thread_count.max(1) {
+                let frame_rx = frame_rx.clone();
+                let results = &results;
+                scope.spawn(move || {
+                    while let Ok(decoded) = frame_rx.recv() {
+                        if let Ok(qr_codes) = self.detect_qr_from_luma(&decoded.luma_data, decoded.width, decoded.height) {
+                            let mut guard = results.lock().unwrap();
+                            for data in qr_codes {
+                                guard.push(QrCodeData {
+                                    frame_number: decoded.frame_number,
+                                    data,
+                                    chunk_id,
+                                    last_frame_number: decoded.frame_number,
+                                    raw_payload_base64: None,
+                                    version: None,
+                                    ecc_level: None,
+                                    corners: None,
+                                    thumbnail_base64: None,
+                                });
+                            }
+                        }
+                    }
+                });
+            }
+
+            decode_handle.join().map_err(|_| anyhow!("Decode thread panicked"))?
+        });
+        decode_result?;
+
+        let mut qr_results = results.into_inner().unwrap();
+        qr_results.sort_by_key(|qr| qr.frame_number);
+        Ok(qr_results)
+    }
+
+    /// Runs on its own thread: decodes every packet, converts accepted
+    /// frames (per `skip_frames`) to grayscale, and pushes them onto
+    /// `frame_tx`. Dropping `frame_tx` on return is what lets the worker
+    /// pool's `recv()` loops end once decoding is done.
+    fn decode_frames_into_queue(
+        chunk_path: &std::path::Path,
+        skip_frames: usize,
+        frame_tx: crossbeam_channel::Sender<DecodedFrame>,
+    ) -> Result<()> {
         ffmpeg::init().map_err(|e| anyhow!("Failed to initialize FFmpeg: {}", e))?;
         ffmpeg::log::set_level(ffmpeg::log::Level::Quiet);
 
-        let mut ictx = ffmpeg::format::input(&chunk.path)?;
+        let mut ictx = ffmpeg::format::input(chunk_path)?;
         let input = ictx.streams().best(ffmpeg::media::Type::Video)
             .ok_or(anyhow!("No video stream found"))?;
         let video_stream_index = input.index();
@@ -377,132 +963,74 @@ impl QrExtractor {
         let context_decoder = ffmpeg::codec::context::Context::from_parameters(input.parameters())?;
         let mut decoder = context_decoder.decoder().video()?;
 
-        // Calculate total frames for progress reporting
-        let duration = input.duration() as f64 / ffmpeg::ffi::AV_TIME_BASE as f64;
-        let fps = input.avg_frame_rate();
-        let estimated_frames = if fps.denominator() > 0 {
-            (duration * fps.numerator() as f64 / fps.denominator() as f64) as u64
-        } else {
-            1000 // Fallback estimate
-        };
-
         let mut frame_count = 0u64;
-        let mut qr_results = Vec::new();
-
-        // Starting frame processing (silent for TUI)
+        let mut scaler: Option<ffmpeg::software::scaling::Context> = None;
 
-        // Process packets from the video stream
         for (stream, packet) in ictx.packets() {
             if stream.index() == video_stream_index {
                 decoder.send_packet(&packet)?;
-                self.receive_and_process_frames(&mut decoder, &mut frame_count, &mut qr_results, chunk.id, estimated_frames)?;
+                Self::drain_decoded_frames(&mut decoder, &mut scaler, &mut frame_count, skip_frames, &frame_tx)?;
             }
         }
 
-        // Flush remaining frames
         decoder.send_eof()?;
-        self.receive_and_process_frames(&mut decoder, &mut frame_count, &mut qr_results, chunk.id, estimated_frames)?;
+        Self::drain_decoded_frames(&mut decoder, &mut scaler, &mut frame_count, skip_frames, &frame_tx)?;
 
-        // Completed processing (silent for TUI)
-
-        Ok(qr_results)
+        Ok(())
     }
 
-    fn receive_and_process_frames(
-        &self,
+    fn drain_decoded_frames(
         decoder: &mut ffmpeg::decoder::Video,
+        scaler: &mut Option<ffmpeg::software::scaling::Context>,
         frame_count: &mut u64,
-        qr_results: &mut Vec<QrCodeData>,
-        chunk_id: usize,
-        estimated_frames: u64,
+        skip_frames: usize,
+        frame_tx: &crossbeam_channel::Sender<DecodedFrame>,
     ) -> Result<()> {
         let mut frame = ffmpeg::frame::Video::empty();
 
-        // Create scaler once outside the loop for efficiency
-        let mut scaler: Option<ffmpeg::software::scaling::Context> = None;
-
         while decoder.receive_frame(&mut frame).is_ok() {
             *frame_count += 1;
 
-            // Progress reporting every 100 frames (silent for clean TUI)
-            if *frame_count % 100 == 0 {
-                // Frame progress tracking (could be added as event if needed)
-            }
-
-            // Skip frames based on skip_frames setting
-            if *frame_count % (self.skip_frames as u64 + 1) != 0 {
+            if *frame_count % (skip_frames as u64 + 1) != 0 {
                 continue; // ✅ Frame discarded immediately without processing
             }
 
-            // Process frame immediately and discard - no accumulation
-            match self.process_frame_immediate(&frame, &mut scaler, *frame_count, chunk_id) {
-                Ok(qr_codes) => {
-                    // Only store QR code text data, not frame data
-                    qr_results.extend(qr_codes);
-                    // ✅ Frame data is discarded here - only QR text kept
-                }
-                Err(_) => {
-                    // ✅ Failed frame is discarded immediately
-                }
+            if scaler.is_none() {
+                *scaler = Some(ffmpeg::software::scaling::context::Context::get(
+                    frame.format(),
+                    frame.width(),
+                    frame.height(),
+                    ffmpeg::format::Pixel::RGB24,
+                    frame.width(),
+                    frame.height(),
+                    ffmpeg::software::scaling::flag::Flags::BILINEAR,
+                )?);
             }
-            // ✅ frame goes out of scope here - memory freed
-        }
-
-        Ok(())
-    }
 
-    /// Process single frame immediately and return QR data (frame is discarded)
-    fn process_frame_immediate(
-        &self,
-        frame: &ffmpeg::frame::Video,
-        scaler: &mut Option<ffmpeg::software::scaling::Context>,
-        frame_number: u64,
-        chunk_id: usize,
-    ) -> Result<Vec<QrCodeData>> {
-        // Reuse scaler context to avoid recreation overhead
-        if scaler.is_none() {
-            *scaler = Some(ffmpeg::software::scaling::context::Context::get(
-                frame.format(),
-                frame.width(),
-                frame.height(),
-                ffmpeg::format::Pixel::RGB24,
-                frame.width(),
-                frame.height(),
-                ffmpeg::software::scaling::flag::Flags::BILINEAR,
-            )?);
-        }
-
-        let scaler_ref = scaler.as_mut().unwrap();
-        let mut rgb_frame = ffmpeg::frame::Video::empty();
-        scaler_ref.run(frame, &mut rgb_frame)?;
+            let scaler_ref = scaler.as_mut().unwrap();
+            let mut rgb_frame = ffmpeg::frame::Video::empty();
+            scaler_ref.run(&frame, &mut rgb_frame)?;
 
-        // Process QR codes directly from frame data (no copying)
-        let qr_codes = self.detect_qr_codes_from_frame(&rgb_frame)?;
+            let (width, height, luma_data) = Self::rgb_frame_to_luma(&rgb_frame);
 
-        // Convert to QrCodeData immediately
-        let mut results = Vec::new();
-        for qr_data in qr_codes {
-            results.push(QrCodeData {
-                frame_number,
-                data: qr_data,
-                chunk_id,
-            });
+            // Blocks once the queue is full - this is the backpressure that
+            // keeps memory bounded regardless of how long the chunk is.
+            if frame_tx.send(DecodedFrame { frame_number: *frame_count, width, height, luma_data }).is_err() {
+                break; // every receiver dropped - detection side gave up
+            }
         }
 
-        // ✅ rgb_frame goes out of scope here - memory freed immediately
-        Ok(results)
+        Ok(())
     }
 
-    /// Detect QR codes directly from FFmpeg frame (no ImageBuffer allocation)
-    fn detect_qr_codes_from_frame(&self, rgb_frame: &ffmpeg::frame::Video) -> Result<Vec<String>> {
-        let width = rgb_frame.width() as u32;
-        let height = rgb_frame.height() as u32;
+    /// Convert an RGB24 ffmpeg frame to a flat grayscale buffer (0.299/0.587/0.114 luminance weights).
+    fn rgb_frame_to_luma(rgb_frame: &ffmpeg::frame::Video) -> (u32, u32, Vec<u8>) {
+        let width = rgb_frame.width();
+        let height = rgb_frame.height();
         let data = rgb_frame.data(0);
         let linesize = rgb_frame.stride(0);
 
-        // Convert RGB to grayscale on-the-fly for QR detection (no buffer allocation)
         let mut luma_data = Vec::with_capacity((width * height) as usize);
-
         for y in 0..height {
             let row_start = y as usize * linesize;
             for x in 0..width {
@@ -511,15 +1039,13 @@ impl QrExtractor {
                     let r = data[pixel_start] as f32;
                     let g = data[pixel_start + 1] as f32;
                     let b = data[pixel_start + 2] as f32;
-                    // Luminance formula: 0.299*R + 0.587*G + 0.114*B
                     let luma = (0.299 * r + 0.587 * g + 0.114 * b) as u8;
                     luma_data.push(luma);
                 }
             }
         }
 
-        // Direct QR detection from luma data
-        self.detect_qr_from_luma(&luma_data, width, height)
+        (width, height, luma_data)
     }
 
     /// Detect QR codes directly from luma data (minimal memory footprint)
@@ -594,10 +1120,11 @@ impl QrExtractor {
         Ok(qr_codes)
     }
 
-    // Keep the old FFmpeg implementation commented out
-    #[allow(dead_code)]
-    fn _extract_chunk_to_qr_data_ffmpeg(&self, _chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
-        /*
+    /// `DecodeBackend::Native`: decode in-process via the `ffmpeg-next`
+    /// bindings instead of spawning an `ffmpeg` binary. Needs libav dev
+    /// headers at build time, but skips the subprocess and its pipe/temp
+    /// files entirely.
+    fn extract_qr_native_ffmpeg(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
         let mut ictx = ffmpeg::format::input(&chunk.path)
             .map_err(|e| anyhow!("Failed to open chunk file: {}", e))?;
 
@@ -616,6 +1143,7 @@ impl QrExtractor {
 
         let mut qr_results = Vec::new();
         let mut frame_number = 0u64;
+        let skip = self.skip_frames.max(1) as u64;
 
         let mut scaler = ffmpeg::software::scaling::Context::get(
             decoder.format(),
@@ -635,7 +1163,7 @@ impl QrExtractor {
 
                 let mut decoded = ffmpeg::frame::Video::empty();
                 while decoder.receive_frame(&mut decoded).is_ok() {
-                    if frame_number % self.skip_frames as u64 == 0 {
+                    if frame_number % skip == 0 {
                         if let Ok(qr_data) = self.extract_qr_from_frame(&mut scaler, &decoded, frame_number, chunk.id) {
                             qr_results.extend(qr_data);
                         }
@@ -648,7 +1176,7 @@ impl QrExtractor {
         decoder.send_eof().ok();
         let mut decoded = ffmpeg::frame::Video::empty();
         while decoder.receive_frame(&mut decoded).is_ok() {
-            if frame_number % self.skip_frames as u64 == 0 {
+            if frame_number % skip == 0 {
                 if let Ok(qr_data) = self.extract_qr_from_frame(&mut scaler, &decoded, frame_number, chunk.id) {
                     qr_results.extend(qr_data);
                 }
@@ -657,48 +1185,124 @@ impl QrExtractor {
         }
 
         Ok(qr_results)
-        */
-        todo!("FFmpeg implementation")
     }
 
-    fn extract_qr_from_frame(
+    /// `DecodeBackend::SubprocessRawVideo`: probe the chunk's dimensions
+    /// with `ffprobe`, then stream raw rgb24 frames off an `ffmpeg ... -f
+    /// rawvideo -pix_fmt rgb24 pipe:1` subprocess - no libav linkage and no
+    /// intermediate PNG files on disk, unlike the other two backends.
+    fn extract_qr_subprocess_rawvideo(&self, chunk: &VideoChunk) -> Result<Vec<QrCodeData>> {
+        use std::process::{Command, Stdio};
+
+        let chunk_path = chunk.path.to_str()
+            .ok_or_else(|| anyhow!("Chunk path {} is not valid UTF-8", chunk.path.display()))?;
+
+        let probe = Command::new("ffprobe")
+            .args([
+                "-v", "error",
+                "-select_streams", "v:0",
+                "-show_entries", "stream=width,height",
+                "-of", "csv=p=0:s=,",
+                chunk_path,
+            ])
+            .output()?;
+        if !probe.status.success() {
+            return Err(anyhow!(
+                "ffprobe failed for {}: {}",
+                chunk.path.display(),
+                String::from_utf8_lossy(&probe.stderr)
+            ));
+        }
+
+        let probe_text = String::from_utf8_lossy(&probe.stdout);
+        let mut fields = probe_text.trim().split(',');
+        let width: u32 = fields.next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| anyhow!("ffprobe did not report a width for {}", chunk.path.display()))?;
+        let height: u32 = fields.next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| anyhow!("ffprobe did not report a height for {}", chunk.path.display()))?;
+
+        let skip = self.skip_frames.max(1);
+        let args: Vec<String> = vec![
+            "-i".to_string(), chunk_path.to_string(),
+            "-f".to_string(), "rawvideo".to_string(),
+            "-pix_fmt".to_string(), "rgb24".to_string(),
+            "-vf".to_string(), format!("select=not(mod(n\\,{}))", skip),
+            "-loglevel".to_string(), "quiet".to_string(),
+            "pipe:1".to_string(),
+        ];
+
+        let mut command = self.ffmpeg_command(&args);
+        command.stdout(Stdio::piped()).stderr(Stdio::null());
+        let mut child = command.spawn()?;
+        let mut stdout = child.stdout.take().ok_or_else(|| anyhow!("Failed to capture ffmpeg stdout"))?;
+
+        let frame_size = width as usize * height as usize * 3;
+        let mut buffer = vec![0u8; frame_size];
+        let mut qr_results = Vec::new();
+        let mut frame_number = 0u64;
+
+        loop {
+            match stdout.read_exact(&mut buffer) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(anyhow!("Failed reading raw frame {} from ffmpeg: {}", frame_number, e)),
+            }
+
+            if let Some(img) = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw(width, height, buffer.clone()) {
+                if let Ok(codes) = self.detect_qr_codes_rqrr(&img) {
+                    for code in codes {
+                        qr_results.push(QrCodeData::from_decoded(code, frame_number, chunk.id));
+                    }
+                }
+                if let Ok(codes) = self.detect_qr_codes_quircs(&img) {
+                    for code in codes {
+                        qr_results.push(QrCodeData::from_decoded(code, frame_number, chunk.id));
+                    }
+                }
+            }
+
+            frame_number += 1;
+        }
+
+        child.wait().ok();
+        Ok(qr_results)
+    }
+
+    /// Shared by the decoded-file extraction path above and by
+    /// `live_capture`'s V4L2 streaming loop: scale `frame` to RGB24 and run
+    /// QR detection on it.
+    pub(crate) fn extract_qr_from_frame(
         &self,
         scaler: &mut ffmpeg::software::scaling::Context,
         frame: &ffmpeg::frame::Video,
         frame_number: u64,
         chunk_id: usize,
-    ) -> Result<Vec<QrCodeData>> {
+    ) -> std::result::Result<Vec<QrCodeData>, QrExtractError> {
         let mut rgb_frame = ffmpeg::frame::Video::empty();
         scaler.run(frame, &mut rgb_frame)
-            .map_err(|e| anyhow!("Failed to scale frame: {}", e))?;
+            .map_err(|e| QrExtractError::Scale(e.to_string()))?;
 
         let width = rgb_frame.width() as u32;
         let height = rgb_frame.height() as u32;
         let data = rgb_frame.data(0);
 
         let img: ImageBuffer<Rgb<u8>, Vec<u8>> = ImageBuffer::from_raw(width, height, data.to_vec())
-            .ok_or_else(|| anyhow!("Failed to create image buffer"))?;
+            .ok_or(QrExtractError::FrameBuffer)?;
 
         let mut qr_results = Vec::new();
 
         if let Ok(codes) = self.detect_qr_codes_rqrr(&img) {
             for code in codes {
-                qr_results.push(QrCodeData {
-                    frame_number,
-                    data: code,
-                    chunk_id,
-                });
+                qr_results.push(QrCodeData::from_decoded(code, frame_number, chunk_id));
             }
         }
 
         if qr_results.is_empty() {
             if let Ok(codes) = self.detect_qr_codes_quircs(&img) {
                 for code in codes {
-                    qr_results.push(QrCodeData {
-                        frame_number,
-                        data: code,
-                        chunk_id,
-                    });
+                    qr_results.push(QrCodeData::from_decoded(code, frame_number, chunk_id));
                 }
             }
         }
@@ -706,7 +1310,31 @@ impl QrExtractor {
         Ok(qr_results)
     }
 
-    fn detect_qr_codes_rqrr(&self, img: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<Vec<String>> {
+    /// Crop `img` to the bounding box of `corners` and encode it as a base64
+    /// PNG data URI payload, for `save_to_html`'s per-code thumbnails.
+    fn crop_thumbnail(img: &ImageBuffer<Rgb<u8>, Vec<u8>>, corners: &[(f64, f64); 4]) -> Option<String> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let min_x = corners.iter().map(|p| p.0).fold(f64::INFINITY, f64::min).max(0.0) as u32;
+        let min_y = corners.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).max(0.0) as u32;
+        let max_x = (corners.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max) as u32).min(img.width());
+        let max_y = (corners.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max) as u32).min(img.height());
+
+        if max_x <= min_x || max_y <= min_y {
+            return None;
+        }
+
+        let cropped = image::imageops::crop_imm(img, min_x, min_y, max_x - min_x, max_y - min_y).to_image();
+
+        let mut png_bytes = Vec::new();
+        image::DynamicImage::ImageRgb8(cropped)
+            .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+            .ok()?;
+
+        Some(general_purpose::STANDARD.encode(png_bytes))
+    }
+
+    fn detect_qr_codes_rqrr(&self, img: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<Vec<DecodedQr>> {
         let luma_img = image::imageops::grayscale(img);
         let mut qr_codes = Vec::new();
 
@@ -714,15 +1342,23 @@ impl QrExtractor {
         let grids = scanner.detect_grids();
 
         for grid in grids {
-            if let Ok((_, content)) = grid.decode() {
-                qr_codes.push(content);
+            let corners = grid.bounds.map(|p| (p.x as f64, p.y as f64));
+            if let Ok((meta, content)) = grid.decode() {
+                qr_codes.push(DecodedQr {
+                    data: content,
+                    raw_bytes: None,
+                    version: Some(meta.version.0 as i32),
+                    ecc_level: Some(format!("{:?}", meta.ecc_level)),
+                    thumbnail_base64: Self::crop_thumbnail(img, &corners),
+                    corners: Some(corners),
+                });
             }
         }
 
         Ok(qr_codes)
     }
 
-    fn detect_qr_codes_quircs(&self, img: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<Vec<String>> {
+    fn detect_qr_codes_quircs(&self, img: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<Vec<DecodedQr>> {
         let luma_img = image::imageops::grayscale(img);
         let mut qr_codes = Vec::new();
 
@@ -731,9 +1367,32 @@ impl QrExtractor {
         for code in codes {
             match code {
                 Ok(valid_code) => {
+                    let corners = valid_code.corners.map(|p| (p.x as f64, p.y as f64));
+                    let thumbnail_base64 = Self::crop_thumbnail(img, &corners);
                     if let Ok(decoded) = valid_code.decode() {
-                        if let Ok(content) = String::from_utf8(decoded.payload) {
-                            qr_codes.push(content);
+                        let version = Some(decoded.version as i32);
+                        let ecc_level = Some(format!("{:?}", decoded.ecc_level));
+                        // Binary framing payloads aren't valid UTF-8 text -
+                        // keep them as raw bytes instead of dropping the
+                        // symbol entirely like the old `String::from_utf8`
+                        // fallthrough used to.
+                        match String::from_utf8(decoded.payload.clone()) {
+                            Ok(content) => qr_codes.push(DecodedQr {
+                                data: content,
+                                raw_bytes: None,
+                                version,
+                                ecc_level,
+                                corners: Some(corners),
+                                thumbnail_base64,
+                            }),
+                            Err(_) => qr_codes.push(DecodedQr {
+                                data: String::new(),
+                                raw_bytes: Some(decoded.payload),
+                                version,
+                                ecc_level,
+                                corners: Some(corners),
+                                thumbnail_base64,
+                            }),
                         }
                     }
                 }
@@ -744,23 +1403,47 @@ impl QrExtractor {
         Ok(qr_codes)
     }
 
-    fn save_chunk_to_jsonl(&self, qr_codes: &[QrCodeData], filename: &str) -> Result<()> {
+    /// Parse a previously-written `chunk_NNN.jsonl` back into `QrCodeData`,
+    /// so a resumed chunk can rejoin the merged results without re-decoding.
+    /// Errors (missing file, a line that doesn't parse) mean the JSONL is
+    /// incomplete or corrupt - the caller treats that as "not actually done".
+    fn load_chunk_from_jsonl(&self, path: &PathBuf) -> Result<Vec<QrCodeData>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read JSONL file {}: {}", path.display(), e))?;
+
+        let mut qr_codes = Vec::new();
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut qr_data = serde_json::from_str::<QrCodeData>(line)
+                .map_err(|e| anyhow!("Failed to parse JSONL line in {}: {}", path.display(), e))?;
+            // JSONL written before `last_frame_number` existed deserializes it
+            // as 0 via `#[serde(default)]` - fall back to `frame_number` so
+            // the dwell window is never reported as ending before it starts.
+            if qr_data.last_frame_number < qr_data.frame_number {
+                qr_data.last_frame_number = qr_data.frame_number;
+            }
+            qr_codes.push(qr_data);
+        }
+
+        Ok(qr_codes)
+    }
+
+    fn save_chunk_to_jsonl(&self, qr_codes: &[QrCodeData], filename: &str) -> std::result::Result<(), QrExtractError> {
         use std::fs::File;
         use std::io::{BufWriter, Write};
 
-        let file = File::create(filename)
-            .map_err(|e| anyhow!("Failed to create JSONL file {}: {}", filename, e))?;
+        let file = File::create(filename)?;
         let mut writer = BufWriter::new(file);
 
         for qr_data in qr_codes {
-            let json_line = serde_json::to_string(qr_data)
-                .map_err(|e| anyhow!("Failed to serialize QR data: {}", e))?;
-            writeln!(writer, "{}", json_line)
-                .map_err(|e| anyhow!("Failed to write JSONL line: {}", e))?;
+            let json_line = serde_json::to_string(qr_data)?;
+            writeln!(writer, "{}", json_line)?;
         }
 
-        writer.flush()
-            .map_err(|e| anyhow!("Failed to flush JSONL file: {}", e))?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
 
         Ok(())
     }
@@ -786,29 +1469,36 @@ impl QrExtractor {
         Ok(())
     }
 
-    pub fn combine_chunk_jsonl_files(&self, chunk_count: usize, output_path: &PathBuf) -> Result<()> {
+    /// Render `results` as a self-contained HTML audit report (grouped by
+    /// chunk then frame, with an inline thumbnail per decoded code where one
+    /// was captured) and write it to `output_path`.
+    pub fn save_to_html(&self, results: &QrExtractionResults, output_path: &PathBuf) -> Result<()> {
+        let html = crate::html_report::render(results);
+        std::fs::write(output_path, html)
+            .map_err(|e| anyhow!("Failed to write HTML report {}: {}", output_path.display(), e))?;
+        Ok(())
+    }
+
+    pub fn combine_chunk_jsonl_files(&self, chunk_count: usize, chunks_dir: &PathBuf, output_path: &PathBuf) -> std::result::Result<(), QrExtractError> {
         use std::fs::File;
         use std::io::{BufRead, BufReader, BufWriter, Write};
 
-        let output_file = File::create(output_path)
-            .map_err(|e| anyhow!("Failed to create combined JSONL file: {}", e))?;
+        let output_file = File::create(output_path)?;
         let mut writer = BufWriter::new(output_file);
 
         let mut all_qr_data = Vec::new();
 
         for chunk_id in 0..chunk_count {
-            let chunk_jsonl_path = PathBuf::from(format!("chunk_{:03}.jsonl", chunk_id + 1));
+            let chunk_jsonl_path = chunks_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
 
             if chunk_jsonl_path.exists() {
-                let file = File::open(&chunk_jsonl_path)
-                    .map_err(|e| anyhow!("Failed to open chunk JSONL: {}", e))?;
+                let file = File::open(&chunk_jsonl_path)?;
                 let reader = BufReader::new(file);
 
                 for line in reader.lines() {
-                    let line = line.map_err(|e| anyhow!("Failed to read line: {}", e))?;
+                    let line = line?;
                     if !line.trim().is_empty() {
-                        let qr_data: QrCodeData = serde_json::from_str(&line)
-                            .map_err(|e| anyhow!("Failed to parse QR data: {}", e))?;
+                        let qr_data: QrCodeData = serde_json::from_str(&line)?;
                         all_qr_data.push(qr_data);
                     }
                 }
@@ -818,14 +1508,11 @@ impl QrExtractor {
         all_qr_data.sort_by_key(|qr| qr.frame_number);
 
         for qr_data in all_qr_data {
-            let json_line = serde_json::to_string(&qr_data)
-                .map_err(|e| anyhow!("Failed to serialize QR data: {}", e))?;
-            writeln!(writer, "{}", json_line)
-                .map_err(|e| anyhow!("Failed to write JSONL line: {}", e))?;
+            let json_line = serde_json::to_string(&qr_data)?;
+            writeln!(writer, "{}", json_line)?;
         }
 
-        writer.flush()
-            .map_err(|e| anyhow!("Failed to flush combined JSONL file: {}", e))?;
+        writer.flush()?;
 
         Ok(())
     }
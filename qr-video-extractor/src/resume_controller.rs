@@ -1,9 +1,26 @@
 use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::fs;
 use crate::resume_state::{ResumeState, ChunkState, ChunkProcessingStatus};
 use crate::events::{EventCallback, ProcessingEvent};
 use crate::error_logger::ErrorLogger;
+use crate::chunk_queue::{ChunkQueue, DoneManifest};
+use crate::video::VideoProcessor;
+
+/// Default cap on retry attempts before a chunk is marked `Abandoned`.
+const DEFAULT_MAX_TRIES: u32 = 5;
+
+/// A chunk is only considered complete once this fraction of its expected
+/// frames have a decoded QR code - chosen to tolerate the occasional dropped
+/// frame without masking a genuinely truncated run.
+const MIN_COVERAGE_RATIO: f64 = 0.98;
+
+/// The largest single contiguous run of missing frames a chunk can have and
+/// still be considered complete, even if overall coverage clears
+/// `MIN_COVERAGE_RATIO` - a handful of scattered single-frame misses is
+/// normal, but one long dropout usually means the decode genuinely stalled.
+const MAX_ACCEPTABLE_GAP_FRAMES: u64 = 30;
 
 pub struct ResumeController {
     state: ResumeState,
@@ -59,7 +76,54 @@ impl ResumeController {
         Ok(ResumePoint::Phase1)
     }
 
+    /// Load `chunk_queue.json`/`done.json` if present and not stale - the
+    /// queue's chunk count must match `self.state.chunk_count`, otherwise
+    /// the run was reconfigured (different `--chunks`/`--duration-per-chunk`)
+    /// since the queue was written and it no longer describes this run.
+    /// `None` means every resume check below must fall back to re-scanning
+    /// the output directory directly, same as before this queue existed.
+    fn queue_and_done(&self) -> Option<(ChunkQueue, DoneManifest)> {
+        let queue = ChunkQueue::load(&self.output_dir).ok().flatten()?;
+        if queue.chunks.len() != self.state.chunk_count {
+            return None;
+        }
+        let done = DoneManifest::load_or_create(&self.output_dir).ok()?;
+        Some((queue, done))
+    }
+
+    /// Re-derive the video's fps via a throwaway `VideoProcessor`, the same
+    /// way `check_completion_status` does, since `ResumeState` doesn't
+    /// persist a copy of `VideoInfo`. Needed to turn a chunk queue entry's
+    /// `start_time`/`end_time` into an expected frame range.
+    fn probe_fps(&self) -> Option<f64> {
+        VideoProcessor::new(&PathBuf::from(&self.state.input_file))
+            .ok()?
+            .get_video_info(&(Box::new(|_| {}) as EventCallback))
+            .ok()
+            .map(|info| info.fps)
+    }
+
     fn can_resume_phase_3(&mut self) -> bool {
+        if let Some((queue, done)) = self.queue_and_done() {
+            let all_done = queue.chunks.iter().all(|entry| done.is_done(entry.id));
+            self.logger.log_info(&format!(
+                "chunk_queue.json/done.json: {}/{} chunks done - can resume from Phase 3: {}",
+                queue.chunks.iter().filter(|e| done.is_done(e.id)).count(), queue.chunks.len(), all_done
+            ));
+            if all_done {
+                for entry in &queue.chunks {
+                    if let Some(qr_codes) = done.qr_codes_found(entry.id) {
+                        self.state.update_chunk_progress(entry.id, qr_codes as u64, qr_codes, ChunkProcessingStatus::Completed);
+                    }
+                }
+            }
+            return all_done;
+        }
+
+        self.can_resume_phase_3_by_scanning()
+    }
+
+    fn can_resume_phase_3_by_scanning(&mut self) -> bool {
         // Check if all JSONL files exist and have reasonable content
         for i in 1..=self.state.chunk_count {
             let jsonl_file = self.output_dir.join(format!("chunk_{:03}.jsonl", i));
@@ -88,6 +152,18 @@ impl ResumeController {
     }
 
     fn can_resume_phase_2(&mut self) -> bool {
+        if let Some((queue, _done)) = self.queue_and_done() {
+            let all_present = queue.chunks.iter().all(|entry| PathBuf::from(&entry.chunk_path).exists());
+            self.logger.log_info(&format!(
+                "chunk_queue.json: all {} chunk files present: {}", queue.chunks.len(), all_present
+            ));
+            return all_present;
+        }
+
+        self.can_resume_phase_2_by_scanning()
+    }
+
+    fn can_resume_phase_2_by_scanning(&mut self) -> bool {
         // Check if video chunks exist
         for i in 1..=self.state.chunk_count {
             let chunk_file = self.output_dir.join(format!("chunk_{:03}.mp4", i));
@@ -102,15 +178,84 @@ impl ResumeController {
     }
 
     fn get_incomplete_chunks(&mut self) -> Vec<ChunkResumeInfo> {
+        match self.queue_and_done() {
+            Some((queue, done)) => self.get_incomplete_chunks_from_queue(&queue, &done),
+            None => self.get_incomplete_chunks_by_scanning(),
+        }
+    }
+
+    /// O(1) per already-`done` chunk: trust `done.json` outright instead of
+    /// re-reading and re-parsing its JSONL. Only chunks `done.json` doesn't
+    /// know about fall through to the same gap analysis the scanning path
+    /// uses, so an interrupted run still gets a precise resume list.
+    fn get_incomplete_chunks_from_queue(&mut self, queue: &ChunkQueue, done: &DoneManifest) -> Vec<ChunkResumeInfo> {
+        let fps = self.probe_fps();
+        let mut incomplete = Vec::new();
+
+        for entry in &queue.chunks {
+            if let Some(qr_codes) = done.qr_codes_found(entry.id) {
+                self.state.update_chunk_progress(entry.id, qr_codes as u64, qr_codes, ChunkProcessingStatus::Completed);
+                continue;
+            }
+
+            let jsonl_file = PathBuf::from(&entry.jsonl_path);
+            let (is_complete, last_frame, qr_count, gap_ranges) = match (jsonl_file.exists(), fps) {
+                (true, Some(fps)) => {
+                    let frame_base = (entry.start_time * fps).round() as u64;
+                    let expected_frame_count = ((entry.end_time - entry.start_time) * fps).round() as u64;
+                    self.analyze_jsonl_completeness(&jsonl_file, entry.id, frame_base, expected_frame_count)
+                }
+                _ => (false, 0, 0, Vec::new()),
+            };
+
+            if !is_complete {
+                incomplete.push(ChunkResumeInfo {
+                    chunk_id: entry.id,
+                    last_frame_processed: last_frame,
+                    qr_codes_found: qr_count,
+                    needs_full_reprocess: qr_count == 0,
+                    gap_ranges,
+                });
+
+                self.state.update_chunk_progress(entry.id, last_frame, qr_count,
+                    if qr_count > 0 { ChunkProcessingStatus::Interrupted } else { ChunkProcessingStatus::NotStarted });
+            } else {
+                self.state.update_chunk_progress(entry.id, last_frame, qr_count, ChunkProcessingStatus::Completed);
+            }
+        }
+
+        self.logger.log_info(&format!("Found {} incomplete chunks for resume (via chunk_queue.json fast path)", incomplete.len()));
+
+        incomplete
+    }
+
+    fn get_incomplete_chunks_by_scanning(&mut self) -> Vec<ChunkResumeInfo> {
         let mut incomplete = Vec::new();
 
+        // `fps` and each chunk's `(start_time, end_time)` are needed to turn
+        // a JSONL's decoded frame numbers into a coverage ratio - re-derive
+        // them the same way `check_completion_status` does rather than
+        // persisting yet another copy of `VideoInfo` in `ResumeState`.
+        let fps = self.probe_fps();
+        let chunk_queue = ChunkQueue::load(&self.output_dir).ok().flatten();
+
         for i in 0..self.state.chunk_count {
             let jsonl_file = self.output_dir.join(format!("chunk_{:03}.jsonl", i + 1));
 
-            let (is_complete, last_frame, qr_count) = if jsonl_file.exists() {
-                self.analyze_jsonl_completeness(&jsonl_file, i)
-            } else {
-                (false, 0, 0)
+            let timing = fps.zip(
+                chunk_queue
+                    .as_ref()
+                    .and_then(|q| q.chunks.iter().find(|entry| entry.id == i))
+                    .map(|entry| (entry.start_time, entry.end_time)),
+            );
+
+            let (is_complete, last_frame, qr_count, gap_ranges) = match (jsonl_file.exists(), timing) {
+                (true, Some((fps, (start_time, end_time)))) => {
+                    let frame_base = (start_time * fps).round() as u64;
+                    let expected_frame_count = ((end_time - start_time) * fps).round() as u64;
+                    self.analyze_jsonl_completeness(&jsonl_file, i, frame_base, expected_frame_count)
+                }
+                _ => (false, 0, 0, Vec::new()),
             };
 
             if !is_complete {
@@ -118,11 +263,12 @@ impl ResumeController {
                     chunk_id: i,
                     last_frame_processed: last_frame,
                     qr_codes_found: qr_count,
-                    needs_full_reprocess: last_frame == 0,
+                    needs_full_reprocess: qr_count == 0,
+                    gap_ranges,
                 });
 
                 self.state.update_chunk_progress(i, last_frame, qr_count,
-                    if last_frame > 0 { ChunkProcessingStatus::Interrupted } else { ChunkProcessingStatus::NotStarted });
+                    if qr_count > 0 { ChunkProcessingStatus::Interrupted } else { ChunkProcessingStatus::NotStarted });
             } else {
                 self.state.update_chunk_progress(i, last_frame, qr_count, ChunkProcessingStatus::Completed);
             }
@@ -133,40 +279,90 @@ impl ResumeController {
         incomplete
     }
 
-    fn analyze_jsonl_completeness(&self, jsonl_path: &PathBuf, chunk_id: usize) -> (bool, u64, usize) {
-        if let Ok(content) = fs::read_to_string(jsonl_path) {
-            let lines: Vec<&str> = content.lines().collect();
-            let qr_count = lines.len();
+    /// Gap-aware replacement for the old "enough QR codes past a high-enough
+    /// frame number" heuristic: parses every `frame_number` out of the
+    /// JSONL, then scans `[frame_base, frame_base + expected_frame_count)`
+    /// for contiguous stretches with no decoded frame. A chunk is complete
+    /// only if the missing frames are a small enough fraction of the whole
+    /// (`MIN_COVERAGE_RATIO`) and no single gap is too large
+    /// (`MAX_ACCEPTABLE_GAP_FRAMES`) - otherwise the concrete gap ranges are
+    /// returned so Phase 2 can re-decode just those intervals.
+    fn analyze_jsonl_completeness(
+        &self,
+        jsonl_path: &PathBuf,
+        chunk_id: usize,
+        frame_base: u64,
+        expected_frame_count: u64,
+    ) -> (bool, u64, usize, Vec<FrameGap>) {
+        let full_reprocess_gap = vec![FrameGap {
+            start_frame: frame_base,
+            end_frame: frame_base + expected_frame_count,
+        }];
+
+        let Ok(content) = fs::read_to_string(jsonl_path) else {
+            return (false, 0, 0, full_reprocess_gap);
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let qr_count = lines.len();
+
+        if qr_count == 0 || expected_frame_count == 0 {
+            return (false, 0, 0, full_reprocess_gap);
+        }
 
-            if qr_count == 0 {
-                return (false, 0, 0);
+        let mut frames: BTreeSet<u64> = BTreeSet::new();
+        for line in &lines {
+            if let Ok(qr_data) = serde_json::from_str::<crate::qr_extraction::QrCodeData>(line) {
+                frames.insert(qr_data.frame_number);
             }
+        }
 
-            // Find the highest frame number processed
-            let mut max_frame = 0u64;
-            for line in lines {
-                if let Ok(qr_data) = serde_json::from_str::<crate::qr_extraction::QrCodeData>(line) {
-                    if qr_data.frame_number > max_frame {
-                        max_frame = qr_data.frame_number;
-                    }
-                }
+        let max_frame = frames.iter().next_back().copied().unwrap_or(0);
+        let expected_end = frame_base + expected_frame_count;
+
+        // Leading gap counts too: a JSONL that starts past `frame_base`
+        // never decoded the chunk's opening frames.
+        let mut gaps = Vec::new();
+        let mut cursor = frame_base;
+        for &frame in &frames {
+            if frame > cursor {
+                gaps.push(FrameGap { start_frame: cursor, end_frame: frame });
             }
+            cursor = cursor.max(frame + 1);
+        }
+        if cursor < expected_end {
+            gaps.push(FrameGap { start_frame: cursor, end_frame: expected_end });
+        }
 
-            // Heuristic for completeness - expect 500+ QR codes per chunk for full processing
-            let is_complete = qr_count >= 500 && max_frame > 1000;
+        let missing_frames: u64 = gaps.iter().map(|g| g.end_frame - g.start_frame).sum();
+        let coverage_ratio = 1.0 - (missing_frames as f64 / expected_frame_count as f64);
+        let largest_gap = gaps.iter().map(|g| g.end_frame - g.start_frame).max().unwrap_or(0);
 
-            self.logger.log_info(&format!("Chunk {}: {} QR codes, max frame {}, complete: {}",
-                                 chunk_id + 1, qr_count, max_frame, is_complete));
+        let is_complete = coverage_ratio >= MIN_COVERAGE_RATIO && largest_gap <= MAX_ACCEPTABLE_GAP_FRAMES;
 
-            (is_complete, max_frame, qr_count)
-        } else {
-            (false, 0, 0)
-        }
+        self.logger.log_info(&format!(
+            "Chunk {}: {} QR codes, {:.1}% frame coverage, largest gap {} frames, complete: {}",
+            chunk_id + 1, qr_count, coverage_ratio * 100.0, largest_gap, is_complete
+        ));
+
+        (is_complete, max_frame, qr_count, if is_complete { Vec::new() } else { gaps })
     }
 
     pub fn update_and_save(&mut self, chunk_id: usize, frame: u64, qr_codes: usize, status: ChunkProcessingStatus) -> Result<()> {
+        let completed = status == ChunkProcessingStatus::Completed;
         self.state.update_chunk_progress(chunk_id, frame, qr_codes, status);
+
+        if completed {
+            let jsonl_file = self.output_dir.join(format!("chunk_{:03}.jsonl", chunk_id + 1));
+            if let Err(e) = self.state.record_chunk_output_hash(chunk_id, &jsonl_file) {
+                self.logger.log_warning("HASH", &format!("Could not hash {}: {}", jsonl_file.display(), e));
+            }
+        }
+
         self.state.save(&self.output_dir)?;
+        if completed {
+            let _ = self.state.save_manifest(&self.output_dir);
+        }
         Ok(())
     }
 
@@ -181,7 +377,7 @@ impl ResumeController {
         self.logger.log_error(context, error);
 
         if let Some(id) = chunk_id {
-            self.state.mark_chunk_error(id, error.to_string());
+            self.state.mark_chunk_error(id, error.to_string(), DEFAULT_MAX_TRIES);
             let _ = self.state.save(&self.output_dir);
         }
 
@@ -235,4 +431,17 @@ pub struct ChunkResumeInfo {
     pub last_frame_processed: u64,
     pub qr_codes_found: usize,
     pub needs_full_reprocess: bool,
+    /// Frame ranges missing from the chunk's JSONL, so Phase 2 can re-decode
+    /// only these intervals instead of the whole chunk. When
+    /// `needs_full_reprocess` is true this is either empty or one range
+    /// spanning the whole chunk.
+    pub gap_ranges: Vec<FrameGap>,
+}
+
+/// A half-open `[start_frame, end_frame)` range of frame numbers with no
+/// decoded QR code in a chunk's JSONL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameGap {
+    pub start_frame: u64,
+    pub end_frame: u64,
 }
\ No newline at end of file
@@ -17,6 +17,119 @@ pub struct ResumeState {
     pub total_frames: u64,
     pub start_time: Option<u64>, // Unix timestamp
     pub last_update: u64,
+    /// Keyframe-aligned chunk boundaries, when the video was split by
+    /// `plan_keyframe_chunks` instead of a fixed `chunk_count`. Empty when
+    /// the legacy fixed-count splitting was used.
+    #[serde(default)]
+    pub chunk_boundaries: Vec<ChunkBoundary>,
+}
+
+/// A single keyframe-delimited segment of the source video.
+///
+/// `start_frame`/`end_frame` are half-open `[start_frame, end_frame)` frame
+/// indices, and `keyframe_pts` is the presentation timestamp (seconds) of
+/// the keyframe the segment starts on, so resume logic can seek directly to
+/// it instead of decoding from frame zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkBoundary {
+    pub start_frame: u64,
+    pub end_frame: u64,
+    pub keyframe_pts: f64,
+}
+
+/// Rough per-worker decode working set: one RGB frame buffer per decoder
+/// thread, scaled by `buffer_factor` to account for the scaler/intermediate
+/// copies ffmpeg and the PNG pipeline keep alive per flight.
+pub fn estimate_per_worker_bytes(width: u32, height: u32, buffer_factor: u64) -> u64 {
+    (width as u64) * (height as u64) * 3 * buffer_factor
+}
+
+/// Currently-available system memory, in bytes, via `sysinfo`. Falls back to
+/// a conservative 1GiB estimate if the platform doesn't expose it, so a
+/// query failure degrades to a caution rather than an unbounded worker count.
+pub fn available_memory_bytes() -> u64 {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+
+    let available = system.available_memory();
+    if available > 0 {
+        available
+    } else {
+        1024 * 1024 * 1024
+    }
+}
+
+/// Compute how many chunks can be processed concurrently given the machine's
+/// available parallelism, the amount of work actually left to do, and a
+/// rough memory budget (frame-buffer size × decoder overhead per worker,
+/// passed in as `bytes_per_worker`). Modeled on Av1an's worker selection:
+/// never plan more workers than there is work for, and downscale further if
+/// the estimated total memory would exceed `memory_ceiling_bytes`.
+pub fn determine_workers(pending_chunks: usize, bytes_per_worker: u64, memory_ceiling_bytes: u64) -> usize {
+    let available = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    let mut workers = std::cmp::min(available, pending_chunks.max(1));
+
+    if bytes_per_worker > 0 && memory_ceiling_bytes > 0 {
+        let memory_bound = (memory_ceiling_bytes / bytes_per_worker).max(1) as usize;
+        workers = std::cmp::min(workers, memory_bound);
+    }
+
+    workers.max(1)
+}
+
+/// Greedily group keyframe PTS timestamps into segments that each cover at
+/// least `target_duration_secs` (or `target_frame_budget` frames, whichever
+/// comes first), without ever splitting inside a GOP. `keyframe_pts` must be
+/// sorted ascending and include the timestamp of the very first frame.
+pub fn plan_keyframe_chunks(
+    keyframe_pts: &[f64],
+    fps: f64,
+    total_frames: u64,
+    target_duration_secs: f64,
+    target_frame_budget: u64,
+) -> Vec<ChunkBoundary> {
+    if keyframe_pts.is_empty() || fps <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut boundaries = Vec::new();
+    let mut segment_start_idx = 0usize;
+
+    for i in 1..=keyframe_pts.len() {
+        let is_last_keyframe = i == keyframe_pts.len();
+        let segment_duration = if is_last_keyframe {
+            f64::MAX
+        } else {
+            keyframe_pts[i] - keyframe_pts[segment_start_idx]
+        };
+        let segment_start_frame = (keyframe_pts[segment_start_idx] * fps).round() as u64;
+        let segment_frames = if is_last_keyframe {
+            u64::MAX
+        } else {
+            ((keyframe_pts[i] * fps).round() as u64).saturating_sub(segment_start_frame)
+        };
+
+        if is_last_keyframe || segment_duration >= target_duration_secs || segment_frames >= target_frame_budget {
+            let end_frame = if is_last_keyframe {
+                total_frames
+            } else {
+                (keyframe_pts[i] * fps).round() as u64
+            };
+
+            boundaries.push(ChunkBoundary {
+                start_frame: segment_start_frame,
+                end_frame,
+                keyframe_pts: keyframe_pts[segment_start_idx],
+            });
+
+            segment_start_idx = i;
+        }
+    }
+
+    boundaries
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +143,43 @@ pub struct ChunkState {
     pub processing_time_ms: u64,
     pub error_count: usize,
     pub last_error: Option<String>,
+    /// Number of processing attempts made so far, used against `max_tries`.
+    #[serde(default)]
+    pub attempt_count: u32,
+    /// (unix timestamp, error message) for every failed attempt.
+    #[serde(default)]
+    pub attempt_history: Vec<(u64, String)>,
+    /// SHA-256 of the chunk's JSONL output, computed once processing
+    /// finished, so resume can detect truncated/corrupted files instead of
+    /// trusting a line-count heuristic.
+    #[serde(default)]
+    pub output_sha256: Option<String>,
+    /// QR code count expected for this chunk when `output_sha256` was taken,
+    /// so a hash match still requires the count to agree.
+    #[serde(default)]
+    pub expected_qr_codes: Option<usize>,
+}
+
+/// SHA-256 of a file's contents, hex-encoded.
+pub fn hash_file_sha256(path: &PathBuf) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let bytes = fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkManifestEntry {
+    pub chunk_id: usize,
+    pub jsonl_file: String,
+    pub sha256: String,
+    pub qr_codes_found: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub chunks: Vec<ChunkManifestEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -39,6 +189,15 @@ pub enum ChunkProcessingStatus {
     Completed,
     Failed,
     Interrupted,
+    /// Exceeded `max_tries` and will no longer be retried automatically.
+    Abandoned,
+}
+
+/// Exponential backoff delay for a given attempt number: `base_delay * 2^attempt`,
+/// capped at `max_delay_secs`.
+pub fn retry_backoff_secs(attempt: u32, base_delay_secs: u64, max_delay_secs: u64) -> u64 {
+    let delay = base_delay_secs.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+    delay.min(max_delay_secs)
 }
 
 impl ResumeState {
@@ -59,6 +218,53 @@ impl ResumeState {
             last_update: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap().as_secs(),
+            chunk_boundaries: Vec::new(),
+        }
+    }
+
+    /// Record keyframe-aligned chunk boundaries, updating `chunk_count` to
+    /// match the (likely variable) number of segments produced.
+    pub fn set_chunk_boundaries(&mut self, boundaries: Vec<ChunkBoundary>) {
+        self.chunk_count = boundaries.len();
+        self.chunk_boundaries = boundaries;
+    }
+
+    /// Carry forward completed chunks from a prior run whose parameters
+    /// (e.g. `--chunks`) have since changed. A chunk only survives if its
+    /// JSONL still exists on disk, its on-disk content still hashes to the
+    /// digest recorded when it finished, and its frame range is covered
+    /// identically by the new `chunk_boundaries` - anything whose range
+    /// shifted is left untouched so it gets requeued and reprocessed.
+    fn migrate_completed_chunks(&mut self, old: &ResumeState, output_dir: &PathBuf) {
+        if self.chunk_boundaries.is_empty() || old.chunk_boundaries.is_empty() {
+            // No boundary information to compare frame ranges against -
+            // equal-duration chunking shifts on any count change, so there's
+            // nothing safe to salvage.
+            return;
+        }
+
+        for (old_id, old_chunk) in &old.chunks {
+            if old_chunk.status != ChunkProcessingStatus::Completed {
+                continue;
+            }
+            let Some(expected_hash) = &old_chunk.output_sha256 else { continue };
+            let Some(old_range) = old.chunk_boundaries.get(*old_id) else { continue };
+
+            let new_id = self.chunk_boundaries.iter().position(|b| {
+                b.start_frame == old_range.start_frame && b.end_frame == old_range.end_frame
+            });
+            let Some(new_id) = new_id else { continue };
+
+            let jsonl_file = output_dir.join(&old_chunk.jsonl_file);
+            if !jsonl_file.exists() {
+                continue;
+            }
+
+            if hash_file_sha256(&jsonl_file).map_or(false, |h| &h == expected_hash) {
+                let mut carried = old_chunk.clone();
+                carried.chunk_id = new_id;
+                self.chunks.insert(new_id, carried);
+            }
         }
     }
 
@@ -70,11 +276,22 @@ impl ResumeState {
             let mut state: ResumeState = serde_json::from_str(&content)?;
 
             // Validate state compatibility
-            if state.input_file != input_file || state.chunk_count != chunk_count {
-                // Parameters changed - start fresh
+            if state.input_file != input_file {
+                // Different input entirely - nothing to salvage.
                 return Ok(Self::new(input_file, &output_dir.to_string_lossy(), chunk_count, thread_count, skip_frames));
             }
 
+            if state.chunk_count != chunk_count {
+                // A parameter like --chunks changed: re-plan fresh.
+                // `migrate_completed_chunks` only carries over chunks whose
+                // frame range matches one of `fresh.chunk_boundaries`, and a
+                // state built by `Self::new` has none, so every chunk starts
+                // over here.
+                let mut fresh = Self::new(input_file, &output_dir.to_string_lossy(), chunk_count, thread_count, skip_frames);
+                fresh.migrate_completed_chunks(&state, output_dir);
+                return Ok(fresh);
+            }
+
             // Update last_update timestamp
             state.last_update = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -138,9 +355,19 @@ impl ResumeState {
         let output_dir = PathBuf::from(&self.output_dir);
         let mut incomplete = Vec::new();
 
-        for i in 0..self.chunk_count {
+        // When keyframe-aligned boundaries were recorded, the chunk list is
+        // keyed off them rather than the fixed `chunk_count` used by the
+        // legacy equal-duration splitting.
+        let chunk_total = if self.chunk_boundaries.is_empty() {
+            self.chunk_count
+        } else {
+            self.chunk_boundaries.len()
+        };
+
+        for i in 0..chunk_total {
             if let Some(chunk) = self.chunks.get(&i) {
-                if chunk.status != ChunkProcessingStatus::Completed {
+                if chunk.status != ChunkProcessingStatus::Completed
+                    && chunk.status != ChunkProcessingStatus::Abandoned {
                     incomplete.push(i);
                 }
             } else {
@@ -160,6 +387,19 @@ impl ResumeState {
             return Ok(true);
         }
 
+        // If we recorded a digest for this chunk last time it finished, trust
+        // that over a line-count guess: a mismatch means the file was
+        // truncated or overwritten by a crashed run. With no digest
+        // recorded, the line-count heuristic below decides.
+        if let Some(chunk) = self.chunks.get(&chunk_id) {
+            if let Some(expected_hash) = &chunk.output_sha256 {
+                let actual_hash = hash_file_sha256(jsonl_path)?;
+                let line_count = fs::read_to_string(jsonl_path)?.lines().count();
+                let qr_codes_match = chunk.expected_qr_codes.map_or(true, |n| n == line_count);
+                return Ok(&actual_hash != expected_hash || !qr_codes_match);
+            }
+        }
+
         let content = fs::read_to_string(jsonl_path)?;
         let line_count = content.lines().count();
 
@@ -168,6 +408,44 @@ impl ResumeState {
         Ok(line_count < 100) // Expect at least 100 QR codes per chunk for completeness
     }
 
+    /// Hash a chunk's finished JSONL and store the digest alongside its QR
+    /// count, so the next resume can verify the file instead of guessing
+    /// from its line count.
+    pub fn record_chunk_output_hash(&mut self, chunk_id: usize, jsonl_path: &PathBuf) -> Result<()> {
+        let hash = hash_file_sha256(jsonl_path)?;
+        let qr_codes = fs::read_to_string(jsonl_path)?.lines().count();
+
+        if let Some(chunk) = self.chunks.get_mut(&chunk_id) {
+            chunk.output_sha256 = Some(hash);
+            chunk.expected_qr_codes = Some(qr_codes);
+        }
+
+        Ok(())
+    }
+
+    /// Write `manifest.json` summarizing every completed chunk's digest, for
+    /// external tooling (or a future run) to verify output integrity without
+    /// re-deriving it from `resume_state.json`.
+    pub fn save_manifest(&self, output_dir: &PathBuf) -> Result<()> {
+        let mut chunks: Vec<ChunkManifestEntry> = self.chunks.values()
+            .filter_map(|c| {
+                let sha256 = c.output_sha256.clone()?;
+                Some(ChunkManifestEntry {
+                    chunk_id: c.chunk_id,
+                    jsonl_file: c.jsonl_file.clone(),
+                    sha256,
+                    qr_codes_found: c.qr_codes_found,
+                })
+            })
+            .collect();
+        chunks.sort_by_key(|c| c.chunk_id);
+
+        let manifest = Manifest { chunks };
+        let manifest_file = output_dir.join("manifest.json");
+        fs::write(&manifest_file, serde_json::to_string_pretty(&manifest)?)?;
+        Ok(())
+    }
+
     pub fn update_chunk_progress(&mut self, chunk_id: usize, frame: u64, qr_codes: usize, status: ChunkProcessingStatus) {
         let chunk_state = self.chunks.entry(chunk_id).or_insert_with(|| ChunkState {
             chunk_id,
@@ -179,6 +457,10 @@ impl ResumeState {
             processing_time_ms: 0,
             error_count: 0,
             last_error: None,
+            attempt_count: 0,
+            attempt_history: Vec::new(),
+            output_sha256: None,
+            expected_qr_codes: None,
         });
 
         chunk_state.last_frame_processed = frame;
@@ -190,28 +472,74 @@ impl ResumeState {
             .unwrap().as_secs();
     }
 
-    pub fn mark_chunk_error(&mut self, chunk_id: usize, error: String) {
-        if let Some(chunk) = self.chunks.get_mut(&chunk_id) {
-            chunk.error_count += 1;
-            chunk.last_error = Some(error);
-            chunk.status = ChunkProcessingStatus::Failed;
-        }
+    /// Record a failed attempt and requeue it, unless it has now exceeded
+    /// `max_tries`, in which case it becomes permanently `Abandoned` so a
+    /// hung input can't stall the whole run.
+    pub fn mark_chunk_error(&mut self, chunk_id: usize, error: String, max_tries: u32) {
+        let chunk = self.chunks.entry(chunk_id).or_insert_with(|| ChunkState {
+            chunk_id,
+            video_file: format!("chunk_{:03}.mp4", chunk_id + 1),
+            jsonl_file: format!("chunk_{:03}.jsonl", chunk_id + 1),
+            status: ChunkProcessingStatus::NotStarted,
+            last_frame_processed: 0,
+            qr_codes_found: 0,
+            processing_time_ms: 0,
+            error_count: 0,
+            last_error: None,
+            attempt_count: 0,
+            attempt_history: Vec::new(),
+            output_sha256: None,
+            expected_qr_codes: None,
+        });
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap().as_secs();
+
+        chunk.error_count += 1;
+        chunk.attempt_count += 1;
+        chunk.attempt_history.push((now, error.clone()));
+        chunk.last_error = Some(error);
+        chunk.status = if chunk.attempt_count >= max_tries {
+            ChunkProcessingStatus::Abandoned
+        } else {
+            ChunkProcessingStatus::Failed
+        };
     }
 
     pub fn get_progress_summary(&self) -> String {
         let completed = self.chunks.values().filter(|c| c.status == ChunkProcessingStatus::Completed).count();
         let processing = self.chunks.values().filter(|c| c.status == ChunkProcessingStatus::Processing).count();
         let failed = self.chunks.values().filter(|c| c.status == ChunkProcessingStatus::Failed).count();
+        let abandoned = self.chunks.values().filter(|c| c.status == ChunkProcessingStatus::Abandoned).count();
         let total_qr = self.chunks.values().map(|c| c.qr_codes_found).sum::<usize>();
+        let total_attempts: u32 = self.chunks.values().map(|c| c.attempt_count).sum();
+
+        let mut summary = format!("Chunks: {}/{} completed, {} processing, {} failed, {} abandoned | QR codes: {} | attempts: {}",
+                completed, self.chunk_count, processing, failed, abandoned, total_qr, total_attempts);
+
+        for chunk in self.chunks.values().filter(|c| !c.attempt_history.is_empty()) {
+            if let Some((_, last_err)) = chunk.attempt_history.last() {
+                summary.push_str(&format!("\n  chunk {}: {} attempts, last error: {}",
+                    chunk.chunk_id + 1, chunk.attempt_count, last_err));
+            }
+        }
+
+        summary
+    }
 
-        format!("Chunks: {}/{} completed, {} processing, {} failed | QR codes: {}",
-                completed, self.chunk_count, processing, failed, total_qr)
+    /// Re-plan `thread_count` for the pending chunks on whatever hardware
+    /// this run happens to be resumed on. Only `self.thread_count` changes;
+    /// nothing is written to disk.
+    pub fn replan_workers(&mut self, bytes_per_worker: u64, memory_ceiling_bytes: u64) {
+        let pending = self.get_incomplete_chunks().len();
+        self.thread_count = determine_workers(pending, bytes_per_worker, memory_ceiling_bytes);
     }
 
     pub fn can_resume_chunk(&self, chunk_id: usize) -> (bool, u64) {
         if let Some(chunk) = self.chunks.get(&chunk_id) {
             match chunk.status {
-                ChunkProcessingStatus::Completed => (false, 0),
+                ChunkProcessingStatus::Completed | ChunkProcessingStatus::Abandoned => (false, 0),
                 ChunkProcessingStatus::Processing | ChunkProcessingStatus::Interrupted => {
                     (true, chunk.last_frame_processed)
                 }
@@ -225,4 +553,104 @@ impl ResumeState {
             (true, 0)
         }
     }
+
+    /// Seconds remaining before a failed chunk's next retry is due, per the
+    /// exponential backoff schedule. `0` means it can be retried now.
+    pub fn retry_delay_remaining_secs(&self, chunk_id: usize, base_delay_secs: u64, max_delay_secs: u64) -> u64 {
+        let Some(chunk) = self.chunks.get(&chunk_id) else { return 0 };
+        let Some((failed_at, _)) = chunk.attempt_history.last() else { return 0 };
+
+        let backoff = retry_backoff_secs(chunk.attempt_count.saturating_sub(1), base_delay_secs, max_delay_secs);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap().as_secs();
+
+        (failed_at + backoff).saturating_sub(now)
+    }
+
+    /// Serialize a comprehensive diagnostic snapshot to
+    /// `output_dir/dump_<unixtime>.json` - per-chunk throughput, an error
+    /// histogram, the resume phase, and a SHA-256 of `resume_state.json`
+    /// itself, so a stalled or slow run can be reported with one artifact
+    /// instead of the whole output directory.
+    pub fn debug_dump(&self, output_dir: &PathBuf) -> Result<PathBuf> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap().as_secs();
+        let elapsed_secs = self.start_time.map(|t| now.saturating_sub(t)).unwrap_or(0);
+
+        let mut total_frames = 0u64;
+        let mut total_time_ms = 0u64;
+        let mut chunk_throughput = Vec::new();
+        let mut error_histogram: HashMap<String, usize> = HashMap::new();
+
+        for chunk in self.chunks.values() {
+            let frames_per_sec = if chunk.processing_time_ms > 0 {
+                chunk.last_frame_processed as f64 / (chunk.processing_time_ms as f64 / 1000.0)
+            } else {
+                0.0
+            };
+
+            chunk_throughput.push(ChunkThroughput {
+                chunk_id: chunk.chunk_id,
+                frames_per_sec,
+                qr_codes_found: chunk.qr_codes_found,
+                status: chunk.status.clone(),
+            });
+
+            total_frames += chunk.last_frame_processed;
+            total_time_ms += chunk.processing_time_ms;
+
+            for (_, error) in &chunk.attempt_history {
+                *error_histogram.entry(error.clone()).or_insert(0) += 1;
+            }
+        }
+        chunk_throughput.sort_by_key(|c| c.chunk_id);
+
+        let aggregate_frames_per_sec = if total_time_ms > 0 {
+            total_frames as f64 / (total_time_ms as f64 / 1000.0)
+        } else {
+            0.0
+        };
+
+        let state_file = output_dir.join("resume_state.json");
+        let state_sha256 = if state_file.exists() {
+            hash_file_sha256(&state_file)?
+        } else {
+            String::new()
+        };
+
+        let dump = DebugDump {
+            dumped_at: now,
+            resume_phase: self.can_resume_from_phase(),
+            elapsed_secs,
+            aggregate_frames_per_sec,
+            chunk_throughput,
+            error_histogram,
+            state_sha256,
+        };
+
+        let dump_file = output_dir.join(format!("dump_{}.json", now));
+        fs::write(&dump_file, serde_json::to_string_pretty(&dump)?)?;
+        Ok(dump_file)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkThroughput {
+    pub chunk_id: usize,
+    pub frames_per_sec: f64,
+    pub qr_codes_found: usize,
+    pub status: ChunkProcessingStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugDump {
+    pub dumped_at: u64,
+    pub resume_phase: u8,
+    pub elapsed_secs: u64,
+    pub aggregate_frames_per_sec: f64,
+    pub chunk_throughput: Vec<ChunkThroughput>,
+    pub error_histogram: HashMap<String, usize>,
+    pub state_sha256: String,
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::tui::TuiState;
+
+/// One phase's final timing, snapshotted from `PhaseInfo` at report time.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseReportEntry {
+    pub name: String,
+    pub status: String,
+    pub duration_ms: Option<u64>,
+    pub message: String,
+}
+
+/// One chunk's outcome, snapshotted from `ChunkInfo` at report time.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkReportEntry {
+    pub chunk_id: usize,
+    pub name: String,
+    pub status: String,
+    pub qr_codes_found: usize,
+    pub jsonl_file: Option<String>,
+    pub duration_ms: Option<u64>,
+}
+
+/// A reproducible record of one run: per-phase and per-chunk timings plus
+/// outcomes, so throughput, failures and total elapsed time survive the TUI
+/// tearing down instead of only existing transiently in `TuiState`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub generated_at: u64,
+    pub elapsed_secs: u64,
+    pub phases: Vec<PhaseReportEntry>,
+    pub chunks: Vec<ChunkReportEntry>,
+}
+
+impl SessionReport {
+    /// Snapshot `state`'s phase and chunk tracking into a report.
+    pub fn from_state(state: &TuiState) -> Self {
+        let generated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let elapsed_secs = state.start_time.map(|t| t.elapsed().as_secs()).unwrap_or(0);
+
+        let phases = state
+            .phases
+            .iter()
+            .map(|p| PhaseReportEntry {
+                name: p.name.clone(),
+                status: format!("{:?}", p.status),
+                duration_ms: p.duration_ms,
+                message: p.message.clone(),
+            })
+            .collect();
+
+        let mut chunks: Vec<ChunkReportEntry> = state
+            .chunks
+            .iter()
+            .map(|c| ChunkReportEntry {
+                chunk_id: c.id,
+                name: c.name.clone(),
+                status: format!("{:?}", c.status),
+                qr_codes_found: c.qr_codes_found,
+                jsonl_file: c.jsonl_file.clone(),
+                duration_ms: c.duration_ms,
+            })
+            .collect();
+        chunks.sort_by_key(|c| c.chunk_id);
+
+        Self {
+            generated_at,
+            elapsed_secs,
+            phases,
+            chunks,
+        }
+    }
+
+    /// Write `session_report.json` (machine-readable) and
+    /// `session_report.md` (a human-readable table) to `output_dir`.
+    pub fn write(&self, output_dir: &Path) -> Result<()> {
+        std::fs::write(
+            output_dir.join("session_report.json"),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        std::fs::write(output_dir.join("session_report.md"), self.to_markdown())?;
+        Ok(())
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# Session Report\n");
+        let _ = writeln!(md, "Elapsed: {}s\n", self.elapsed_secs);
+
+        let _ = writeln!(md, "## Phases\n");
+        let _ = writeln!(md, "| Phase | Status | Duration (ms) | Message |");
+        let _ = writeln!(md, "|---|---|---|---|");
+        for p in &self.phases {
+            let duration = p.duration_ms.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string());
+            let _ = writeln!(md, "| {} | {} | {} | {} |", p.name, p.status, duration, p.message);
+        }
+
+        let _ = writeln!(md, "\n## Chunks\n");
+        let _ = writeln!(md, "| Chunk | Status | QR Codes | JSONL | Duration (ms) |");
+        let _ = writeln!(md, "|---|---|---|---|---|");
+        for c in &self.chunks {
+            let duration = c.duration_ms.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string());
+            let jsonl = c.jsonl_file.as_deref().unwrap_or("-");
+            let _ = writeln!(md, "| {} | {} | {} | {} | {} |", c.chunk_id + 1, c.status, c.qr_codes_found, jsonl, duration);
+        }
+
+        md
+    }
+}
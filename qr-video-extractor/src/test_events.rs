@@ -63,12 +63,14 @@ pub fn test_event_system() {
         ProcessingEvent::ChunkStarted {
             chunk_id: 0,
             chunk_name: "chunk_001.mp4".to_string(),
+            worker_id: 0,
         },
         ProcessingEvent::ChunkCompleted {
             chunk_id: 0,
             qr_codes_found: 150,
             jsonl_file: "chunk_001.jsonl".to_string(),
             duration_ms: 5000,
+            worker_id: 0,
         },
         ProcessingEvent::PhaseCompleted {
             phase: 1,
@@ -1,32 +1,136 @@
 use anyhow::{anyhow, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{DisableMouseCapture, EnableMouseCapture, Event as CtEvent, EventStream, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures::{FutureExt, StreamExt};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame, Terminal,
 };
+use std::collections::VecDeque;
 use std::io::{self, IsTerminal};
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::events::{EventCallback, ProcessingEvent};
+use crate::pipeline_control::PipelineControl;
+
+/// The three input sources `TuiManager::run` merges into one async loop, so
+/// key handling, processing updates and the redraw tick all fall out of a
+/// single `futures::select!` instead of a blocking `event::poll` tick that
+/// forces a fixed redraw cadence and contends with producer threads over a
+/// shared `Mutex<TuiState>`.
+enum Event {
+    Key(KeyEvent),
+    Resize(u16, u16),
+    Processing(ProcessingEvent),
+    Tick,
+}
 
 pub struct TuiState {
     pub phases: Vec<PhaseInfo>,
     pub current_phase: usize,
-    pub messages: Vec<String>,
+    pub messages: Vec<(Severity, String)>,
     pub should_quit: bool,
     pub chunks: Vec<ChunkInfo>,
     pub total_frames: u64,
     pub frames_processed: u64,
     pub start_time: Option<std::time::Instant>,
+    /// Index into `chunks` highlighted by the scrubbable timeline.
+    pub selected_chunk_index: usize,
+    /// Whether the detail pane for the selected chunk is open.
+    pub show_detail: bool,
+    /// Mirrors `PipelineControl::is_paused` for rendering in the status bar.
+    pub paused: bool,
+    /// Set once a graceful cancel ('c') has been requested on
+    /// `PipelineControl`. Workers finish whatever chunk they're on and
+    /// flush it normally; this just tracks that a stop is in flight so the
+    /// status bar can show it instead of looking like nothing happened.
+    pub cancelling: bool,
+    /// Worker slot -> what that `ChunkBroker` consumer is currently
+    /// decoding, keyed by `ProcessingEvent::worker_id`. Rendered as the
+    /// lane view so a stalled or idle worker is visible at a glance instead
+    /// of only showing the flat per-chunk list.
+    pub worker_lanes: Vec<WorkerLane>,
+    /// Lines scrolled up from the bottom of the (filtered) message log.
+    /// `0` means the view is pinned to the tail.
+    pub scroll_offset: usize,
+    /// Whether the log view auto-scrolls to new messages. Cleared by any
+    /// manual scroll and re-set once the user scrolls back to the bottom.
+    pub follow_tail: bool,
+    /// Minimum severity a message needs to be shown; cycled with 'f'.
+    pub severity_filter: Severity,
+    /// Ring buffer of `(sample time, cumulative frames_processed)` pushed on
+    /// every `FrameProgress`, pruned to the last `THROUGHPUT_WINDOW_SECS` -
+    /// the oldest/newest pair gives a windowed frames/sec that reacts to
+    /// real-time speed changes instead of lagging like a cumulative average.
+    pub throughput_samples: VecDeque<(std::time::Instant, u64)>,
+    /// Exponential moving average of frames/sec, blended with each new
+    /// windowed rate (`0.7*ema + 0.3*window_rate`) to damp its frame-to-frame
+    /// noise while still tracking real throughput changes quickly. Drives
+    /// the status bar's live f/s figure, and its remaining-time ETA until
+    /// per-chunk rates (`aggregate_eta_secs`) are available.
+    pub throughput_ema: f64,
+}
+
+/// Width of the sliding window used to compute instantaneous throughput.
+const THROUGHPUT_WINDOW_SECS: f64 = 10.0;
+
+/// How serious a logged message is. Ordered so `severity_filter` can hide
+/// everything below a chosen threshold with a plain `>=` comparison.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Progress,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    /// Cycle order used by the 'f' filter key: show everything, then
+    /// progressively hide the chattier levels.
+    fn next(self) -> Self {
+        match self {
+            Severity::Info => Severity::Progress,
+            Severity::Progress => Severity::Warn,
+            Severity::Warn => Severity::Error,
+            Severity::Error => Severity::Info,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Progress => "Progress",
+            Severity::Warn => "Warn",
+            Severity::Error => "Error",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Severity::Info => Color::White,
+            Severity::Progress => Color::Gray,
+            Severity::Warn => Color::Yellow,
+            Severity::Error => Color::Red,
+        }
+    }
+}
+
+/// One row of the worker-lane view.
+#[derive(Clone, Default)]
+pub struct WorkerLane {
+    /// Chunk this worker slot is currently decoding, or `None` if idle.
+    pub chunk_id: Option<usize>,
+    /// When this worker picked up `chunk_id`, used to compute its live
+    /// QR/sec rate.
+    pub started_at: Option<std::time::Instant>,
 }
 
 #[derive(Clone)]
@@ -38,6 +142,27 @@ pub struct ChunkInfo {
     pub qr_codes_found: usize,
     pub jsonl_file: Option<String>,
     pub duration_ms: Option<u64>,
+    /// Real source-video start/end offsets in seconds, from `ChunkBoundariesPlanned`.
+    /// `None` until that event arrives (e.g. `--phase3-only` never splits a video).
+    pub source_offsets: Option<(f64, f64)>,
+    /// Total frames this chunk will process, from `FrameProgress`. `None`
+    /// until the first `FrameProgress` for this chunk arrives.
+    pub total_frames: Option<u64>,
+    /// Smoothed frames-per-second from the most recent `ChunkProgress`/`FrameProgress`.
+    pub fps: f64,
+    /// Time remaining for this chunk at `fps`, or `None` if not yet known.
+    pub eta_secs: Option<u64>,
+    /// Most recent log lines for this chunk, shown in the detail pane.
+    pub log: Vec<String>,
+}
+
+const CHUNK_LOG_CAP: usize = 20;
+
+fn push_chunk_log(chunk: &mut ChunkInfo, line: String) {
+    chunk.log.push(line);
+    if chunk.log.len() > CHUNK_LOG_CAP {
+        chunk.log.remove(0);
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -98,9 +223,107 @@ impl TuiState {
             total_frames: 0,
             frames_processed: 0,
             start_time: None,
+            selected_chunk_index: 0,
+            show_detail: false,
+            paused: false,
+            cancelling: false,
+            worker_lanes: Vec::new(),
+            scroll_offset: 0,
+            follow_tail: true,
+            severity_filter: Severity::Info,
+            throughput_samples: VecDeque::new(),
+            throughput_ema: 0.0,
         }
     }
 
+    /// Record a new cumulative frame-count sample and recompute the smoothed
+    /// frames/sec estimate from the oldest/newest sample still inside the
+    /// window.
+    fn record_throughput_sample(&mut self, frames_processed: u64) {
+        let now = std::time::Instant::now();
+        self.throughput_samples.push_back((now, frames_processed));
+        while let Some(&(t, _)) = self.throughput_samples.front() {
+            if now.duration_since(t).as_secs_f64() > THROUGHPUT_WINDOW_SECS {
+                self.throughput_samples.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if let (Some(&(oldest_t, oldest_frames)), Some(&(newest_t, newest_frames))) =
+            (self.throughput_samples.front(), self.throughput_samples.back())
+        {
+            let dt = newest_t.duration_since(oldest_t).as_secs_f64();
+            if dt > 0.0 && newest_frames > oldest_frames {
+                let window_rate = (newest_frames - oldest_frames) as f64 / dt;
+                self.throughput_ema = if self.throughput_ema > 0.0 {
+                    0.7 * self.throughput_ema + 0.3 * window_rate
+                } else {
+                    window_rate
+                };
+            }
+        }
+    }
+
+    /// Ensure `worker_lanes` has a slot for `worker_id`, growing it with
+    /// idle lanes as needed - workers report in as they pick up their first
+    /// chunk, so the vec is sized lazily rather than up front.
+    fn worker_lane_mut(&mut self, worker_id: usize) -> &mut WorkerLane {
+        if self.worker_lanes.len() <= worker_id {
+            self.worker_lanes.resize(worker_id + 1, WorkerLane::default());
+        }
+        &mut self.worker_lanes[worker_id]
+    }
+
+    /// Append a message with its severity, capping the buffer at 100
+    /// entries like before. While following the tail, the view stays
+    /// pinned to the newest message; a manual scroll (see `scroll`)
+    /// disables that until the user scrolls back down to the bottom.
+    fn push_message(&mut self, severity: Severity, message: String) {
+        self.messages.push((severity, message));
+        if self.messages.len() > 100 {
+            self.messages.remove(0);
+        }
+    }
+
+    /// Messages at or above `severity_filter`, oldest first.
+    pub fn visible_messages(&self) -> Vec<&(Severity, String)> {
+        self.messages.iter().filter(|(s, _)| *s >= self.severity_filter).collect()
+    }
+
+    /// Scroll the log by `delta` lines (positive = toward older messages,
+    /// negative = toward the live tail). `scroll_offset` is how many lines
+    /// up from the bottom the view sits; `ui_static` clamps it to the
+    /// actual scrollable range each time it draws. Reaching the bottom
+    /// re-enables auto-follow.
+    pub fn scroll(&mut self, delta: i64) {
+        let current = self.scroll_offset as i64;
+        self.scroll_offset = current.saturating_add(delta).max(0) as usize;
+        self.follow_tail = self.scroll_offset == 0;
+    }
+
+    /// Jump to the oldest visible message. The huge sentinel is clamped
+    /// down to the real top-of-log offset the next time `ui_static` draws.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = usize::MAX / 2;
+        self.follow_tail = false;
+    }
+
+    /// Jump back to the live tail.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = 0;
+        self.follow_tail = true;
+    }
+
+    /// Cycle the severity filter, e.g. bound to the 'f' key.
+    pub fn cycle_severity_filter(&mut self) {
+        self.severity_filter = self.severity_filter.next();
+        // The set of visible messages just changed size; clamp so the
+        // scroll position doesn't point past the new (possibly shorter) log.
+        let total = self.visible_messages().len();
+        self.scroll_offset = self.scroll_offset.min(total);
+    }
+
     pub fn handle_event(&mut self, event: ProcessingEvent) {
         match event {
             ProcessingEvent::PhaseStarted { phase, description } => {
@@ -115,7 +338,7 @@ impl TuiState {
                     self.phases[phase_idx].message = "Starting...".to_string();
                     self.current_phase = phase_idx;
                 }
-                self.messages.push(format!("Started: {}", description));
+                self.push_message(Severity::Info, format!("Started: {}", description));
             }
             ProcessingEvent::Progress { phase, current, total, message } => {
                 let phase_idx = (phase as usize).saturating_sub(1);
@@ -139,10 +362,7 @@ impl TuiState {
                     }
                 }
 
-                self.messages.push(format!("Progress [{}]: {}", phase, message));
-                if self.messages.len() > 100 {
-                    self.messages.remove(0);
-                }
+                self.push_message(Severity::Progress, format!("Progress [{}]: {}", phase, message));
             }
             ProcessingEvent::PhaseCompleted { phase, duration_ms } => {
                 let phase_idx = (phase as usize).saturating_sub(1);
@@ -152,7 +372,7 @@ impl TuiState {
                     self.phases[phase_idx].duration_ms = Some(duration_ms);
                     self.phases[phase_idx].message = format!("Completed in {}ms", duration_ms);
                 }
-                self.messages.push(format!("Completed: Phase {} ({}ms)", phase, duration_ms));
+                self.push_message(Severity::Info, format!("Completed: Phase {} ({}ms)", phase, duration_ms));
             }
             ProcessingEvent::Error { phase, error } => {
                 let phase_idx = (phase as usize).saturating_sub(1);
@@ -160,13 +380,13 @@ impl TuiState {
                     self.phases[phase_idx].status = PhaseStatus::Error;
                     self.phases[phase_idx].message = format!("Error: {}", error);
                 }
-                self.messages.push(format!("Error in Phase {}: {}", phase, error));
+                self.push_message(Severity::Error, format!("Error in Phase {}: {}", phase, error));
             }
             ProcessingEvent::AllCompleted { total_duration_ms, files_extracted } => {
-                self.messages.push(format!("🎉 All processing completed! Extracted {} files in {}ms", files_extracted, total_duration_ms));
-                self.messages.push("Press 'q' to quit".to_string());
+                self.push_message(Severity::Info, format!("🎉 All processing completed! Extracted {} files in {}ms", files_extracted, total_duration_ms));
+                self.push_message(Severity::Info, "Press 'q' to quit".to_string());
             }
-            ProcessingEvent::ChunkStarted { chunk_id, chunk_name } => {
+            ProcessingEvent::ChunkStarted { chunk_id, chunk_name, worker_id } => {
                 let chunk_info = ChunkInfo {
                     id: chunk_id,
                     name: chunk_name.clone(),
@@ -175,94 +395,183 @@ impl TuiState {
                     qr_codes_found: 0,
                     jsonl_file: None,
                     duration_ms: None,
+                    source_offsets: None,
+                    total_frames: None,
+                    fps: 0.0,
+                    eta_secs: None,
+                    log: Vec::new(),
                 };
 
                 // Find existing chunk or add new one
                 if let Some(existing) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
                     existing.status = ChunkStatus::Processing;
+                    push_chunk_log(existing, format!("Started: {}", chunk_name));
                 } else {
                     self.chunks.push(chunk_info);
+                    if let Some(chunk) = self.chunks.last_mut() {
+                        push_chunk_log(chunk, format!("Started: {}", chunk_name));
+                    }
                 }
 
-                self.messages.push(format!("Started processing chunk {}: {}", chunk_id + 1, chunk_name));
+                let lane = self.worker_lane_mut(worker_id);
+                lane.chunk_id = Some(chunk_id);
+                lane.started_at = Some(std::time::Instant::now());
+
+                self.push_message(Severity::Info, format!("Started processing chunk {}: {}", chunk_id + 1, chunk_name));
             }
-            ProcessingEvent::ChunkProgress { chunk_id, frames_processed, qr_codes_found, status } => {
+            ProcessingEvent::ChunkProgress { chunk_id, frames_processed, qr_codes_found, status, fps, eta_secs } => {
                 if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
                     chunk.frames_processed = frames_processed;
                     chunk.qr_codes_found = qr_codes_found;
                     chunk.status = ChunkStatus::Processing;
+                    chunk.fps = fps;
+                    chunk.eta_secs = eta_secs;
+                    push_chunk_log(chunk, format!("{} - {} frames, {} QR codes", status, frames_processed, qr_codes_found));
                 }
-                self.messages.push(format!("Chunk {}: {} - {} frames, {} QR codes", chunk_id + 1, status, frames_processed, qr_codes_found));
+                self.push_message(Severity::Progress, format!("Chunk {}: {} - {} frames, {} QR codes", chunk_id + 1, status, frames_processed, qr_codes_found));
             }
-            ProcessingEvent::ChunkCompleted { chunk_id, qr_codes_found, jsonl_file, duration_ms } => {
+            ProcessingEvent::ChunkCompleted { chunk_id, qr_codes_found, jsonl_file, duration_ms, worker_id } => {
                 if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
                     chunk.status = ChunkStatus::Completed;
                     chunk.qr_codes_found = qr_codes_found;
                     chunk.jsonl_file = Some(jsonl_file.clone());
                     chunk.duration_ms = Some(duration_ms);
+                    push_chunk_log(chunk, format!("✅ Completed: {} QR codes → {} ({}ms)", qr_codes_found, jsonl_file, duration_ms));
+                }
+
+                if let Some(lane) = self.worker_lanes.get_mut(worker_id) {
+                    if lane.chunk_id == Some(chunk_id) {
+                        lane.chunk_id = None;
+                        lane.started_at = None;
+                    }
                 }
-                self.messages.push(format!("✅ Chunk {} completed: {} QR codes → {} ({}ms)", chunk_id + 1, qr_codes_found, jsonl_file, duration_ms));
+
+                self.push_message(Severity::Info, format!("✅ Chunk {} completed: {} QR codes → {} ({}ms)", chunk_id + 1, qr_codes_found, jsonl_file, duration_ms));
             }
             ProcessingEvent::FileReconstructed { file_name, file_size, checksum_valid, output_path } => {
                 let status = if checksum_valid { "✅" } else { "⚠️" };
-                self.messages.push(format!("{} File reconstructed: {} ({} bytes) → {}", status, file_name, file_size, output_path));
+                let severity = if checksum_valid { Severity::Info } else { Severity::Warn };
+                self.push_message(severity, format!("{} File reconstructed: {} ({} bytes) → {}", status, file_name, file_size, output_path));
             }
             ProcessingEvent::ChecksumValidation { file_name, checksum_type, expected, actual, valid } => {
                 let status = if valid { "✅" } else { "❌" };
-                self.messages.push(format!("{} {}: {} (expected: {}, actual: {})", status, checksum_type, file_name, expected, actual));
+                let severity = if valid { Severity::Info } else { Severity::Error };
+                self.push_message(severity, format!("{} {}: {} (expected: {}, actual: {})", status, checksum_type, file_name, expected, actual));
             }
             ProcessingEvent::SystemError { context, error } => {
-                self.messages.push(format!("🚨 System Error in {}: {}", context, error));
-                if self.messages.len() > 100 {
-                    self.messages.remove(0);
-                }
+                self.push_message(Severity::Error, format!("🚨 System Error in {}: {}", context, error));
             }
             ProcessingEvent::InitializationProgress { stage, message } => {
-                self.messages.push(format!("🔧 {}: {}", stage, message));
-                if self.messages.len() > 100 {
-                    self.messages.remove(0);
-                }
+                self.push_message(Severity::Info, format!("🔧 {}: {}", stage, message));
             }
             ProcessingEvent::FinalSummary { files_count, output_dir, total_duration_ms } => {
-                self.messages.push(format!("📊 Final Summary:"));
-                self.messages.push(format!("   Files extracted: {}", files_count));
-                self.messages.push(format!("   Output directory: {}", output_dir));
-                self.messages.push(format!("   Total duration: {}ms", total_duration_ms));
-                self.messages.push("Press 'q' to quit".to_string());
+                self.push_message(Severity::Info, "📊 Final Summary:".to_string());
+                self.push_message(Severity::Info, format!("   Files extracted: {}", files_count));
+                self.push_message(Severity::Info, format!("   Output directory: {}", output_dir));
+                self.push_message(Severity::Info, format!("   Total duration: {}ms", total_duration_ms));
+                self.write_session_report(&output_dir);
+                self.push_message(Severity::Info, "Press 'q' to quit".to_string());
             }
             ProcessingEvent::ModeTransition { from, to, reason } => {
-                self.messages.push(format!("🔄 Mode transition: {} → {} ({})", from, to, reason));
-                if self.messages.len() > 100 {
-                    self.messages.remove(0);
-                }
+                self.push_message(Severity::Warn, format!("🔄 Mode transition: {} → {} ({})", from, to, reason));
             }
-            ProcessingEvent::FrameProgress { chunk_id, frames_processed, total_frames, qr_codes_found } => {
+            ProcessingEvent::FrameProgress { chunk_id, frames_processed, total_frames, qr_codes_found, worker_id, fps, eta_secs } => {
                 // Update chunk-specific frame count
                 if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
                     chunk.frames_processed = frames_processed as usize;
                     chunk.qr_codes_found = qr_codes_found;
+                    chunk.total_frames = Some(total_frames);
+                    chunk.fps = fps;
+                    chunk.eta_secs = eta_secs;
+                }
+
+                // Keep the lane mapping in sync even if this worker's
+                // `ChunkStarted` was missed, so the rate never tracks a
+                // stale chunk.
+                let lane = self.worker_lane_mut(worker_id);
+                if lane.chunk_id != Some(chunk_id) {
+                    lane.chunk_id = Some(chunk_id);
+                    lane.started_at = Some(std::time::Instant::now());
                 }
 
                 // Update total frame progress
                 self.frames_processed = self.chunks.iter().map(|c| c.frames_processed as u64).sum();
+                self.record_throughput_sample(self.frames_processed);
 
                 // Only log significant progress updates to avoid spam
                 if frames_processed % 500 == 0 || frames_processed == total_frames {
                     let progress = (frames_processed as f64 / total_frames as f64 * 100.0).min(100.0);
-                    self.messages.push(format!("📊 Chunk {}: {}/{} frames ({:.1}%) - {} QR codes",
+                    let line = format!("📊 {}/{} frames ({:.1}%) - {} QR codes", frames_processed, total_frames, progress, qr_codes_found);
+                    if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
+                        push_chunk_log(chunk, line);
+                    }
+                    self.push_message(Severity::Progress, format!("📊 Chunk {}: {}/{} frames ({:.1}%) - {} QR codes",
                                               chunk_id + 1, frames_processed, total_frames, progress, qr_codes_found));
-                    if self.messages.len() > 100 {
-                        self.messages.remove(0);
+                }
+            }
+            ProcessingEvent::ChunkRetry { chunk_id, attempt, max_tries, reason } => {
+                if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
+                    push_chunk_log(chunk, format!("🔁 Retrying ({}/{}): {}", attempt, max_tries, reason));
+                }
+                self.push_message(Severity::Warn, format!("🔁 Chunk {} retrying ({}/{}): {}", chunk_id + 1, attempt, max_tries, reason));
+            }
+            ProcessingEvent::ChunkBoundariesPlanned { boundaries, keyframe_aligned } => {
+                let mode = if keyframe_aligned { "keyframe-aligned" } else { "uniform" };
+                for (chunk_id, start_time, end_time) in boundaries {
+                    if let Some(chunk) = self.chunks.iter_mut().find(|c| c.id == chunk_id) {
+                        chunk.source_offsets = Some((start_time, end_time));
+                    } else {
+                        self.chunks.push(ChunkInfo {
+                            id: chunk_id,
+                            name: format!("chunk_{:03}", chunk_id + 1),
+                            status: ChunkStatus::Pending,
+                            frames_processed: 0,
+                            qr_codes_found: 0,
+                            jsonl_file: None,
+                            duration_ms: None,
+                            source_offsets: Some((start_time, end_time)),
+                            total_frames: None,
+                            fps: 0.0,
+                            eta_secs: None,
+                            log: Vec::new(),
+                        });
                     }
                 }
+                self.push_message(Severity::Info, format!("✂️  Planned {} {} chunk boundaries", boundaries.len(), mode));
+            }
+            ProcessingEvent::GracefulStop { chunks_completed, chunks_total, resume_command, output_dir } => {
+                self.push_message(Severity::Warn, format!("🛑 Stopped gracefully after {}/{} chunks", chunks_completed, chunks_total));
+                self.push_message(Severity::Info, format!("   To continue: {}", resume_command));
+                self.write_session_report(&output_dir);
+                self.push_message(Severity::Info, "Press 'q' to quit".to_string());
             }
         }
     }
+
+    /// Write the session report (JSON + Markdown) to `output_dir`, logging a
+    /// message either way rather than letting a write failure interrupt the
+    /// run - the report is a convenience artifact, not load-bearing state.
+    fn write_session_report(&mut self, output_dir: &str) {
+        let report = crate::session_report::SessionReport::from_state(self);
+        match report.write(std::path::Path::new(output_dir)) {
+            Ok(()) => self.push_message(
+                Severity::Info,
+                format!("📝 Session report written to {}/session_report.json", output_dir),
+            ),
+            Err(e) => self.push_message(Severity::Error, format!("Failed to write session report: {}", e)),
+        }
+    }
 }
 
 pub struct TuiManager {
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
-    state: Arc<Mutex<TuiState>>,
+    control: Arc<PipelineControl>,
+    /// Feeds `run()`'s merged event loop - `get_callback` hands out cloned
+    /// senders to the background processing threads, so `ProcessingEvent`s
+    /// arrive as just another branch of the same `select!` as key input and
+    /// the tick timer, instead of racing worker threads over a state mutex.
+    event_tx: tokio::sync::mpsc::UnboundedSender<ProcessingEvent>,
+    event_rx: tokio::sync::mpsc::UnboundedReceiver<ProcessingEvent>,
 }
 
 impl TuiManager {
@@ -292,9 +601,10 @@ impl TuiManager {
                 anyhow!("Failed to create terminal: {}", e)
             })?;
 
-        let state = Arc::new(Mutex::new(TuiState::new()));
+        let control = PipelineControl::new();
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
 
-        Ok(Self { terminal, state })
+        Ok(Self { terminal, control, event_tx, event_rx })
     }
 
     pub fn new_forced() -> Result<Self> {
@@ -318,64 +628,133 @@ impl TuiManager {
                 anyhow!("Failed to create terminal: {}", e)
             })?;
 
-        let state = Arc::new(Mutex::new(TuiState::new()));
+        let control = PipelineControl::new();
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
 
-        Ok(Self { terminal, state })
+        Ok(Self { terminal, control, event_tx, event_rx })
     }
 
     pub fn get_callback(&self) -> EventCallback {
-        let state = Arc::clone(&self.state);
+        let event_tx = self.event_tx.clone();
         Box::new(move |event| {
-            if let Ok(mut state) = state.lock() {
-                state.handle_event(event);
-            }
+            // The receiver only goes away once `run()` returns, at which
+            // point there's no one left to draw an update anyway.
+            let _ = event_tx.send(event);
         })
     }
 
+    /// Hand out the pause/resume back-channel so the worker pipeline can be
+    /// wired to the keybindings handled in `run()`.
+    pub fn get_control(&self) -> Arc<PipelineControl> {
+        Arc::clone(&self.control)
+    }
+
     pub fn run(&mut self) -> Result<()> {
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| anyhow!("Failed to start TUI async runtime: {}", e))?;
+        runtime.block_on(self.run_async())
+    }
+
+    /// The merged event loop: terminal input, `ProcessingEvent`s forwarded
+    /// from `get_callback`, and a tick timer all feed the same `select!`, so
+    /// a redraw only happens when one of them actually has something to
+    /// show (dirty-redraw) instead of on a fixed wall-clock poll.
+    async fn run_async(&mut self) -> Result<()> {
         let tick_rate = Duration::from_millis(250);
-        let mut last_tick = Instant::now();
+        let mut state = TuiState::new();
+        let mut terminal_events = EventStream::new();
+        let mut ticker = tokio::time::interval(tick_rate);
 
-        loop {
-            let state_clone = Arc::clone(&self.state);
-            self.terminal.draw(|f| {
-                let state = state_clone.lock().unwrap();
-                Self::ui_static(&state, f);
-            })?;
+        self.terminal.draw(|f| Self::ui_static(&mut state, f))?;
 
-            let timeout = tick_rate
-                .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0));
+        loop {
+            let event = futures::select! {
+                term_event = terminal_events.next().fuse() => match term_event {
+                    Some(Ok(CtEvent::Key(key))) => Event::Key(key),
+                    Some(Ok(CtEvent::Resize(w, h))) => Event::Resize(w, h),
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(anyhow!("Terminal event stream error: {}", e)),
+                    None => break,
+                },
+                processing = self.event_rx.recv().fuse() => match processing {
+                    Some(processing_event) => Event::Processing(processing_event),
+                    None => continue,
+                },
+                _ = ticker.tick().fuse() => Event::Tick,
+            };
 
-            if crossterm::event::poll(timeout)? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => {
-                            if let Ok(mut state) = self.state.lock() {
-                                state.should_quit = true;
-                            }
-                            break;
+            let mut dirty = matches!(event, Event::Key(_));
+            match event {
+                Event::Key(key) => match key.code {
+                    KeyCode::Esc => {
+                        if state.show_detail {
+                            state.show_detail = false;
+                        } else {
+                            state.should_quit = true;
                         }
-                        _ => {}
                     }
+                    KeyCode::Char('q') => {
+                        state.should_quit = true;
+                    }
+                    KeyCode::Char(' ') => {
+                        state.paused = self.control.toggle_paused();
+                    }
+                    // Graceful cancel: in-flight chunks finish and flush
+                    // normally (`PipelineControl::is_stopping` is only
+                    // checked before a worker picks up its *next* chunk),
+                    // so already-extracted data is never lost.
+                    KeyCode::Char('c') => {
+                        self.control.request_stop();
+                        state.cancelling = true;
+                    }
+                    KeyCode::Up => {
+                        state.selected_chunk_index = state.selected_chunk_index.saturating_sub(1);
+                    }
+                    KeyCode::Down => {
+                        let max_index = state.chunks.len().saturating_sub(1);
+                        if state.selected_chunk_index < max_index {
+                            state.selected_chunk_index += 1;
+                        }
+                    }
+                    KeyCode::Enter | KeyCode::Char('d') => {
+                        state.show_detail = !state.show_detail;
+                    }
+                    // 'j'/'k' and Page Up/Down scroll the message log rather
+                    // than the chunk timeline (arrows above do that), 'g'/'G'
+                    // jump to its ends, and 'f' cycles the severity filter.
+                    KeyCode::Char('k') => state.scroll(1),
+                    KeyCode::Char('j') => state.scroll(-1),
+                    KeyCode::PageUp => state.scroll(10),
+                    KeyCode::PageDown => state.scroll(-10),
+                    KeyCode::Char('g') => state.scroll_to_top(),
+                    KeyCode::Char('G') => state.scroll_to_bottom(),
+                    KeyCode::Char('f') => state.cycle_severity_filter(),
+                    _ => {}
+                },
+                Event::Resize(_, _) => dirty = true,
+                Event::Processing(processing_event) => {
+                    state.handle_event(processing_event);
+                    dirty = true;
                 }
+                Event::Tick => dirty = true,
             }
 
-            if last_tick.elapsed() >= tick_rate {
-                last_tick = Instant::now();
+            if dirty {
+                self.terminal.draw(|f| Self::ui_static(&mut state, f))?;
             }
 
-            if let Ok(state) = self.state.lock() {
-                if state.should_quit {
-                    break;
-                }
+            if state.should_quit {
+                break;
             }
         }
 
         Ok(())
     }
 
-    fn ui_static(state: &TuiState, f: &mut Frame) {
+    fn ui_static(state: &mut TuiState, f: &mut Frame) {
+
+        let lane_count = state.worker_lanes.len();
+        let lanes_height = if lane_count > 0 { lane_count as u16 + 2 } else { 0 };
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -383,8 +762,9 @@ impl TuiManager {
             .constraints([
                 Constraint::Length(3),          // Title
                 Constraint::Length(6),          // Phases
-                Constraint::Min(10),            // Chunk tracking (more space)
-                Constraint::Length(4),          // Messages (compact)
+                Constraint::Length(lanes_height), // Worker lanes (one row per worker)
+                Constraint::Min(6),              // Chunk tracking
+                Constraint::Min(8),              // Message log (full-height, scrollable)
                 Constraint::Length(3),          // Status bar
             ])
             .split(f.size());
@@ -438,11 +818,49 @@ impl TuiManager {
             .block(Block::default().borders(Borders::ALL).title("Processing Phases"));
         f.render_widget(phases_list, chunks[1]);
 
-        // Render chunk tracking section
+        // Render the worker-lane view: one row per `ChunkBroker` consumer,
+        // showing which chunk it's currently decoding, its frame count, and
+        // its live QR/sec rate, so a stalled worker is visible at a glance.
+        if lane_count > 0 {
+            let lane_items: Vec<ListItem> = state
+                .worker_lanes
+                .iter()
+                .enumerate()
+                .map(|(worker_id, lane)| {
+                    let line = match lane.chunk_id {
+                        Some(chunk_id) => {
+                            let chunk = state.chunks.iter().find(|c| c.id == chunk_id);
+                            let frames = chunk.map(|c| c.frames_processed).unwrap_or(0);
+                            let qrs = chunk.map(|c| c.qr_codes_found).unwrap_or(0);
+                            let rate = lane
+                                .started_at
+                                .map(|start| {
+                                    let secs = start.elapsed().as_secs_f64();
+                                    if secs > 0.0 { qrs as f64 / secs } else { 0.0 }
+                                })
+                                .unwrap_or(0.0);
+                            format!("Worker {}: chunk {} - {} frames, {} QR ({:.1} QR/s)",
+                                   worker_id + 1, chunk_id + 1, frames, qrs, rate)
+                        }
+                        None => format!("Worker {}: idle", worker_id + 1),
+                    };
+                    ListItem::new(Line::from(line))
+                })
+                .collect();
+
+            let lanes_list = List::new(lane_items)
+                .block(Block::default().borders(Borders::ALL).title("Worker Lanes"));
+            f.render_widget(lanes_list, chunks[2]);
+        }
+
+        // Render chunk tracking section: the scrubbable timeline. The
+        // selected row is highlighted so the arrow keys visibly
+        // move a cursor the detail pane below then reads from.
         let chunk_items: Vec<ListItem> = state
             .chunks
             .iter()
-            .map(|chunk| {
+            .enumerate()
+            .map(|(i, chunk)| {
                 let status_char = match chunk.status {
                     ChunkStatus::Pending => "⏸",
                     ChunkStatus::Processing => "⏳",
@@ -450,13 +868,17 @@ impl TuiManager {
                     ChunkStatus::Error => "❌",
                 };
 
-                let style = match chunk.status {
+                let mut style = match chunk.status {
                     ChunkStatus::Pending => Style::default().fg(Color::Gray),
                     ChunkStatus::Processing => Style::default().fg(Color::Yellow),
                     ChunkStatus::Completed => Style::default().fg(Color::Green),
                     ChunkStatus::Error => Style::default().fg(Color::Red),
                 };
 
+                if i == state.selected_chunk_index {
+                    style = style.bg(Color::DarkGray);
+                }
+
                 // Compact duration display (convert ms to minutes:seconds)
                 let duration_info = chunk.duration_ms
                     .map(|d| {
@@ -465,67 +887,161 @@ impl TuiManager {
                     })
                     .unwrap_or_default();
 
+                let cursor = if i == state.selected_chunk_index { ">" } else { " " };
+
                 // Compact display: no redundant JSONL filename
                 ListItem::new(Line::from(vec![
-                    Span::styled(format!("{} Chunk {}: {} QR codes{}",
-                                       status_char, chunk.id + 1, chunk.qr_codes_found, duration_info), style),
+                    Span::styled(format!("{} {} Chunk {}: {} QR codes{}",
+                                       cursor, status_char, chunk.id + 1, chunk.qr_codes_found, duration_info), style),
                 ]))
             })
             .collect();
 
         let chunks_list = List::new(chunk_items)
-            .block(Block::default().borders(Borders::ALL).title("Chunk Processing"));
-        f.render_widget(chunks_list, chunks[2]);
+            .block(Block::default().borders(Borders::ALL).title("Chunk Processing (↑/↓ select, Enter detail)"));
+        f.render_widget(chunks_list, chunks[3]);
 
-        let messages: Vec<ListItem> = state
-            .messages
-            .iter()
-            .rev()
-            .take(3)
-            .map(|m| ListItem::new(m.as_str()))
-            .collect();
+        // The bottom info panel shows either the global rolling message log,
+        // or (when a chunk is selected for inspection) that chunk's own
+        // recent log lines plus its frame/QR counts.
+        if state.show_detail {
+            let selected = state.chunks.get(state.selected_chunk_index);
+            let detail_items: Vec<ListItem> = match selected {
+                Some(chunk) => {
+                    let offsets_info = chunk.source_offsets
+                        .map(|(start, end)| format!(", {:.1}s-{:.1}s", start, end))
+                        .unwrap_or_default();
+                    let rate_info = if chunk.fps > 0.0 {
+                        let eta = chunk.eta_secs.map(|s| format!(", ETA {:02}:{:02}", s / 60, s % 60)).unwrap_or_default();
+                        format!(", {:.1} f/s{}", chunk.fps, eta)
+                    } else {
+                        String::new()
+                    };
+                    let header = format!(
+                        "Chunk {} ({}): {} frames, {} QR codes{}{}",
+                        chunk.id + 1, chunk.name, chunk.frames_processed, chunk.qr_codes_found, offsets_info, rate_info
+                    );
+                    std::iter::once(ListItem::new(header))
+                        .chain(chunk.log.iter().rev().take(3).map(|l| ListItem::new(l.as_str())))
+                        .collect()
+                }
+                None => vec![ListItem::new("No chunk selected")],
+            };
 
-        let messages_list = List::new(messages)
-            .block(Block::default().borders(Borders::ALL).title("Recent Messages"));
-        f.render_widget(messages_list, chunks[3]);
+            let detail_list = List::new(detail_items)
+                .block(Block::default().borders(Borders::ALL).title("Chunk Detail (Esc to close)"));
+            f.render_widget(detail_list, chunks[4]);
+        } else {
+            let area = chunks[4];
+            let inner_height = area.height.saturating_sub(2) as usize;
+
+            let total = state.visible_messages().len();
+            let max_offset = total.saturating_sub(inner_height);
+            // Clamp back into `state` so the next key press's delta builds
+            // on the real offset instead of a scroll-to-top sentinel.
+            state.scroll_offset = state.scroll_offset.min(max_offset);
+            let offset = state.scroll_offset;
+
+            let end = total - offset;
+            let start = end.saturating_sub(inner_height);
+
+            let visible = state.visible_messages();
+            let message_items: Vec<ListItem> = visible[start..end]
+                .iter()
+                .map(|(severity, text)| {
+                    ListItem::new(Line::from(Span::styled(
+                        text.clone(),
+                        Style::default().fg(severity.color()),
+                    )))
+                })
+                .collect();
+
+            let title = format!(
+                "Log [{}+] ('f' filter, j/k/PgUp/PgDn scroll, g/G top/bottom){}",
+                state.severity_filter.label(),
+                if state.follow_tail { "" } else { " (scrolled)" },
+            );
+
+            let messages_list = List::new(message_items)
+                .block(Block::default().borders(Borders::ALL).title(title));
+            f.render_widget(messages_list, area);
+
+            if max_offset > 0 {
+                let mut scrollbar_state = ScrollbarState::new(max_offset).position(max_offset - offset);
+                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+                f.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+            }
+        }
 
         // Status bar with controls and frame progress
         let total_qr_codes: usize = state.chunks.iter().map(|c| c.qr_codes_found).sum();
         let completed_chunks = state.chunks.iter().filter(|c| c.status == ChunkStatus::Completed).count();
         let total_chunks = state.chunks.len();
 
+        let pause_suffix = if state.paused { " | ⏸ PAUSED (space resume)" } else { " | space pause" };
+        let cancel_suffix = if state.cancelling { " | ⏹ CANCELLING (finishing in-flight chunks)" } else { " | 'c' cancel" };
+        let controls_suffix = format!("{}{} | ↑/↓ select | Enter detail | j/k scroll log | 'f' filter | 'q' quit", pause_suffix, cancel_suffix);
+
         let status_text = if let Some(start_time) = state.start_time {
             let elapsed = start_time.elapsed();
             let elapsed_str = format!("{:02}:{:02}", elapsed.as_secs() / 60, elapsed.as_secs() % 60);
 
-            if state.total_frames > 0 && state.frames_processed > 0 {
+            let base = if state.total_frames > 0 && state.frames_processed > 0 {
                 let frame_progress = (state.frames_processed as f64 / state.total_frames as f64 * 100.0).min(100.0);
                 let remaining_frames = state.total_frames.saturating_sub(state.frames_processed);
-                let frames_per_sec = state.frames_processed as f64 / elapsed.as_secs_f64();
-                let remaining_secs = if frames_per_sec > 0.0 {
-                    (remaining_frames as f64 / frames_per_sec) as u64
+                // The smoothed windowed rate reacts to real-time throughput
+                // changes; fall back to the cumulative average until enough
+                // samples have landed to fill the window.
+                let frames_per_sec = if state.throughput_ema > 0.0 {
+                    state.throughput_ema
                 } else {
-                    0
+                    state.frames_processed as f64 / elapsed.as_secs_f64()
                 };
+                // Prefer aggregating each chunk's own rate (weighted by its
+                // remaining frames) once chunks have reported enough
+                // samples; fall back to the cruder whole-run average until
+                // they have.
+                let per_chunk_rates: Vec<(f64, u64)> = state.chunks.iter()
+                    .filter_map(|c| {
+                        let total = c.total_frames?;
+                        Some((c.fps, total.saturating_sub(c.frames_processed as u64)))
+                    })
+                    .collect();
+                let remaining_secs = crate::progress_estimator::aggregate_eta_secs(&per_chunk_rates)
+                    .unwrap_or_else(|| {
+                        if frames_per_sec > 0.0 {
+                            (remaining_frames as f64 / frames_per_sec) as u64
+                        } else {
+                            0
+                        }
+                    });
                 let remaining_str = format!("{:02}:{:02}", remaining_secs / 60, remaining_secs % 60);
 
-                format!("Frames: {}/{} ({:.1}%) | Chunks: {}/{} | QR: {} | Time: {}/-{} | 'q' to quit",
+                format!("Frames: {}/{} ({:.1}%) | Chunks: {}/{} | QR: {} | {:.1} f/s | Time: {}/-{}",
                        state.frames_processed, state.total_frames, frame_progress,
-                       completed_chunks, total_chunks, total_qr_codes, elapsed_str, remaining_str)
+                       completed_chunks, total_chunks, total_qr_codes, frames_per_sec, elapsed_str, remaining_str)
             } else if total_chunks > 0 {
-                format!("Chunks: {}/{} | QR: {} | Time: {} | 'q' to quit",
+                format!("Chunks: {}/{} | QR: {} | Time: {}",
                        completed_chunks, total_chunks, total_qr_codes, elapsed_str)
             } else {
-                format!("Time: {} | 'q' to quit", elapsed_str)
-            }
+                format!("Time: {}", elapsed_str)
+            };
+            format!("{}{}", base, controls_suffix)
         } else {
-            "Press 'q' or 'Esc' to quit | Processing will begin shortly...".to_string()
+            format!("Processing will begin shortly...{}", controls_suffix)
         };
 
+        let status_color = if state.cancelling {
+            Color::Red
+        } else if state.paused {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
         let status_bar = Paragraph::new(status_text)
             .block(Block::default().borders(Borders::ALL).title("Status & Controls"))
-            .style(Style::default().fg(Color::Green));
-        f.render_widget(status_bar, chunks[4]);
+            .style(Style::default().fg(status_color));
+        f.render_widget(status_bar, chunks[5]);
     }
 }
 
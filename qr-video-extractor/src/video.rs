@@ -1,7 +1,10 @@
 use anyhow::{anyhow, Result};
 use ffmpeg_next as ffmpeg;
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::chunk_broker::ChunkBroker;
+use crate::error_handler::ErrorHandler;
 use crate::events::{EventCallback, ProcessingEvent};
 
 #[derive(Debug, Clone)]
@@ -21,11 +24,19 @@ pub struct VideoChunk {
     pub start_time: f64,
     pub duration: f64,
     pub end_time: f64,
+    /// Whether `start_time`/`end_time` were snapped to the nearest preceding
+    /// keyframe (see `VideoProcessor::probe_keyframe_times`) rather than
+    /// falling on an arithmetic boundary that `-c copy` might not decode
+    /// cleanly.
+    pub keyframe_aligned: bool,
 }
 
 pub struct VideoProcessor {
     input_path: PathBuf,
     video_info: Option<VideoInfo>,
+    keyframe_times: Option<Vec<f64>>,
+    max_tries: u32,
+    error_handler: Option<Arc<ErrorHandler>>,
 }
 
 impl VideoProcessor {
@@ -38,9 +49,147 @@ impl VideoProcessor {
         Ok(Self {
             input_path: input_path.clone(),
             video_info: None,
+            keyframe_times: None,
+            max_tries: 3,
+            error_handler: None,
         })
     }
 
+    /// Attempts allowed per chunk, via the `ChunkBroker`, before
+    /// `create_chunk_files` gives up splitting it and moves on.
+    pub fn with_max_tries(mut self, max_tries: u32) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// Route every retried or abandoned chunk split through `handler`'s
+    /// `processing.log`, in addition to the `ProcessingEvent`s already sent
+    /// over `callback`.
+    pub fn with_error_handler(mut self, handler: Arc<ErrorHandler>) -> Self {
+        self.error_handler = Some(handler);
+        self
+    }
+
+    /// Probe the input's keyframe (IDR) timestamps once via `ffprobe
+    /// -skip_frame nokey`, so `split_by_count`/`split_by_duration` can snap
+    /// their arithmetic chunk boundaries onto one instead of cutting
+    /// mid-GOP. With stream copy (`-c copy`), a boundary that doesn't land
+    /// on a keyframe produces leading frames in the next chunk that
+    /// reference GOP state from the previous one and decode as garbage -
+    /// for a QR pipeline, that's QR codes silently lost at every chunk
+    /// seam. Call this before splitting to opt into keyframe-aligned mode;
+    /// skip it and boundaries stay purely arithmetic, as before.
+    pub fn probe_keyframe_times(&mut self, callback: &EventCallback) -> Result<()> {
+        use std::process::Command;
+
+        callback(ProcessingEvent::Progress {
+            phase: 1,
+            current: 1,
+            total: 4,
+            message: "Probing keyframe timestamps...".to_string(),
+        });
+
+        let output = Command::new("ffprobe")
+            .arg("-v")
+            .arg("error")
+            .arg("-select_streams")
+            .arg("v")
+            .arg("-skip_frame")
+            .arg("nokey")
+            .arg("-show_entries")
+            .arg("frame=pkt_pts_time")
+            .arg("-of")
+            .arg("csv=p=0")
+            .arg(&self.input_path)
+            .output()
+            .map_err(|e| anyhow!("Failed to execute ffprobe: {}", e))?;
+
+        for line in StringOrBytes::from_raw(output.stderr.clone()).as_str_lossy().lines() {
+            report_tool_line(line, "ffprobe", None, self.error_handler.as_ref(), callback);
+        }
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("ffprobe keyframe probe failed: {}", stderr));
+        }
+
+        let mut times: Vec<f64> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().parse::<f64>().ok())
+            .collect();
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        callback(ProcessingEvent::Progress {
+            phase: 1,
+            current: 2,
+            total: 4,
+            message: format!("Found {} keyframes", times.len()),
+        });
+
+        self.keyframe_times = Some(times);
+        Ok(())
+    }
+
+    /// Snap each interior boundary in `raw_boundaries` to the greatest
+    /// keyframe time `<=` it, leaving the first (0) and last (video
+    /// duration) untouched since those aren't mid-stream cuts. No-op when
+    /// `probe_keyframe_times` hasn't been called. Duplicate boundaries
+    /// produced by snapping (two requested cuts landing on the same
+    /// keyframe) collapse into one, so the resulting chunk count can be
+    /// smaller than requested but every chunk still decodes cleanly.
+    fn snap_chunk_boundaries(&self, raw_boundaries: &[f64], callback: &EventCallback) -> Vec<f64> {
+        let Some(keyframe_times) = &self.keyframe_times else {
+            return raw_boundaries.to_vec();
+        };
+
+        let last = raw_boundaries.len() - 1;
+        let mut snapped = Vec::with_capacity(raw_boundaries.len());
+        for (i, &t) in raw_boundaries.iter().enumerate() {
+            let snapped_t = if i == 0 || i == last {
+                t
+            } else {
+                keyframe_times
+                    .iter()
+                    .rev()
+                    .find(|&&k| k <= t)
+                    .copied()
+                    .unwrap_or(0.0)
+            };
+
+            if (snapped_t - t).abs() > f64::EPSILON {
+                callback(ProcessingEvent::Progress {
+                    phase: 1,
+                    current: i,
+                    total: raw_boundaries.len(),
+                    message: format!("Snapped chunk boundary {:.3}s to keyframe at {:.3}s", t, snapped_t),
+                });
+            }
+
+            snapped.push(snapped_t);
+        }
+
+        snapped.dedup();
+        snapped
+    }
+
+    fn chunks_from_boundaries(&self, boundaries: &[f64], output_dir: &PathBuf) -> Vec<VideoChunk> {
+        boundaries
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let (start_time, end_time) = (pair[0], pair[1]);
+                VideoChunk {
+                    id: i,
+                    path: output_dir.join(format!("chunk_{:03}.mp4", i + 1)),
+                    start_time,
+                    duration: end_time - start_time,
+                    end_time,
+                    keyframe_aligned: self.keyframe_times.is_some(),
+                }
+            })
+            .collect()
+    }
+
     pub fn get_video_info(&mut self, callback: &EventCallback) -> Result<VideoInfo> {
         callback(ProcessingEvent::Progress {
             phase: 1,
@@ -49,6 +198,20 @@ impl VideoProcessor {
             message: "Opening video file...".to_string(),
         });
 
+        #[cfg(feature = "mp4-native")]
+        if let Ok(video_info) = crate::mp4_demux::probe_video_info(&self.input_path) {
+            callback(ProcessingEvent::Progress {
+                phase: 1,
+                current: 2,
+                total: 4,
+                message: format!("Video analyzed (native mp4 reader): {}x{}, {:.1}fps, {:.1}s, {} frames",
+                               video_info.width, video_info.height, video_info.fps,
+                               video_info.duration, video_info.total_frames),
+            });
+            self.video_info = Some(video_info.clone());
+            return Ok(video_info);
+        }
+
         let ictx = ffmpeg::format::input(&self.input_path)
             .map_err(|e| anyhow!("Failed to open video file: {}", e))?;
 
@@ -103,7 +266,7 @@ impl VideoProcessor {
         Ok(video_info)
     }
 
-    pub fn split_by_count(&self, chunk_count: usize, callback: &EventCallback) -> Result<Vec<VideoChunk>> {
+    pub fn split_by_count(&self, chunk_count: usize, output_dir: &PathBuf, callback: &EventCallback) -> Result<Vec<VideoChunk>> {
         let video_info = self.video_info.as_ref()
             .ok_or_else(|| anyhow!("Video info not available. Call get_video_info first."))?;
 
@@ -115,26 +278,13 @@ impl VideoProcessor {
         });
 
         let chunk_duration = video_info.duration / chunk_count as f64;
-        let mut chunks = Vec::with_capacity(chunk_count);
-
-        for i in 0..chunk_count {
-            let start_time = i as f64 * chunk_duration;
-            let end_time = if i == chunk_count - 1 {
-                video_info.duration
-            } else {
-                (i + 1) as f64 * chunk_duration
-            };
+        let raw_boundaries: Vec<f64> = (0..=chunk_count)
+            .map(|i| if i == chunk_count { video_info.duration } else { i as f64 * chunk_duration })
+            .collect();
 
-            let chunk_path = PathBuf::from(format!("chunk_{:03}.mp4", i + 1));
-
-            chunks.push(VideoChunk {
-                id: i,
-                path: chunk_path,
-                start_time,
-                duration: end_time - start_time,
-                end_time,
-            });
-        }
+        let boundaries = self.snap_chunk_boundaries(&raw_boundaries, callback);
+        let chunks = self.chunks_from_boundaries(&boundaries, output_dir);
+        self.emit_chunk_boundaries(&chunks, callback);
 
         self.create_chunk_files(&chunks, callback)?;
 
@@ -148,7 +298,7 @@ impl VideoProcessor {
         Ok(chunks)
     }
 
-    pub fn split_by_duration(&self, duration_per_chunk: f64, callback: &EventCallback) -> Result<Vec<VideoChunk>> {
+    pub fn split_by_duration(&self, duration_per_chunk: f64, output_dir: &PathBuf, callback: &EventCallback) -> Result<Vec<VideoChunk>> {
         let video_info = self.video_info.as_ref()
             .ok_or_else(|| anyhow!("Video info not available. Call get_video_info first."))?;
 
@@ -162,22 +312,13 @@ impl VideoProcessor {
                            duration_per_chunk, chunk_count),
         });
 
-        let mut chunks = Vec::with_capacity(chunk_count);
+        let raw_boundaries: Vec<f64> = (0..=chunk_count)
+            .map(|i| ((i as f64 * duration_per_chunk).min(video_info.duration)))
+            .collect();
 
-        for i in 0..chunk_count {
-            let start_time = i as f64 * duration_per_chunk;
-            let end_time = ((i + 1) as f64 * duration_per_chunk).min(video_info.duration);
-
-            let chunk_path = PathBuf::from(format!("chunk_{:03}.mp4", i + 1));
-
-            chunks.push(VideoChunk {
-                id: i,
-                path: chunk_path,
-                start_time,
-                duration: end_time - start_time,
-                end_time,
-            });
-        }
+        let boundaries = self.snap_chunk_boundaries(&raw_boundaries, callback);
+        let chunks = self.chunks_from_boundaries(&boundaries, output_dir);
+        self.emit_chunk_boundaries(&chunks, callback);
 
         self.create_chunk_files(&chunks, callback)?;
 
@@ -191,30 +332,184 @@ impl VideoProcessor {
         Ok(chunks)
     }
 
+    /// Emit the finalized per-chunk start/end offsets so the TUI can show
+    /// real timestamps instead of dividing `duration` by `chunk_count`.
+    fn emit_chunk_boundaries(&self, chunks: &[VideoChunk], callback: &EventCallback) {
+        callback(ProcessingEvent::ChunkBoundariesPlanned {
+            boundaries: chunks.iter().map(|c| (c.id, c.start_time, c.end_time)).collect(),
+            keyframe_aligned: self.keyframe_times.is_some(),
+        });
+    }
+
+    /// Dispatch one `split_video_segment_embedded` call per chunk across a
+    /// `ChunkBroker`, instead of a strictly sequential loop that aborts the
+    /// whole split on the first ffmpeg failure. A chunk that fails is
+    /// retried with backoff up to `max_tries` times; one still failing after
+    /// that is reported via `ProcessingEvent::Error` and skipped, so a
+    /// single bad cut doesn't take down every other chunk's splitting.
     fn create_chunk_files(&self, chunks: &[VideoChunk], callback: &EventCallback) -> Result<()> {
-        for (idx, chunk) in chunks.iter().enumerate() {
-            self.split_video_segment_embedded(chunk)?;
+        let total = chunks.len();
+        let items: Vec<(usize, VideoChunk)> = chunks.iter().cloned().map(|c| (c.id, c)).collect();
+        let worker_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).min(total.max(1));
+        let broker = ChunkBroker::new(worker_count, self.max_tries);
+
+        let work = |_chunk_id: usize, chunk: &VideoChunk, _worker_id: usize| -> Result<()> {
+            self.split_video_segment_embedded(chunk, callback)
+        };
 
+        let on_success = |_chunk_id: usize, chunk: &VideoChunk, ()| {
             callback(ProcessingEvent::Progress {
                 phase: 1,
                 current: 3,
                 total: 4,
                 message: format!("Created chunk {} of {} ({:.1}s-{:.1}s)",
-                               idx + 1, chunks.len(), chunk.start_time, chunk.end_time),
+                               chunk.id + 1, total, chunk.start_time, chunk.end_time),
             });
-        }
+        };
+
+        let on_failure = |chunk_id: usize, _chunk: &VideoChunk, error: &str, attempts: u32| {
+            if let Some(handler) = &self.error_handler {
+                handler.handle_ffmpeg_error(chunk_id, "split", error);
+            }
+            callback(ProcessingEvent::Error {
+                phase: 1,
+                error: format!("Chunk {} failed to split after {} attempt(s): {}", chunk_id + 1, attempts, error),
+            });
+        };
+
+        let on_retry = |chunk_id: usize, _chunk: &VideoChunk, attempt: u32, max_tries: u32, reason: &str| {
+            if let Some(handler) = &self.error_handler {
+                handler.handle_ffmpeg_error(chunk_id, "split", reason);
+            }
+            callback(ProcessingEvent::ChunkRetry {
+                chunk_id,
+                attempt,
+                max_tries,
+                reason: reason.to_string(),
+            });
+        };
+
+        broker.run(items, work, on_success, on_failure, on_retry);
 
         Ok(())
     }
 
-    fn split_video_segment_embedded(&self, chunk: &VideoChunk) -> Result<()> {
-        // Use external ffmpeg to avoid borrowing issues
-        use std::process::Command;
+    /// Spawn ffmpeg's own segment muxer to cut `input` (a real path, or `-`
+    /// for this process's stdin) into fixed-duration `.mp4` segments under
+    /// `output_dir` as data arrives, instead of requiring the whole input
+    /// up front like `split_by_count`/`split_by_duration`.
+    pub fn spawn_streaming_segmenter(
+        input: &PathBuf,
+        output_dir: &PathBuf,
+        duration_per_chunk: f64,
+    ) -> Result<std::process::Child> {
+        use std::process::{Command, Stdio};
+
+        let ffmpeg_input: &std::ffi::OsStr = if input.as_os_str() == "-" {
+            std::ffi::OsStr::new("pipe:0")
+        } else {
+            input.as_os_str()
+        };
+
+        let child = Command::new("ffmpeg")
+            .arg("-i")
+            .arg(ffmpeg_input)
+            .arg("-c")
+            .arg("copy")
+            .arg("-f")
+            .arg("segment")
+            .arg("-segment_time")
+            .arg(format!("{:.3}", duration_per_chunk))
+            .arg("-segment_start_number")
+            .arg("1")
+            .arg("-reset_timestamps")
+            .arg("1")
+            .arg("-y")
+            .arg(output_dir.join("chunk_%03d.mp4"))
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to spawn streaming ffmpeg segmenter: {}", e))?;
+
+        Ok(child)
+    }
+
+    /// Poll `output_dir` for segments the streaming segmenter has finished
+    /// writing: a segment is considered done once ffmpeg has moved on to
+    /// the next index, or the segmenter has exited (the final segment).
+    /// Each finalized segment is sent as a `VideoChunk` over the returned
+    /// channel so the caller can start QR extraction on it immediately.
+    pub fn watch_streaming_segments(
+        mut child: std::process::Child,
+        output_dir: PathBuf,
+        duration_per_chunk: f64,
+    ) -> std::sync::mpsc::Receiver<VideoChunk> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let mut next_id = 0usize;
+            loop {
+                let exited = matches!(child.try_wait(), Ok(Some(_)));
+                let current_segment = output_dir.join(format!("chunk_{:03}.mp4", next_id + 1));
+                let next_segment = output_dir.join(format!("chunk_{:03}.mp4", next_id + 2));
+
+                if current_segment.exists() && (next_segment.exists() || exited) {
+                    let start_time = next_id as f64 * duration_per_chunk;
+                    let chunk = VideoChunk {
+                        id: next_id,
+                        path: current_segment,
+                        start_time,
+                        duration: duration_per_chunk,
+                        end_time: start_time + duration_per_chunk,
+                        keyframe_aligned: false,
+                    };
+                    if tx.send(chunk).is_err() {
+                        break;
+                    }
+                    next_id += 1;
+                    continue;
+                }
+
+                if exited {
+                    break;
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(250));
+            }
+        });
+
+        rx
+    }
+
+    /// Split one chunk via external ffmpeg, driving real sub-chunk
+    /// `ProcessingEvent::Progress` updates off `-progress pipe:1` instead of
+    /// emitting a single event once the whole chunk is done, and attaching
+    /// the last lines of stderr to the returned error on failure (via
+    /// `StringOrBytes`, so a non-UTF8 diagnostic - an unusual filename in an
+    /// ffmpeg log line - doesn't panic the capture). Under the `mp4-native`
+    /// feature, `mp4_demux::split_chunk` is tried first and this external
+    /// path only runs if that fails (a non-mp4 container, an unsupported
+    /// box layout, and so on).
+    fn split_video_segment_embedded(&self, chunk: &VideoChunk, callback: &EventCallback) -> Result<()> {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        #[cfg(feature = "mp4-native")]
+        if crate::mp4_demux::split_chunk(&self.input_path, &chunk.path, chunk.start_time, chunk.end_time).is_ok() {
+            callback(ProcessingEvent::Progress {
+                phase: 1,
+                current: 100,
+                total: 100,
+                message: format!("Split chunk {} via native mp4 reader", chunk.id + 1),
+            });
+            return Ok(());
+        }
 
         let start_time = format!("{:.3}", chunk.start_time);
         let duration = format!("{:.3}", chunk.duration);
 
-        let output = Command::new("ffmpeg")
+        let mut child = Command::new("ffmpeg")
             .arg("-i")
             .arg(&self.input_path)
             .arg("-ss")
@@ -226,16 +521,187 @@ impl VideoProcessor {
             .arg("-avoid_negative_ts")
             .arg("make_zero")
             .arg("-y")
+            .arg("-progress")
+            .arg("pipe:1")
+            .arg("-nostats")
             .arg(&chunk.path)
-            .output()
-            .map_err(|e| anyhow!("Failed to execute ffmpeg: {}", e))?;
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to spawn ffmpeg: {}", e))?;
+
+        let stdout = child.stdout.take().expect("ffmpeg stdout was piped via Stdio::piped()");
+        let stderr_pipe = child.stderr.take().expect("ffmpeg stderr was piped via Stdio::piped()");
+
+        std::thread::scope(|scope| -> Result<()> {
+            scope.spawn(move || {
+                let reader = BufReader::new(stdout);
+                for line in reader.lines().map_while(|l| l.ok()) {
+                    let Some(value) = line.strip_prefix("out_time_ms=") else { continue };
+                    let Ok(micros) = value.trim().parse::<i64>() else { continue };
+
+                    let elapsed_secs = (micros.max(0) as f64) / 1_000_000.0;
+                    let pct = if chunk.duration > 0.0 {
+                        ((elapsed_secs / chunk.duration) * 100.0).clamp(0.0, 100.0) as usize
+                    } else {
+                        100
+                    };
+
+                    callback(ProcessingEvent::Progress {
+                        phase: 1,
+                        current: pct,
+                        total: 100,
+                        message: format!("Splitting chunk {}: {:.1}s/{:.1}s", chunk.id + 1, elapsed_secs, chunk.duration),
+                    });
+                }
+            });
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("FFmpeg failed: {}", stderr));
+            let stderr_bytes = stream_tool_stderr(stderr_pipe, "ffmpeg", Some(chunk.id), self.error_handler.as_ref(), callback);
+
+            let status = child.wait().map_err(|e| anyhow!("Failed to wait on ffmpeg: {}", e))?;
+
+            if !status.success() {
+                let stderr = StringOrBytes::from_raw(stderr_bytes);
+                return Err(anyhow!(
+                    "FFmpeg failed for chunk {} (exit {:?}):\n{}",
+                    chunk.id + 1,
+                    status.code(),
+                    stderr.last_lines(20)
+                ));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Either a valid UTF-8 string or the raw bytes, for capturing ffmpeg
+/// stderr: most builds emit UTF-8 diagnostics, validated here via
+/// `simdutf8`'s fast path, but a log line can legitimately contain
+/// non-UTF8 bytes (an unusual filename echoed back) that would otherwise
+/// panic a naive `String::from_utf8`. Mirrors Av1an's `StringOrBytes`.
+#[derive(Debug, Clone)]
+enum StringOrBytes {
+    Utf8(String),
+    Bytes(Vec<u8>),
+}
+
+impl StringOrBytes {
+    fn from_raw(bytes: Vec<u8>) -> Self {
+        match simdutf8::basic::from_utf8(&bytes) {
+            Ok(s) => StringOrBytes::Utf8(s.to_string()),
+            Err(_) => StringOrBytes::Bytes(bytes),
         }
+    }
 
-        Ok(())
+    /// Lossy string view: non-UTF8 bytes are converted just for display,
+    /// same as `last_lines` already did inline before this was pulled out
+    /// so `stream_tool_stderr` could reuse it per-line.
+    fn as_str_lossy(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            StringOrBytes::Utf8(s) => std::borrow::Cow::Borrowed(s.as_str()),
+            StringOrBytes::Bytes(b) => String::from_utf8_lossy(b),
+        }
+    }
+
+    /// The last `n` lines, for attaching to an error.
+    fn last_lines(&self, n: usize) -> String {
+        let text = self.as_str_lossy();
+        let mut lines: Vec<&str> = text.lines().collect();
+        if lines.len() > n {
+            lines = lines.split_off(lines.len() - n);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Rough severity for one line of ffmpeg/ffprobe stderr - enough to route
+/// it without flooding `processing.log` with every routine line: anything
+/// mentioning "error" is treated as an error, "warning"/"deprecated" as a
+/// warning (dropped frames, timestamp resets), everything else as routine
+/// progress/info chatter.
+enum ToolLineSeverity {
+    Error,
+    Warning,
+    Progress,
+}
+
+fn classify_tool_line(line: &str) -> ToolLineSeverity {
+    let lower = line.to_lowercase();
+    if lower.contains("error") {
+        ToolLineSeverity::Error
+    } else if lower.contains("warning") || lower.contains("deprecated") {
+        ToolLineSeverity::Warning
+    } else {
+        ToolLineSeverity::Progress
+    }
+}
+
+/// Classify one stderr line from `tool` and route it to both
+/// `ErrorHandler` (so it lands in `processing.log`, same as any other
+/// ffmpeg failure) and `ProcessingEvent::ExternalToolOutput` (so the TUI
+/// and `--stream` JSON consumers actually see it) - this is what turns the
+/// "FFmpeg stderr output not captured" item `check_for_thread_output_leaks`
+/// only described into something that's actually wired up.
+fn report_tool_line(
+    line: &str,
+    tool: &str,
+    chunk_id: Option<usize>,
+    error_handler: Option<&Arc<ErrorHandler>>,
+    callback: &EventCallback,
+) {
+    let line = line.trim_end();
+    if line.is_empty() {
+        return;
+    }
+
+    if let Some(handler) = error_handler {
+        match classify_tool_line(line) {
+            ToolLineSeverity::Error | ToolLineSeverity::Warning => {
+                handler.handle_ffmpeg_error(chunk_id.unwrap_or(0), tool, line);
+            }
+            ToolLineSeverity::Progress => {
+                handler.log_debug(tool, line);
+            }
+        }
+    }
+
+    callback(ProcessingEvent::ExternalToolOutput {
+        tool: tool.to_string(),
+        chunk_id,
+        line: line.to_string(),
+    });
+}
+
+/// Read `stderr` line-by-line as the child produces it, rather than only
+/// being able to see it once the whole chunk is stuck or already failed -
+/// each line is classified and reported via `report_tool_line`. Returns the
+/// raw accumulated bytes so a caller can still build a `StringOrBytes` for
+/// a failure message out of them.
+fn stream_tool_stderr(
+    stderr: std::process::ChildStderr,
+    tool: &str,
+    chunk_id: Option<usize>,
+    error_handler: Option<&Arc<ErrorHandler>>,
+    callback: &EventCallback,
+) -> Vec<u8> {
+    use std::io::BufRead;
+
+    let mut reader = std::io::BufReader::new(stderr);
+    let mut raw = Vec::new();
+    let mut line_bytes = Vec::new();
+
+    loop {
+        line_bytes.clear();
+        match reader.read_until(b'\n', &mut line_bytes) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                raw.extend_from_slice(&line_bytes);
+                let text = StringOrBytes::from_raw(line_bytes.clone());
+                report_tool_line(&text.as_str_lossy(), tool, chunk_id, error_handler, callback);
+            }
+        }
     }
 
+    raw
 }
\ No newline at end of file
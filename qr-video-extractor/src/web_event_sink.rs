@@ -0,0 +1,104 @@
+use crate::events::{EventCallback, ProcessingEvent};
+use anyhow::Result;
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use futures::stream::{unfold, Stream};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// A client slow enough to fall behind by this many events gets the oldest
+/// ones dropped instead of ever blocking a sender.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Fans `ProcessingEvent`s out over HTTP, the network counterpart to the
+/// in-process `OutputHandler` trait: `GET /events` is a Server-Sent-Events
+/// stream of every event as it happens, and `GET /status` returns the most
+/// recent one as a JSON snapshot for a client that just connected. Built on
+/// a bounded broadcast channel so a stalled browser tab can't back up and
+/// stall the processing threads that call the returned callback.
+pub struct WebEventSink {
+    tx: broadcast::Sender<String>,
+    last_event: Mutex<Option<String>>,
+}
+
+impl WebEventSink {
+    /// Bind `addr` and start serving in a background thread, returning a
+    /// sink whose `callback()` composes into the same closure chain as
+    /// `ConsoleOutputHandler`/`IndicatifOutputHandler`.
+    pub fn spawn(addr: String) -> Result<Arc<Self>> {
+        let (tx, _rx) = broadcast::channel(CHANNEL_CAPACITY);
+        let sink = Arc::new(Self {
+            tx,
+            last_event: Mutex::new(None),
+        });
+
+        let server_sink = Arc::clone(&sink);
+        std::thread::Builder::new()
+            .name("web-events".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Runtime::new() {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        eprintln!("⚠️  Failed to start --web-events runtime: {}", e);
+                        return;
+                    }
+                };
+                if let Err(e) = runtime.block_on(serve(addr, server_sink)) {
+                    eprintln!("⚠️  --web-events server stopped: {}", e);
+                }
+            })?;
+
+        Ok(sink)
+    }
+
+    /// An `EventCallback` that serializes each event to JSON, remembers it
+    /// for `/status`, and broadcasts it to every connected SSE client.
+    /// `send` only errors when there are zero receivers, which just means
+    /// nobody's watching yet - not a failure worth surfacing.
+    pub fn callback(self: &Arc<Self>) -> EventCallback {
+        let sink = Arc::clone(self);
+        Box::new(move |event: ProcessingEvent| {
+            if let Ok(json) = serde_json::to_string(&event) {
+                *sink.last_event.lock().unwrap() = Some(json.clone());
+                let _ = sink.tx.send(json);
+            }
+        })
+    }
+}
+
+async fn serve(addr: String, sink: Arc<WebEventSink>) -> Result<()> {
+    let app = Router::new()
+        .route("/events", get(events_stream))
+        .route("/status", get(status))
+        .with_state(sink);
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("🌐 Streaming ProcessingEvents on http://{}/events", addr);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn events_stream(
+    State(sink): State<Arc<WebEventSink>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = sink.tx.subscribe();
+    let stream = unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(json) => return Some((Ok(Event::default().data(json)), rx)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn status(State(sink): State<Arc<WebEventSink>>) -> Json<serde_json::Value> {
+    let last_event = sink.last_event.lock().unwrap().clone();
+    Json(serde_json::json!({ "lastEvent": last_event }))
+}